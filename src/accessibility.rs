@@ -0,0 +1,86 @@
+use crate::{CancellationToken, Device, Endpoint, Error, Key, Result};
+use std::time::Duration;
+use tokio::time::sleep;
+
+const VERIFY_POLL: Duration = Duration::from_millis(250);
+const VERIFY_ATTEMPTS: u32 = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Shortcut {
+    pub key: Key,
+    pub presses: u32,
+    pub interval: Duration,
+}
+
+impl Shortcut {
+    pub const AUDIO_GUIDE: Shortcut = Shortcut {
+        key: Key::Info,
+        presses: 4,
+        interval: Duration::from_millis(150),
+    };
+
+    pub fn new(key: Key, presses: u32) -> Shortcut {
+        Shortcut {
+            key,
+            presses,
+            interval: Shortcut::AUDIO_GUIDE.interval,
+        }
+    }
+}
+
+impl Device {
+    pub async fn shortcut(&self, shortcut: &Shortcut) -> Result<()> {
+        self.keypress_repeat(
+            &shortcut.key,
+            shortcut.presses,
+            shortcut.interval,
+            &CancellationToken::new(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn audio_guide_enabled(&self) -> Result<Option<bool>> {
+        self.invalidate(Endpoint::DeviceInfo);
+        Ok(self.device_info().await?.audio_guide_enabled)
+    }
+
+    pub async fn toggle_audio_guide(&self) -> Result<Option<bool>> {
+        self.invalidate(Endpoint::DeviceInfo);
+        let info = self.device_info().await?;
+        if !info.supports_audio_guide {
+            return Err(Error::Argument(
+                "device does not support Audio Guide".to_string(),
+            ));
+        }
+        self.shortcut(&Shortcut::AUDIO_GUIDE).await?;
+        match info.audio_guide_enabled {
+            Some(enabled) => self.verify_audio_guide(!enabled).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn set_audio_guide(&self, enabled: bool) -> Result<bool> {
+        let current = self.audio_guide_enabled().await?.ok_or_else(|| {
+            Error::Argument("device does not report Audio Guide state".to_string())
+        })?;
+        if current == enabled {
+            return Ok(false);
+        }
+        self.toggle_audio_guide().await?;
+        Ok(true)
+    }
+
+    async fn verify_audio_guide(&self, expected: bool) -> Result<bool> {
+        for _ in 0..VERIFY_ATTEMPTS {
+            sleep(VERIFY_POLL).await;
+            if self.audio_guide_enabled().await? == Some(expected) {
+                return Ok(expected);
+            }
+        }
+        Err(Error::Response(format!(
+            "Audio Guide still {} after shortcut",
+            if expected { "off" } else { "on" }
+        )))
+    }
+}
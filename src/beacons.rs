@@ -0,0 +1,118 @@
+use crate::{FwBeacon, FwBeacons};
+use serde::{Deserialize, Serialize};
+use std::{
+    convert::Infallible,
+    fmt,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum BeaconEvent {
+    AppLaunchInitiate,
+    AppCompileComplete,
+    AppSplashInitiate,
+    AppSplashComplete,
+    AppLaunchComplete,
+    AppDialogInitiate,
+    AppDialogComplete,
+    AppExit,
+    Other(String),
+}
+
+impl BeaconEvent {
+    fn name(&self) -> &str {
+        match self {
+            BeaconEvent::AppLaunchInitiate => "AppLaunchInitiate",
+            BeaconEvent::AppCompileComplete => "AppCompileComplete",
+            BeaconEvent::AppSplashInitiate => "AppSplashInitiate",
+            BeaconEvent::AppSplashComplete => "AppSplashComplete",
+            BeaconEvent::AppLaunchComplete => "AppLaunchComplete",
+            BeaconEvent::AppDialogInitiate => "AppDialogInitiate",
+            BeaconEvent::AppDialogComplete => "AppDialogComplete",
+            BeaconEvent::AppExit => "AppExit",
+            BeaconEvent::Other(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for BeaconEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for BeaconEvent {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<BeaconEvent, Infallible> {
+        Ok(match s.trim() {
+            "AppLaunchInitiate" => BeaconEvent::AppLaunchInitiate,
+            "AppCompileComplete" => BeaconEvent::AppCompileComplete,
+            "AppSplashInitiate" => BeaconEvent::AppSplashInitiate,
+            "AppSplashComplete" => BeaconEvent::AppSplashComplete,
+            "AppLaunchComplete" => BeaconEvent::AppLaunchComplete,
+            "AppDialogInitiate" => BeaconEvent::AppDialogInitiate,
+            "AppDialogComplete" => BeaconEvent::AppDialogComplete,
+            "AppExit" => BeaconEvent::AppExit,
+            other => BeaconEvent::Other(other.to_string()),
+        })
+    }
+}
+
+impl From<String> for BeaconEvent {
+    fn from(name: String) -> BeaconEvent {
+        match name.parse() {
+            Ok(event) => event,
+            Err(never) => match never {},
+        }
+    }
+}
+
+impl From<BeaconEvent> for String {
+    fn from(event: BeaconEvent) -> String {
+        event.to_string()
+    }
+}
+
+impl FwBeacon {
+    pub fn time(&self) -> Option<SystemTime> {
+        Some(UNIX_EPOCH + Duration::from_millis(self.timestamp?))
+    }
+}
+
+impl FwBeacons {
+    pub fn iter(&self) -> impl Iterator<Item = &FwBeacon> {
+        self.data.beacons.iter()
+    }
+
+    pub fn between(&self, start: &BeaconEvent, end: &BeaconEvent) -> Option<Duration> {
+        let mut started = None;
+        let mut elapsed = None;
+        for beacon in self.iter() {
+            if &beacon.event == start {
+                started = beacon.timestamp;
+            } else if &beacon.event == end {
+                if let (Some(started), Some(ended)) = (started, beacon.timestamp) {
+                    elapsed = Some(Duration::from_millis(ended.saturating_sub(started)));
+                }
+            }
+        }
+        elapsed
+    }
+
+    pub fn launch_time(&self) -> Option<Duration> {
+        self.between(
+            &BeaconEvent::AppLaunchInitiate,
+            &BeaconEvent::AppLaunchComplete,
+        )
+    }
+
+    pub fn dialog_time(&self) -> Option<Duration> {
+        self.between(
+            &BeaconEvent::AppDialogInitiate,
+            &BeaconEvent::AppDialogComplete,
+        )
+    }
+}
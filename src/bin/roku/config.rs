@@ -0,0 +1,94 @@
+use roku::{Error, Profile, Profiles};
+use serde::Deserialize;
+use std::{collections::HashMap, env, error::Error as StdError, fs, io, path::PathBuf};
+
+#[derive(Default, Deserialize)]
+pub struct Config {
+    pub default: Option<String>,
+    pub password: Option<String>,
+    #[serde(default)]
+    pub devices: Profiles,
+    #[serde(default)]
+    pub groups: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub captions: HashMap<String, u32>,
+}
+
+pub enum Target {
+    Host(String),
+    Profile(Profile),
+}
+
+impl Config {
+    pub fn load(path: Option<PathBuf>) -> Result<Config, Box<dyn StdError>> {
+        let explicit = path.is_some();
+        let path = match path.or_else(default_path) {
+            Some(path) => path,
+            None => return Ok(Config::default()),
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => toml::from_str(&text)
+                .map_err(|err| format!("failed to parse {}: {}", path.display(), err).into()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound && !explicit => Ok(Config::default()),
+            Err(err) => Err(format!("failed to read {}: {}", path.display(), err).into()),
+        }
+    }
+
+    pub fn targets(&self, device: Option<&str>) -> Result<Vec<Target>, Error> {
+        let name = device.or(self.default.as_deref());
+        match name.and_then(|name| self.groups.get(name)) {
+            Some(members) => members
+                .iter()
+                .filter_map(|member| self.target(Some(member)).transpose())
+                .collect(),
+            None => Ok(self.target(device)?.into_iter().collect()),
+        }
+    }
+
+    pub fn password(&self, device: Option<&str>) -> Option<String> {
+        let name = device.or(self.default.as_deref());
+        name.and_then(|name| self.devices.get(name))
+            .and_then(|profile| profile.password.clone())
+            .or_else(|| self.password.clone())
+    }
+
+    pub fn target(&self, device: Option<&str>) -> Result<Option<Target>, Error> {
+        let name = match device.or(self.default.as_deref()) {
+            Some(name) => name,
+            None => return Ok(None),
+        };
+        Ok(Some(match self.devices.get(name) {
+            None => Target::Host(name.to_string()),
+            Some(profile) if profile.host.is_some() || profile.serial.is_some() => {
+                Target::Profile(profile.clone())
+            }
+            Some(_) => {
+                return Err(Error::Argument(format!(
+                    "device {} needs a host or serial",
+                    name
+                )))
+            }
+        }))
+    }
+}
+
+pub fn jobs_path() -> Option<PathBuf> {
+    Some(config_dir()?.join("jobs.json"))
+}
+
+#[cfg(feature = "repl")]
+pub fn history_path() -> Option<PathBuf> {
+    Some(config_dir()?.join("history"))
+}
+
+fn default_path() -> Option<PathBuf> {
+    Some(config_dir()?.join("config.toml"))
+}
+
+fn config_dir() -> Option<PathBuf> {
+    let dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+    Some(dir.join("roku"))
+}
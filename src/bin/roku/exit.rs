@@ -0,0 +1,66 @@
+use reqwest::StatusCode;
+use roku::Error;
+use std::{error::Error as StdError, fmt};
+
+pub const FAILURE: i32 = 1;
+pub const UNREACHABLE: i32 = 3;
+pub const RESTRICTED: i32 = 4;
+pub const NOT_FOUND: i32 = 5;
+pub const INTERRUPTED: i32 = 130;
+
+pub const HELP: &str = "Exit codes:
+  0    success
+  1    other failure
+  2    usage error
+  3    device not found or unreachable
+  4    control restricted by the device's network access setting
+  5    app or endpoint not found
+  130  interrupted with Ctrl-C (held keys are released first)";
+
+#[derive(Debug)]
+pub struct NoDevice(pub String);
+
+impl fmt::Display for NoDevice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl StdError for NoDevice {}
+
+#[derive(Debug)]
+pub struct Interrupted;
+
+impl fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "interrupted")
+    }
+}
+
+impl StdError for Interrupted {}
+
+pub fn code(err: &(dyn StdError + 'static)) -> i32 {
+    if err.is::<NoDevice>() {
+        return UNREACHABLE;
+    }
+    if err.is::<Interrupted>() {
+        return INTERRUPTED;
+    }
+    match err.downcast_ref::<Error>() {
+        Some(Error::Request(err)) if err.is_connect() || err.is_timeout() => UNREACHABLE,
+        Some(Error::Request(err)) => match err.status() {
+            Some(StatusCode::FORBIDDEN) => RESTRICTED,
+            Some(StatusCode::NOT_FOUND) => NOT_FOUND,
+            _ => FAILURE,
+        },
+        Some(Error::Ecp(err)) => match StatusCode::from_u16(err.code) {
+            Ok(StatusCode::FORBIDDEN) => RESTRICTED,
+            Ok(StatusCode::NOT_FOUND) => NOT_FOUND,
+            _ => FAILURE,
+        },
+        Some(Error::SSDPRequest(_)) => UNREACHABLE,
+        Some(Error::NotFound(_)) => NOT_FOUND,
+        Some(Error::Incomplete { source, .. }) => code(&**source),
+        _ => FAILURE,
+    }
+}
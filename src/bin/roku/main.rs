@@ -0,0 +1,1472 @@
+use clap::{Parser, Subcommand};
+use futures_util::StreamExt;
+use roku::{
+    doctor, fan_out, parse_duration, profiles::host_url, App, CancellationToken, Capabilities,
+    CaptionToggle, ChannelScan, CheckStatus, DeepLink, DeepLinkType, DevServer, Device,
+    DeviceClass, Direction, Error, FileServer, FwBeacons, GridKeyboard, InstallQueue,
+    InstallStatus, Key, LaunchParams, Media, Node, PerfSession, RawResponse, Scheduler, Script,
+    Search, SearchType, SecretScreen, SgRendezvous, SideloadResult, Stats, TvActiveChannel,
+    TvChannels,
+};
+use std::{
+    error::Error as StdError,
+    fs,
+    future::Future,
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, SystemTime},
+};
+use tokio::time::sleep;
+use url::Url;
+
+use config::{Config, Target};
+use exit::{Interrupted, NoDevice};
+use output::Format;
+
+mod config;
+mod exit;
+mod output;
+#[cfg(feature = "tui")]
+mod remote;
+#[cfg(feature = "repl")]
+mod repl;
+
+#[derive(Parser)]
+#[command(
+    name = "roku",
+    version,
+    about = "Control Roku devices over ECP",
+    after_help = exit::HELP
+)]
+struct Cli {
+    /// Device alias, IP, host[:port][/prefix] or ECP URL (discovered if omitted)
+    #[arg(short, long, global = true)]
+    device: Option<String>,
+    /// Config file [default: ~/.config/roku/config.toml]
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+    /// Print query results as JSON
+    #[arg(long, global = true, conflicts_with = "yaml")]
+    json: bool,
+    /// Print query results as YAML
+    #[arg(long, global = true, conflicts_with = "porcelain")]
+    yaml: bool,
+    /// Print query results as stable tab-separated lines
+    #[arg(long, global = true, conflicts_with = "json")]
+    porcelain: bool,
+    /// Print nothing on success; rely on the exit code
+    #[arg(short, long, global = true)]
+    quiet: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List devices found on the local network
+    Discover,
+    /// Diagnose discovery and connection problems
+    Doctor,
+    /// Show device information
+    Info,
+    /// List installed channels
+    Apps,
+    /// Show the active channel
+    Active,
+    /// Probe which ECP queries the device's firmware supports
+    Capabilities,
+    /// Send a raw ECP request and print the response body
+    Raw {
+        /// Path on the device, e.g. query/device-info
+        path: String,
+        /// Send a POST instead of a GET
+        #[arg(short, long)]
+        post: bool,
+        /// Query parameter as key=value (POST only)
+        #[arg(long = "param", requires = "post")]
+        params: Vec<String>,
+    },
+    /// List the Live TV channel lineup (Roku TV)
+    TvChannels,
+    /// Show what's on the active Live TV channel (Roku TV)
+    TvNow,
+    /// Tune Live TV to a broadcast channel, e.g. 4.1 (Roku TV)
+    Tune { channel: String },
+    /// Compare a saved lineup (from `roku --json tv-channels`) with another or the device's
+    LineupDiff {
+        before: PathBuf,
+        /// Saved lineup to compare against [default: the device's current lineup]
+        after: Option<PathBuf>,
+    },
+    /// Pause Live TV on the tuner input (Roku TV with Live TV Pause)
+    LivePause,
+    /// Resume paused Live TV (Roku TV with Live TV Pause)
+    LiveResume,
+    /// Run a channel scan macro and wait for the lineup to settle (Roku TV)
+    Scan {
+        /// Macro script that navigates to and starts the scan, instead of the built-in
+        /// Settings > TV inputs > Live TV path
+        script: Option<PathBuf>,
+        /// Seconds to wait for the lineup to settle
+        #[arg(short, long, default_value_t = 1800)]
+        timeout: u64,
+    },
+    /// Launch a channel by id, optionally deep linking into content
+    Launch {
+        app_id: String,
+        /// Content to deep link to
+        #[arg(long)]
+        content_id: Option<String>,
+        /// Media type of the content, e.g. movie, episode, series, live
+        #[arg(long, requires = "content_id")]
+        media_type: Option<String>,
+        /// Extra launch parameter as key=value
+        #[arg(long = "param")]
+        params: Vec<String>,
+    },
+    /// Deep link the running channel to content
+    Link {
+        content_id: String,
+        /// movie, episode, season, series, shortFormVideo, tvSpecial, special or live
+        #[arg(long)]
+        media_type: Option<DeepLinkType>,
+        /// Extra parameter as key=value
+        #[arg(long = "param")]
+        params: Vec<String>,
+    },
+    /// Open a hidden diagnostic screen by entering its remote key sequence
+    Secret {
+        /// platform, wireless, channel-info, developer, reboot, reset, bitrate-override,
+        /// hdmi or antenna (the last two on Roku TVs only)
+        screen: SecretScreen,
+    },
+    /// Download a channel's icon
+    Icon {
+        app_id: String,
+        /// Output file [default: <app_id>.<ext>]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Install channels by id from the channel store, skipping installed ones
+    Install {
+        #[arg(required = true)]
+        app_ids: Vec<String>,
+        /// Don't press Select to confirm the channel store's add button
+        #[arg(long)]
+        no_confirm: bool,
+    },
+    /// Press one or more keys in order
+    Key {
+        #[arg(required = true)]
+        keys: Vec<Key>,
+        /// Press each key this many times
+        #[arg(short, long, default_value_t = 1)]
+        repeat: u32,
+        /// Seconds between repeated presses
+        #[arg(short, long, default_value = "0.2", value_parser = parse_duration)]
+        interval: Duration,
+    },
+    /// Move through long lists with accelerating arrow presses
+    Scroll {
+        /// up, down, left or right
+        direction: Direction,
+        #[arg(default_value_t = 1)]
+        count: u32,
+    },
+    /// Interactive remote control
+    #[cfg(feature = "tui")]
+    Remote,
+    /// Interactive prompt with history and tab completion
+    #[cfg(feature = "repl")]
+    Repl,
+    /// Play a media URL or local file with Play on Roku
+    Cast {
+        /// HTTP(S) URL or path to a local file
+        target: String,
+        #[arg(short, long)]
+        title: Option<String>,
+        /// Artwork URL shown while playing audio
+        #[arg(short, long)]
+        artwork: Option<Url>,
+        /// MIME type of the media, for URLs without a recognizable extension
+        #[arg(long)]
+        mime: Option<String>,
+        /// Play through the Roku Media Player channel instead of Play on Roku
+        #[arg(short, long)]
+        media_player: bool,
+        /// Port to serve local files on (0 picks a free port)
+        #[arg(short, long, default_value_t = 0)]
+        port: u16,
+    },
+    /// Run a macro script against a device or group
+    Run {
+        script: PathBuf,
+        /// Print the parsed steps without running them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Schedule a macro script to run against a device or group
+    Schedule {
+        /// YYYY-MM-DD HH:MM (once), HH:MM (daily) or a five-field cron expression
+        when: String,
+        script: PathBuf,
+    },
+    /// List scheduled jobs
+    Jobs {
+        /// Cancel the job with this id
+        #[arg(long)]
+        cancel: Option<u64>,
+    },
+    /// Run scheduled jobs until interrupted
+    Scheduler,
+    /// Save a screenshot of the sideloaded channel (developer mode)
+    Screenshot {
+        /// Output file [default: screenshot.<ext>]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Developer web server password
+        #[arg(long, env = "ROKU_DEV_PASSWORD", hide_env_values = true)]
+        password: Option<String>,
+    },
+    /// Sample channel CPU and memory usage and summarize it
+    Perf {
+        /// Seconds between samples
+        #[arg(short, long, default_value = "1", value_parser = parse_duration)]
+        interval: Duration,
+        /// Seconds to sample for (Ctrl-C stops early)
+        #[arg(short = 't', long, default_value = "60", value_parser = parse_duration)]
+        duration: Duration,
+        /// Write the samples to a .csv or .json file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Dump the SceneGraph node tree of the running channel
+    Sgnodes {
+        /// Only the root nodes
+        #[arg(long, conflicts_with = "node")]
+        roots: bool,
+        /// Only the node with this id and its children
+        #[arg(long)]
+        node: Option<String>,
+    },
+    /// Show texture memory used by the running channel's bitmaps
+    Bitmaps {
+        /// Only list the largest bitmaps
+        #[arg(short, long)]
+        top: Option<usize>,
+    },
+    /// Show SceneGraph rendezvous tracked for the running channel
+    Rendezvous {
+        /// Start tracking rendezvous first
+        #[arg(long, conflicts_with = "untrack")]
+        track: bool,
+        /// Stop tracking rendezvous
+        #[arg(long)]
+        untrack: bool,
+    },
+    /// Show firmware launch beacons for channel launch timing
+    Beacons {
+        /// Start tracking beacons first
+        #[arg(long, conflicts_with = "untrack")]
+        track: bool,
+        /// Stop tracking beacons
+        #[arg(long)]
+        untrack: bool,
+    },
+    /// Show a channel's registry sections (the sideloaded channel by default)
+    Registry {
+        /// Channel id to read the registry of
+        #[arg(long)]
+        app: Option<String>,
+    },
+    /// Download BrightScript profiler data for the sideloaded channel
+    Profile {
+        /// Output file [default: profile.bsprof]
+        #[arg(short, long, default_value = "profile.bsprof")]
+        output: PathBuf,
+        /// Developer web server password
+        #[arg(long, env = "ROKU_DEV_PASSWORD", hide_env_values = true)]
+        password: Option<String>,
+    },
+    /// Zip a channel source directory, sideload it and launch it
+    Deploy {
+        #[arg(default_value = ".")]
+        source_dir: PathBuf,
+        /// Developer web server password
+        #[arg(long, env = "ROKU_DEV_PASSWORD", hide_env_values = true)]
+        password: Option<String>,
+    },
+    /// Install a channel archive on the developer web server
+    Sideload {
+        #[arg(required_unless_present = "delete")]
+        archive: Option<PathBuf>,
+        /// Delete the sideloaded channel instead
+        #[arg(long, conflicts_with = "archive")]
+        delete: bool,
+        /// Developer web server password
+        #[arg(long, env = "ROKU_DEV_PASSWORD", hide_env_values = true)]
+        password: Option<String>,
+    },
+    /// Package the sideloaded channel into a signed .pkg
+    Package {
+        /// Channel name and version, e.g. "My Channel/1.0.0"
+        app_name: String,
+        /// Output file [default: package.pkg]
+        #[arg(short, long, default_value = "package.pkg")]
+        output: PathBuf,
+        /// Signing key password
+        #[arg(long, env = "ROKU_SIGNING_PASSWORD", hide_env_values = true)]
+        signing_password: String,
+        /// Developer web server password
+        #[arg(long, env = "ROKU_DEV_PASSWORD", hide_env_values = true)]
+        password: Option<String>,
+    },
+    /// Rekey the device with the signing key of an existing .pkg
+    Rekey {
+        pkg: PathBuf,
+        /// Signing key password
+        #[arg(long, env = "ROKU_SIGNING_PASSWORD", hide_env_values = true)]
+        signing_password: String,
+        /// Developer web server password
+        #[arg(long, env = "ROKU_DEV_PASSWORD", hide_env_values = true)]
+        password: Option<String>,
+    },
+    /// Tail the BrightScript debug console (developer mode)
+    Logs,
+    /// Run a command on the port 8080 diagnostic shell (developer mode)
+    Shell {
+        #[arg(required = true)]
+        command: Vec<String>,
+    },
+    /// Stream device events as line-delimited JSON
+    Watch {
+        /// Seconds between polls
+        #[arg(short, long, default_value = "1", value_parser = parse_duration)]
+        interval: Duration,
+    },
+    /// Toggle closed captions through the Options (*) menu during playback
+    Captions {
+        /// Down presses from the top of the Options menu to the captions entry
+        #[arg(short, long)]
+        offset: Option<u32>,
+        /// Right presses used to cycle the captions setting
+        #[arg(short, long, default_value_t = 1)]
+        presses: u32,
+    },
+    /// Type text into the focused text field
+    Type {
+        text: String,
+        /// Press Enter after typing
+        #[arg(short, long)]
+        enter: bool,
+        /// Seconds between characters
+        #[arg(short, long, default_value = "0.1", value_parser = parse_duration)]
+        interval: Duration,
+        /// Spell the text on an on-screen keyboard row by row instead (⇧ marks shift)
+        #[arg(short, long = "row")]
+        rows: Vec<String>,
+        /// The on-screen keyboard wraps around at its edges
+        #[arg(short, long, requires = "rows")]
+        wrap: bool,
+    },
+    /// Open the search UI for a keyword
+    Search {
+        keyword: String,
+        #[arg(short = 't', long = "type")]
+        search_type: Option<SearchType>,
+        #[arg(long)]
+        provider: Vec<String>,
+        #[arg(long)]
+        provider_id: Vec<String>,
+        #[arg(long)]
+        season: Option<u32>,
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long)]
+        tmsid: Option<String>,
+        #[arg(long)]
+        launch: bool,
+        #[arg(long)]
+        match_any: bool,
+        #[arg(long)]
+        show_unavailable: bool,
+        /// Map --provider names to ids using the installed channels
+        #[arg(long)]
+        resolve: bool,
+        /// Only search channels installed on the device
+        #[arg(long)]
+        installed_only: bool,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    if let Err(err) = run(cli).await {
+        eprintln!("error: {}", err);
+        let mut source = err.source();
+        while let Some(err) = source {
+            eprintln!("  caused by: {}", err);
+            source = err.source();
+        }
+        process::exit(exit::code(&*err));
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Box<dyn StdError>> {
+    let format = Format::new(cli.json, cli.yaml, cli.porcelain, cli.quiet);
+    if let Command::Discover = cli.command {
+        let devices = Device::discover().await?;
+        let urls: Vec<&str> = devices.iter().map(|device| device.url().as_str()).collect();
+        let print = |urls: &Vec<&str>| {
+            for url in urls {
+                println!("{}", url);
+            }
+        };
+        return format.emit(&urls, print, print);
+    }
+    let config = Config::load(cli.config)?;
+    if let Command::Doctor = cli.command {
+        let device = match config.target(cli.device.as_deref())? {
+            Some(target) => Some(resolve(target).await?),
+            None => None,
+        };
+        let report = doctor(device.as_ref()).await;
+        format.emit(
+            &report,
+            |report| {
+                for check in &report.checks {
+                    println!(
+                        "{:<4}  {:<9} {}",
+                        status(check.status),
+                        check.name,
+                        check.detail
+                    );
+                }
+            },
+            |report| {
+                for check in &report.checks {
+                    println!("{}\t{}\t{}", check.name, status(check.status), check.detail);
+                }
+            },
+        )?;
+        if !report.is_ok() {
+            return Err("some checks failed".into());
+        }
+        return Ok(());
+    }
+    if let Command::Run { script, dry_run } = &cli.command {
+        let text = fs::read_to_string(script)
+            .map_err(|err| format!("failed to read {}: {}", script.display(), err))?;
+        let script = Script::parse(&text)?;
+        if *dry_run {
+            for step in script.steps() {
+                println!("{}", step);
+            }
+            return Ok(());
+        }
+        let mut devices = vec![];
+        for target in config.targets(cli.device.as_deref())? {
+            devices.push(resolve(target).await?);
+        }
+        if devices.is_empty() {
+            devices.push(discover_one().await?);
+        }
+        let results = interruptible(&devices, async {
+            Ok(fan_out(&devices, |device| {
+                let script = &script;
+                async move { script.run(&device).await }
+            })
+            .await)
+        })
+        .await?;
+        for (device, err) in &results.failures {
+            eprintln!("{}: {}", device.url(), err);
+        }
+        if !results.is_ok() {
+            return Err(format!(
+                "{} of {} devices failed",
+                results.failures.len(),
+                results.len()
+            )
+            .into());
+        }
+        return Ok(());
+    }
+    match &cli.command {
+        Command::Schedule { when, script } => {
+            let text = fs::read_to_string(script)
+                .map_err(|err| format!("failed to read {}: {}", script.display(), err))?;
+            let script = Script::parse(&text)?;
+            let mut devices = vec![];
+            for target in config.targets(cli.device.as_deref())? {
+                devices.push(resolve(target).await?);
+            }
+            if devices.is_empty() {
+                devices.push(discover_one().await?);
+            }
+            let id = scheduler()?.at(when, &devices, &script)?;
+            format.status(&format!("scheduled job {}", id));
+            return Ok(());
+        }
+        Command::Jobs { cancel: Some(id) } => {
+            if !scheduler()?.cancel(*id)? {
+                return Err(format!("no job {}", id).into());
+            }
+            return Ok(());
+        }
+        Command::Jobs { cancel: None } => {
+            let scheduler = scheduler()?;
+            return format.emit(
+                &scheduler.jobs(),
+                |jobs| {
+                    for job in jobs.iter() {
+                        println!(
+                            "{:>4}  {:<16}  {}",
+                            job.id,
+                            job.when,
+                            job.devices.join(", ")
+                        );
+                    }
+                },
+                |jobs| {
+                    for job in jobs.iter() {
+                        println!("{}\t{}\t{}", job.id, job.when, job.devices.join(","));
+                    }
+                },
+            );
+        }
+        Command::Scheduler => {
+            let cancel = CancellationToken::new();
+            let ctrl_c = cancel.clone();
+            tokio::spawn(async move {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    ctrl_c.cancel();
+                }
+            });
+            scheduler()?
+                .run(&cancel, |job, device, result| match result {
+                    Ok(()) => format.status(&format!("job {} ran on {}", job.id, device)),
+                    Err(err) => eprintln!("job {} failed on {}: {}", job.id, device, err),
+                })
+                .await?;
+            return Ok(());
+        }
+        _ => {}
+    }
+    let target = config.target(cli.device.as_deref())?;
+    #[cfg(feature = "tui")]
+    if let Command::Remote = cli.command {
+        let devices = match target {
+            Some(target) => vec![resolve(target).await?],
+            None => Device::discover().await?,
+        };
+        if devices.is_empty() {
+            return Err(NoDevice("no devices found".to_string()).into());
+        }
+        return remote::run(devices).await;
+    }
+    let device = match target {
+        Some(target) => resolve(target).await?,
+        None => discover_one().await?,
+    };
+    match cli.command {
+        Command::Discover | Command::Doctor => unreachable!(),
+        #[cfg(feature = "tui")]
+        Command::Remote => unreachable!(),
+        #[cfg(feature = "repl")]
+        Command::Repl => repl::run(&device, config::history_path().as_deref()).await?,
+        Command::Run { .. } => unreachable!(),
+        Command::Schedule { .. } | Command::Jobs { .. } | Command::Scheduler => unreachable!(),
+        Command::Info => {
+            format.emit(
+                &device.device_info().await?,
+                |info| {
+                    println!("name:     {}", info.user_device_name);
+                    println!("model:    {} ({})", info.model_name, info.model_number);
+                    println!("class:    {}", DeviceClass::from_info(info));
+                    println!("serial:   {}", info.serial_number);
+                    println!(
+                        "software: {} build {}",
+                        info.software_version, info.software_build
+                    );
+                    println!("network:  {} ({})", info.network_name, info.network_type);
+                    println!("power:    {}", info.power_mode);
+                },
+                |info| {
+                    println!("user_device_name\t{}", info.user_device_name);
+                    println!("model_name\t{}", info.model_name);
+                    println!("model_number\t{}", info.model_number);
+                    println!("serial_number\t{}", info.serial_number);
+                    println!("software_version\t{}", info.software_version);
+                    println!("software_build\t{}", info.software_build);
+                    println!("network_name\t{}", info.network_name);
+                    println!("network_type\t{}", info.network_type);
+                    println!("power_mode\t{}", info.power_mode);
+                },
+            )?;
+        }
+        Command::Apps => {
+            format.emit(
+                &device.apps().await?,
+                |apps| {
+                    for app in &apps.apps {
+                        println!("{:>8}  {}", app.id.as_deref().unwrap_or("-"), app.name);
+                    }
+                },
+                |apps| {
+                    for app in &apps.apps {
+                        println!("{}\t{}", app.id.as_deref().unwrap_or_default(), app.name);
+                    }
+                },
+            )?;
+        }
+        Command::TvChannels => {
+            format.emit(&device.tv_channels().await?, print_lineup, |lineup| {
+                for channel in &lineup.channels {
+                    println!(
+                        "{}\t{}\t{}\t{}",
+                        channel.number, channel.name, channel.channel_type, channel.user_hidden
+                    );
+                }
+            })?;
+        }
+        Command::Capabilities => {
+            let print = |capabilities: &Capabilities| {
+                for (capability, supported) in capabilities.iter() {
+                    println!("{}\t{}", capability, supported);
+                }
+            };
+            format.emit(&device.capabilities_probe().await?, print, print)?;
+        }
+        Command::Raw { path, post, params } => {
+            let response = if post {
+                let params = params
+                    .iter()
+                    .map(|param| {
+                        param
+                            .split_once('=')
+                            .ok_or_else(|| format!("expected key=value, got {}", param))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                device.raw_post(&path, &params).await?
+            } else {
+                device.raw_get(&path).await?
+            };
+            let print = |response: &RawResponse| println!("{}", response.body.trim_end());
+            format.emit(&response, print, print)?;
+            response.error_for_status()?;
+        }
+        Command::Tune { channel } => device.tune(&channel).await?,
+        Command::TvNow => {
+            format.emit(&device.tv_active_channel().await?, print_now, |active| {
+                if let Some(channel) = &active.channel {
+                    println!(
+                        "{}\t{}\t{}\t{}\t{}",
+                        channel.number,
+                        channel.name,
+                        channel.program_title.as_deref().unwrap_or_default(),
+                        channel.program_start_time.unwrap_or_default(),
+                        channel.program_duration.unwrap_or_default()
+                    );
+                }
+            })?;
+        }
+        Command::LineupDiff { before, after } => {
+            let before = read_lineup(&before)?;
+            let after = match after {
+                Some(after) => read_lineup(&after)?,
+                None => device.tv_channels().await?,
+            };
+            format.emit(
+                &before.diff(&after),
+                |diff| {
+                    for channel in &diff.added {
+                        println!("+ {:>6}  {}", channel.number, channel.name);
+                    }
+                    for channel in &diff.removed {
+                        println!("- {:>6}  {}", channel.number, channel.name);
+                    }
+                    for (_, channel) in &diff.changed {
+                        println!("~ {:>6}  {}", channel.number, channel.name);
+                    }
+                },
+                |diff| {
+                    for channel in &diff.added {
+                        println!("added\t{}\t{}", channel.number, channel.name);
+                    }
+                    for channel in &diff.removed {
+                        println!("removed\t{}\t{}", channel.number, channel.name);
+                    }
+                    for (_, channel) in &diff.changed {
+                        println!("changed\t{}\t{}", channel.number, channel.name);
+                    }
+                },
+            )?;
+        }
+        Command::LivePause => device.pause_live_tv().await?,
+        Command::LiveResume => device.resume_live_tv().await?,
+        Command::Scan { script, timeout } => {
+            let mut scan = ChannelScan::new();
+            if let Some(script) = script {
+                let text = fs::read_to_string(&script)
+                    .map_err(|err| format!("failed to read {}: {}", script.display(), err))?;
+                scan.script(Script::parse(&text)?);
+            }
+            scan.timeout(Duration::from_secs(timeout));
+            let lineup = scan.run(&device).await?;
+            format.emit(&lineup, print_lineup, |lineup| {
+                println!("{}", lineup.channels.len())
+            })?;
+        }
+        Command::Active => {
+            format.emit(
+                &device.active_app().await?,
+                |active| {
+                    match &active.app.id {
+                        Some(id) => println!("{} ({})", active.app.name, id),
+                        None => println!("{}", active.app.name),
+                    }
+                    if let Some(screensaver) = &active.screensaver {
+                        println!("screensaver: {} ({})", screensaver.name, screensaver.id);
+                    }
+                },
+                |active| {
+                    let id = active.app.id.as_deref().unwrap_or_default();
+                    println!("app\t{}\t{}", id, active.app.name);
+                    if let Some(screensaver) = &active.screensaver {
+                        println!("screensaver\t{}\t{}", screensaver.id, screensaver.name);
+                    }
+                },
+            )?;
+        }
+        Command::Launch {
+            app_id,
+            content_id,
+            media_type,
+            params,
+        } => {
+            let app = App {
+                id: Some(app_id),
+                name: String::new(),
+                version: None,
+            };
+            let mut launch = LaunchParams::new();
+            if let Some(content_id) = content_id {
+                launch.content_id(content_id);
+            }
+            if let Some(media_type) = media_type {
+                launch.media_type(media_type);
+            }
+            for param in &params {
+                let (key, value) = param
+                    .split_once('=')
+                    .ok_or_else(|| format!("expected key=value, got {}", param))?;
+                launch.param(key.to_string(), value.to_string());
+            }
+            device.launch_with(&app, launch).await?;
+        }
+        Command::Link {
+            content_id,
+            media_type,
+            params,
+        } => {
+            let mut link = DeepLink::new(content_id);
+            if let Some(media_type) = media_type {
+                link.media_type(media_type);
+            }
+            for param in &params {
+                let (key, value) = param
+                    .split_once('=')
+                    .ok_or_else(|| format!("expected key=value, got {}", param))?;
+                link.param(key.to_string(), value.to_string());
+            }
+            device.deep_link(&link).await?;
+        }
+        Command::Secret { screen } => device.open_secret_screen(screen).await?,
+        Command::Icon { app_id, output } => {
+            let image = device.icon(&app_id).await?;
+            let output = output.unwrap_or_else(|| {
+                PathBuf::from(format!("{}.{}", app_id, image.extension().unwrap_or("png")))
+            });
+            save(format, &output, &image.data)?;
+        }
+        Command::Install {
+            app_ids,
+            no_confirm,
+        } => {
+            let report = InstallQueue::new()
+                .confirm(!no_confirm)
+                .run(&device, &app_ids, |progress| {
+                    let status = match &progress.status {
+                        InstallStatus::Skipped => "already installed".to_string(),
+                        InstallStatus::Installing => "installing".to_string(),
+                        InstallStatus::Confirming => "confirming".to_string(),
+                        InstallStatus::Installed => "installed".to_string(),
+                        InstallStatus::Failed { error } => format!("failed: {}", error),
+                    };
+                    format.status(&format!(
+                        "[{}/{}] {}: {}",
+                        progress.index + 1,
+                        progress.total,
+                        progress.app_id,
+                        status
+                    ));
+                })
+                .await?;
+            format.emit(
+                &report,
+                |report| {
+                    println!(
+                        "{} installed, {} skipped, {} failed",
+                        report.installed.len(),
+                        report.skipped.len(),
+                        report.failed.len()
+                    );
+                },
+                |report| {
+                    for id in &report.installed {
+                        println!("{}\tinstalled", id);
+                    }
+                    for id in &report.skipped {
+                        println!("{}\tskipped", id);
+                    }
+                    for failure in &report.failed {
+                        println!("{}\tfailed\t{}", failure.app_id, failure.error);
+                    }
+                },
+            )?;
+            if !report.is_ok() {
+                return Err("some channels failed to install".into());
+            }
+        }
+        Command::Key {
+            keys,
+            repeat,
+            interval,
+        } => {
+            let cancel = CancellationToken::new();
+            let ctrl_c = cancel.clone();
+            tokio::spawn(async move {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    ctrl_c.cancel();
+                }
+            });
+            for key in &keys {
+                device
+                    .keypress_repeat(key, repeat, interval, &cancel)
+                    .await?;
+            }
+        }
+        Command::Scroll { direction, count } => {
+            interruptible(std::slice::from_ref(&device), async {
+                Ok(device.scroll(direction, count).await?)
+            })
+            .await?
+        }
+        Command::Type {
+            text,
+            enter,
+            interval,
+            rows,
+            wrap,
+        } => {
+            if rows.is_empty() {
+                device.type_text(&text, interval).await?;
+            } else {
+                GridKeyboard::new(&rows)
+                    .wrap(wrap)
+                    .interval(interval)
+                    .type_text(&device, &text)
+                    .await?;
+            }
+            if enter {
+                sleep(interval).await;
+                device.keypress(&Key::Enter).await?;
+            }
+        }
+        Command::Captions { offset, presses } => {
+            let mut toggle = CaptionToggle::new();
+            toggle.presses(presses);
+            for (app_id, offset) in &config.captions {
+                toggle.channel_offset(app_id.clone(), *offset);
+            }
+            if let Some(offset) = offset {
+                toggle.offset(offset);
+            }
+            match toggle.run(&device).await? {
+                Some(captions) => format.status(&format!("captions: {}", captions)),
+                None => format.status("captions toggled, media player reports no format"),
+            }
+        }
+        Command::Cast {
+            target,
+            title,
+            artwork,
+            mime,
+            media_player,
+            port,
+        } => {
+            let (url, server) = match Url::parse(&target) {
+                Ok(url) if url.scheme() == "http" || url.scheme() == "https" => (url, None),
+                _ => {
+                    let server = FileServer::start(&target, device.url(), port).await?;
+                    (server.url().clone(), Some(server))
+                }
+            };
+            let serving = server.is_some();
+            let mut media = match mime {
+                Some(mime) => Media::from_mime(url, &mime)?,
+                None => Media::from_url(url)?,
+            };
+            let title = title.or_else(|| {
+                let stem = PathBuf::from(&target).file_stem()?.to_owned();
+                serving.then(|| stem.to_string_lossy().into_owned())
+            });
+            if let Some(title) = title {
+                media.title(title);
+            }
+            if let Some(artwork) = artwork {
+                media.artwork(artwork);
+            }
+            if media_player {
+                device.cast_media_player(&media).await?;
+            } else {
+                device.cast(&media).await?;
+            }
+            if serving {
+                format.status(&format!("serving {} at {}", target, media.url()));
+                format.status("press Ctrl-C to stop");
+                tokio::signal::ctrl_c().await?;
+            }
+        }
+        Command::Screenshot { output, password } => {
+            let dev = dev_server(&device, password, &config, cli.device.as_deref())?;
+            let image = dev.screenshot().await?;
+            let output = output.unwrap_or_else(|| {
+                PathBuf::from(format!("screenshot.{}", image.extension().unwrap_or("jpg")))
+            });
+            save(format, &output, &image.data)?;
+        }
+        Command::Sgnodes { roots, node } => {
+            let tree = match (roots, node) {
+                (true, _) => device.sgnodes_roots().await?,
+                (false, Some(node)) => device.sgnodes_node(&node).await?,
+                (false, None) => device.sgnodes_all().await?,
+            };
+            format.emit(
+                &tree,
+                |tree| {
+                    for root in &tree.roots {
+                        print_node(root, 0, &mut |depth, node| {
+                            let indent = "  ".repeat(depth);
+                            match &node.id {
+                                Some(id) => println!("{}{} \"{}\"", indent, node.node_type, id),
+                                None => println!("{}{}", indent, node.node_type),
+                            }
+                        });
+                    }
+                },
+                |tree| {
+                    for root in &tree.roots {
+                        print_node(root, 0, &mut |depth, node| {
+                            println!(
+                                "{}\t{}\t{}",
+                                depth,
+                                node.node_type,
+                                node.id.as_deref().unwrap_or_default()
+                            );
+                        });
+                    }
+                },
+            )?;
+        }
+        Command::Bitmaps { top } => {
+            let report = device.r2d2_bitmaps().await?;
+            if let Some(error) = &report.error {
+                return Err(error.clone().into());
+            }
+            let bitmaps = report.largest(top.unwrap_or(usize::MAX));
+            format.emit(
+                &bitmaps,
+                |bitmaps| {
+                    for bitmap in bitmaps {
+                        println!(
+                            "{:>10}  {:>4}x{:<4}  {}",
+                            bitmap.size,
+                            bitmap.width.unwrap_or_default(),
+                            bitmap.height.unwrap_or_default(),
+                            bitmap.name
+                        );
+                    }
+                    println!(
+                        "{:>10}  total in {} bitmaps",
+                        report.total_size(),
+                        report.iter().count()
+                    );
+                },
+                |bitmaps| {
+                    for bitmap in bitmaps {
+                        println!("{}\t{}", bitmap.size, bitmap.name);
+                    }
+                },
+            )?;
+        }
+        Command::Rendezvous { track, untrack } => {
+            if untrack {
+                device.untrack_sgrendezvous().await?;
+            } else {
+                if track {
+                    device.track_sgrendezvous().await?;
+                }
+                print_rendezvous(format, &device.sgrendezvous().await?)?;
+            }
+        }
+        Command::Beacons { track, untrack } => {
+            if untrack {
+                device.untrack_fwbeacons().await?;
+            } else {
+                if track {
+                    device.track_fwbeacons().await?;
+                }
+                print_beacons(format, &device.fwbeacons().await?)?;
+            }
+        }
+        Command::Registry { app } => {
+            let registry = match app {
+                Some(app_id) => device.app_registry(&app_id).await?,
+                None => device.registry().await?,
+            };
+            if let Some(error) = &registry.error {
+                return Err(error.clone().into());
+            }
+            let sections = registry.to_map();
+            format.emit(
+                &sections,
+                |sections| {
+                    for (section, items) in sections {
+                        println!("[{}]", section);
+                        for (key, value) in items {
+                            println!("{} = {}", key, value);
+                        }
+                    }
+                },
+                |sections| {
+                    for (section, items) in sections {
+                        for (key, value) in items {
+                            println!("{}\t{}\t{}", section, key, value);
+                        }
+                    }
+                },
+            )?;
+        }
+        Command::Perf {
+            interval,
+            duration,
+            output,
+        } => {
+            let cancel = CancellationToken::new();
+            let ctrl_c = cancel.clone();
+            tokio::spawn(async move {
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    ctrl_c.cancel();
+                }
+            });
+            let session = PerfSession::record(&device, interval, duration, &cancel).await?;
+            if let Some(output) = output {
+                let data = match output.extension().and_then(|extension| extension.to_str()) {
+                    Some("json") => serde_json::to_string_pretty(&session)?,
+                    _ => session.to_csv(),
+                };
+                fs::write(&output, data)
+                    .map_err(|err| format!("failed to write {}: {}", output.display(), err))?;
+            }
+            let summary = session
+                .summary()
+                .ok_or_else(|| Error::Response("no channel running".to_string()))?;
+            format.emit(
+                &summary,
+                |summary| {
+                    println!("samples:  {}", summary.samples);
+                    let row = |name: &str, stats: &Stats, scale: f64, unit: &str| {
+                        println!(
+                            "{:<9} min {:.1}{unit}  max {:.1}{unit}  avg {:.1}{unit}",
+                            name,
+                            stats.min / scale,
+                            stats.max / scale,
+                            stats.avg / scale,
+                        )
+                    };
+                    row("cpu user", &summary.cpu_user, 1.0, "%");
+                    row("cpu sys", &summary.cpu_sys, 1.0, "%");
+                    row("mem used", &summary.memory_used, 1048576.0, " MiB");
+                    row("mem res", &summary.memory_res, 1048576.0, " MiB");
+                },
+                |summary| {
+                    println!("samples\t{}", summary.samples);
+                    for (name, stats) in [
+                        ("cpu_user", &summary.cpu_user),
+                        ("cpu_sys", &summary.cpu_sys),
+                        ("memory_used", &summary.memory_used),
+                        ("memory_res", &summary.memory_res),
+                    ] {
+                        println!("{}\t{}\t{}\t{}", name, stats.min, stats.max, stats.avg);
+                    }
+                },
+            )?;
+        }
+        Command::Profile { output, password } => {
+            let dev = dev_server(&device, password, &config, cli.device.as_deref())?;
+            let profile = dev.profile().await?;
+            save(format, &output, &profile)?;
+        }
+        Command::Deploy {
+            source_dir,
+            password,
+        } => {
+            let password = dev_password(password, &config, cli.device.as_deref())?;
+            match roku::dev::deploy(&source_dir, &device, password).await? {
+                SideloadResult::Identical => format.status("identical to the installed channel"),
+                _ => format.status(&format!("deployed {}", source_dir.display())),
+            }
+        }
+        Command::Sideload {
+            archive,
+            delete,
+            password,
+        } => {
+            let dev = dev_server(&device, password, &config, cli.device.as_deref())?;
+            match archive {
+                Some(archive) if !delete => match dev.sideload(&archive).await? {
+                    SideloadResult::Installed => {
+                        format.status(&format!("installed {}", archive.display()))
+                    }
+                    SideloadResult::Identical => {
+                        format.status("identical to the installed channel, not replaced")
+                    }
+                    SideloadResult::CompilationFailed(messages)
+                    | SideloadResult::Failed(messages) => {
+                        return Err(messages.join("\n").into());
+                    }
+                },
+                _ => {
+                    dev.delete_sideloaded().await?;
+                    format.status("deleted sideloaded channel");
+                }
+            }
+        }
+        Command::Package {
+            app_name,
+            output,
+            signing_password,
+            password,
+        } => {
+            let dev = dev_server(&device, password, &config, cli.device.as_deref())?;
+            let pkg = dev.package(&app_name, &signing_password).await?;
+            save(format, &output, &pkg)?;
+        }
+        Command::Rekey {
+            pkg,
+            signing_password,
+            password,
+        } => {
+            let dev = dev_server(&device, password, &config, cli.device.as_deref())?;
+            let data = fs::read(&pkg)
+                .map_err(|err| format!("failed to read {}: {}", pkg.display(), err))?;
+            dev.rekey(data, &signing_password).await?;
+            format.status(&format!("rekeyed with {}", pkg.display()));
+        }
+        Command::Logs => {
+            let mut logs = device.debug_logs()?;
+            while let Some(line) = logs.next().await {
+                match line {
+                    Ok(line) => println!("{}", line),
+                    Err(err) => eprintln!("error: {}", err),
+                }
+            }
+        }
+        Command::Shell { command } => {
+            let mut console = device.console().await?;
+            println!("{}", console.command(&command.join(" ")).await?);
+        }
+        Command::Watch { interval } => {
+            let mut events = device.events(interval);
+            while let Some(event) = events.next().await {
+                match event {
+                    Ok(event) => println!("{}", serde_json::to_string(&event)?),
+                    Err(err) => eprintln!("error: {}", err),
+                }
+            }
+        }
+        Command::Search {
+            keyword,
+            search_type,
+            provider,
+            provider_id,
+            season,
+            title,
+            tmsid,
+            launch,
+            match_any,
+            show_unavailable,
+            resolve,
+            installed_only,
+        } => {
+            let mut search = Search::new(keyword);
+            search
+                .resolve_providers(resolve)
+                .installed_only(installed_only);
+            if let Some(search_type) = search_type {
+                search.search_type(search_type);
+            }
+            for provider in provider {
+                search.provider(provider);
+            }
+            for provider_id in provider_id {
+                search.provider_id(provider_id);
+            }
+            if let Some(season) = season {
+                search.season(season);
+            }
+            if let Some(title) = title {
+                search.title(title);
+            }
+            if let Some(tmsid) = tmsid {
+                search.tmsid(tmsid);
+            }
+            if launch {
+                search.launch(true);
+            }
+            if match_any {
+                search.match_any(true);
+            }
+            if show_unavailable {
+                search.show_unavailable(true);
+            }
+            device.search(search).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn interruptible<T>(
+    devices: &[Device],
+    future: impl Future<Output = Result<T, Box<dyn StdError>>>,
+) -> Result<T, Box<dyn StdError>> {
+    tokio::select! {
+        result = future => result,
+        _ = tokio::signal::ctrl_c() => {
+            for device in devices {
+                if let Err(err) = device.release_all().await {
+                    eprintln!("{}: failed to release held keys: {}", device.url(), err);
+                }
+            }
+            Err(Interrupted.into())
+        }
+    }
+}
+
+fn save(format: Format, output: &Path, data: &[u8]) -> Result<(), Box<dyn StdError>> {
+    fs::write(output, data)
+        .map_err(|err| format!("failed to write {}: {}", output.display(), err))?;
+    let output = output.display().to_string();
+    let print = |output: &String| println!("{}", output);
+    format.emit(&output, print, print)
+}
+
+fn scheduler() -> Result<Scheduler, Box<dyn StdError>> {
+    let path = config::jobs_path().ok_or("cannot locate the config directory for jobs.json")?;
+    Ok(Scheduler::load(path)?)
+}
+
+fn read_lineup(path: &Path) -> Result<TvChannels, Box<dyn StdError>> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+    serde_json::from_str(&text)
+        .map_err(|err| format!("failed to parse {}: {}", path.display(), err).into())
+}
+
+fn print_lineup(lineup: &TvChannels) {
+    for channel in &lineup.channels {
+        let hidden = if channel.user_hidden {
+            "  (hidden)"
+        } else {
+            ""
+        };
+        println!("{:>6}  {}{}", channel.number, channel.name, hidden);
+    }
+}
+
+fn status(status: CheckStatus) -> &'static str {
+    match status {
+        CheckStatus::Pass => "ok",
+        CheckStatus::Warn => "warn",
+        CheckStatus::Fail => "fail",
+    }
+}
+
+fn print_now(active: &TvActiveChannel) {
+    let channel = match &active.channel {
+        Some(channel) => channel,
+        None => {
+            println!("no active channel");
+            return;
+        }
+    };
+    let mut header = format!("{}  {}", channel.number, channel.name);
+    if let Some(resolution) = channel.resolution() {
+        header.push_str(&format!("  {}", resolution));
+    }
+    println!("{}", header);
+    let program = match channel.program() {
+        Some(program) => program,
+        None => return,
+    };
+    let mut line = program.title.clone();
+    if !program.ratings.is_empty() {
+        line.push_str(&format!(" ({})", program.ratings.join(", ")));
+    }
+    if let Some(audio_format) = &program.audio_format {
+        line.push_str(&format!(" [{}]", audio_format));
+    }
+    let now = SystemTime::now();
+    if let (Some(elapsed), Some(duration)) = (program.elapsed(now), program.duration) {
+        line.push_str(&format!(
+            "  {} of {} min",
+            elapsed.as_secs() / 60,
+            duration.as_secs() / 60
+        ));
+    }
+    println!("{}", line);
+    if let Some(description) = &program.description {
+        println!("{}", description);
+    }
+}
+
+fn print_beacons(format: Format, beacons: &FwBeacons) -> Result<(), Box<dyn StdError>> {
+    format.emit(
+        beacons,
+        |beacons| {
+            if !beacons.tracking_enabled {
+                println!("tracking disabled, enable it with --track");
+            }
+            let start = beacons.iter().find_map(|beacon| beacon.timestamp);
+            for beacon in beacons.iter() {
+                println!(
+                    "{:>+8} ms  {}  ({})",
+                    match (start, beacon.timestamp) {
+                        (Some(start), Some(timestamp)) => timestamp as i64 - start as i64,
+                        _ => 0,
+                    },
+                    beacon.event,
+                    beacon.channel_id.as_deref().unwrap_or_default()
+                );
+            }
+            if let Some(launch) = beacons.launch_time() {
+                println!("launch took {} ms", launch.as_millis());
+            }
+        },
+        |beacons| {
+            for beacon in beacons.iter() {
+                println!(
+                    "{}\t{}\t{}",
+                    beacon.timestamp.unwrap_or_default(),
+                    beacon.event,
+                    beacon.channel_id.as_deref().unwrap_or_default()
+                );
+            }
+        },
+    )
+}
+
+fn print_rendezvous(format: Format, rendezvous: &SgRendezvous) -> Result<(), Box<dyn StdError>> {
+    format.emit(
+        rendezvous,
+        |rendezvous| {
+            if !rendezvous.tracking_enabled {
+                println!("tracking disabled, enable it with --track");
+            }
+            for item in &rendezvous.data.items {
+                println!(
+                    "{:>6} ms  {}.{}  ({})",
+                    item.ms.unwrap_or_default(),
+                    item.node.as_deref().unwrap_or("?"),
+                    item.field.as_deref().unwrap_or("?"),
+                    item.thread.as_deref().unwrap_or_default()
+                );
+            }
+        },
+        |rendezvous| {
+            for item in &rendezvous.data.items {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    item.ms.unwrap_or_default(),
+                    item.node.as_deref().unwrap_or_default(),
+                    item.field.as_deref().unwrap_or_default(),
+                    item.thread.as_deref().unwrap_or_default()
+                );
+            }
+        },
+    )
+}
+
+fn print_node(node: &Node, depth: usize, print: &mut impl FnMut(usize, &Node)) {
+    print(depth, node);
+    for child in &node.children {
+        print_node(child, depth + 1, print);
+    }
+}
+
+fn dev_server(
+    device: &Device,
+    password: Option<String>,
+    config: &Config,
+    alias: Option<&str>,
+) -> Result<DevServer, Error> {
+    device.dev_server(dev_password(password, config, alias)?)
+}
+
+fn dev_password(
+    password: Option<String>,
+    config: &Config,
+    alias: Option<&str>,
+) -> Result<String, Error> {
+    password
+        .or(config.password(alias))
+        .ok_or_else(|| Error::Argument("developer password required".to_string()))
+}
+
+async fn discover_one() -> Result<Device, Box<dyn StdError>> {
+    Device::discover()
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| NoDevice("no devices found".to_string()).into())
+}
+
+async fn resolve(target: Target) -> Result<Device, Box<dyn StdError>> {
+    let profile = match target {
+        Target::Host(host) => return Ok(Device::new(host_url(&host)?)),
+        Target::Profile(profile) => profile,
+    };
+    let device = match (&profile.host, &profile.serial) {
+        (Some(host), _) => Device::new(host_url(host)?),
+        (None, Some(serial)) => find_serial(serial).await?,
+        (None, None) => return Err(NoDevice("profile has no host or serial".to_string()).into()),
+    };
+    Ok(match profile.timeout() {
+        Some(timeout) => device.with_timeout(timeout)?,
+        None => device,
+    })
+}
+
+async fn find_serial(serial: &str) -> Result<Device, Box<dyn StdError>> {
+    for device in Device::discover().await? {
+        if let Ok(info) = device.device_info().await {
+            if info.serial_number.eq_ignore_ascii_case(serial) {
+                return Ok(device);
+            }
+        }
+    }
+    Err(NoDevice(format!("no device with serial {}", serial)).into())
+}
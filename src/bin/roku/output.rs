@@ -0,0 +1,49 @@
+use serde::Serialize;
+use std::error::Error;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    Human,
+    Porcelain,
+    Json,
+    Yaml,
+    Quiet,
+}
+
+impl Format {
+    pub fn new(json: bool, yaml: bool, porcelain: bool, quiet: bool) -> Format {
+        if quiet {
+            Format::Quiet
+        } else if json {
+            Format::Json
+        } else if yaml {
+            Format::Yaml
+        } else if porcelain {
+            Format::Porcelain
+        } else {
+            Format::Human
+        }
+    }
+
+    pub fn emit<T: Serialize>(
+        self,
+        value: &T,
+        human: impl FnOnce(&T),
+        porcelain: impl FnOnce(&T),
+    ) -> Result<(), Box<dyn Error>> {
+        match self {
+            Format::Human => human(value),
+            Format::Porcelain => porcelain(value),
+            Format::Json => println!("{}", serde_json::to_string_pretty(value)?),
+            Format::Yaml => print!("{}", serde_yaml::to_string(value)?),
+            Format::Quiet => {}
+        }
+        Ok(())
+    }
+
+    pub fn status(self, message: &str) {
+        if self == Format::Human {
+            eprintln!("{}", message);
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use futures_util::StreamExt;
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, List, ListState, Paragraph, Wrap},
+    DefaultTerminal, Frame,
+};
+use roku::{Device, Key, MediaPlayer};
+use std::{error::Error, sync::Arc, time::Duration};
+use tokio::{sync::watch, time::sleep};
+
+const NAVIGATE_HELP: &str = "arrows move  enter select  backspace back  h home  space play  \
+                             , . rev/fwd  r replay  * info  + - m volume  t type  q quit";
+const TEXT_HELP: &str = "keys are typed literally  enter enter  backspace delete  esc done";
+
+#[derive(Clone, Default)]
+struct Status {
+    app: Option<String>,
+    playback: Option<String>,
+}
+
+enum Mode {
+    Navigate,
+    Text,
+}
+
+enum Action {
+    Press(Key),
+    Quit,
+    None,
+}
+
+pub async fn run(devices: Vec<Device>) -> Result<(), Box<dyn Error>> {
+    let mut terminal = ratatui::try_init()?;
+    let result = remote(&mut terminal, devices).await;
+    ratatui::try_restore()?;
+    result
+}
+
+async fn remote(
+    terminal: &mut DefaultTerminal,
+    devices: Vec<Device>,
+) -> Result<(), Box<dyn Error>> {
+    let mut events = EventStream::new();
+    let device = match select(terminal, &mut events, devices).await? {
+        Some(device) => Arc::new(device),
+        None => return Ok(()),
+    };
+    let name = name(&device).await;
+    let (tx, mut rx) = watch::channel(Status::default());
+    let poller = tokio::spawn(poll(Arc::clone(&device), tx));
+    let mut mode = Mode::Navigate;
+    let mut last = String::new();
+    loop {
+        let status = rx.borrow().clone();
+        terminal.draw(|frame| draw(frame, &name, &status, &mode, &last))?;
+        tokio::select! {
+            event = events.next() => match event {
+                Some(Ok(Event::Key(event))) if event.kind == KeyEventKind::Press => {
+                    match action(&mut mode, event) {
+                        Action::Press(key) => {
+                            last = match device.keypress(&key).await {
+                                Ok(()) => key.to_string(),
+                                Err(err) => format!("{}: {}", key, err),
+                            };
+                        }
+                        Action::Quit => break,
+                        Action::None => {}
+                    }
+                }
+                Some(Ok(_)) => {}
+                Some(Err(err)) => {
+                    poller.abort();
+                    return Err(err.into());
+                }
+                None => break,
+            },
+            _ = rx.changed() => {}
+        }
+    }
+    poller.abort();
+    Ok(())
+}
+
+async fn select(
+    terminal: &mut DefaultTerminal,
+    events: &mut EventStream,
+    mut devices: Vec<Device>,
+) -> Result<Option<Device>, Box<dyn Error>> {
+    if devices.len() == 1 {
+        return Ok(devices.pop());
+    }
+    let mut names = vec![];
+    for device in &devices {
+        names.push(format!("{}  {}", name(device).await, device.url()));
+    }
+    let mut state = ListState::default().with_selected(Some(0));
+    loop {
+        terminal.draw(|frame| {
+            let list = List::new(names.iter().map(String::as_str))
+                .block(Block::bordered().title("Select a device"))
+                .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+            frame.render_stateful_widget(list, frame.area(), &mut state);
+        })?;
+        match events.next().await {
+            Some(Ok(Event::Key(event))) if event.kind == KeyEventKind::Press => match event.code {
+                KeyCode::Up => state.select_previous(),
+                KeyCode::Down => state.select_next(),
+                KeyCode::Enter => {
+                    let index = state.selected().unwrap_or(0).min(devices.len() - 1);
+                    return Ok(Some(devices.swap_remove(index)));
+                }
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(None),
+                KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(None)
+                }
+                _ => {}
+            },
+            Some(Ok(_)) => {}
+            Some(Err(err)) => return Err(err.into()),
+            None => return Ok(None),
+        }
+    }
+}
+
+fn action(mode: &mut Mode, event: KeyEvent) -> Action {
+    if event.modifiers.contains(KeyModifiers::CONTROL) && event.code == KeyCode::Char('c') {
+        return Action::Quit;
+    }
+    match mode {
+        Mode::Navigate => Action::Press(match event.code {
+            KeyCode::Up => Key::Up,
+            KeyCode::Down => Key::Down,
+            KeyCode::Left => Key::Left,
+            KeyCode::Right => Key::Right,
+            KeyCode::Enter => Key::Select,
+            KeyCode::Backspace => Key::Back,
+            KeyCode::Home | KeyCode::Char('h') => Key::Home,
+            KeyCode::Char(' ') => Key::Play,
+            KeyCode::Char(',') | KeyCode::Char('<') => Key::Rev,
+            KeyCode::Char('.') | KeyCode::Char('>') => Key::Fwd,
+            KeyCode::Char('r') => Key::InstantReplay,
+            KeyCode::Char('*') | KeyCode::Char('i') => Key::Info,
+            KeyCode::Char('+') | KeyCode::Char('=') => Key::VolumeUp,
+            KeyCode::Char('-') => Key::VolumeDown,
+            KeyCode::Char('m') => Key::VolumeMute,
+            KeyCode::Char('t') | KeyCode::Char('/') => {
+                *mode = Mode::Text;
+                return Action::None;
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Action::Quit,
+            _ => return Action::None,
+        }),
+        Mode::Text => Action::Press(match event.code {
+            KeyCode::Enter => Key::Enter,
+            KeyCode::Backspace => Key::Backspace,
+            KeyCode::Char(c) => Key::Lit(c),
+            KeyCode::Esc => {
+                *mode = Mode::Navigate;
+                return Action::None;
+            }
+            _ => return Action::None,
+        }),
+    }
+}
+
+fn draw(frame: &mut Frame, name: &str, status: &Status, mode: &Mode, last: &str) {
+    let [status_area, last_area, help_area] = Layout::vertical([
+        Constraint::Length(5),
+        Constraint::Length(3),
+        Constraint::Min(3),
+    ])
+    .areas(frame.area());
+    let (mode, help) = match mode {
+        Mode::Navigate => ("navigate", NAVIGATE_HELP),
+        Mode::Text => ("type", TEXT_HELP),
+    };
+    let lines = vec![
+        Line::from(format!("app:  {}", status.app.as_deref().unwrap_or("-"))),
+        Line::from(format!(
+            "play: {}",
+            status.playback.as_deref().unwrap_or("-")
+        )),
+        Line::from(format!("mode: {}", mode)),
+    ];
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(name)),
+        status_area,
+    );
+    frame.render_widget(
+        Paragraph::new(last).block(Block::bordered().title("Last key")),
+        last_area,
+    );
+    frame.render_widget(
+        Paragraph::new(help)
+            .wrap(Wrap { trim: true })
+            .block(Block::bordered().title("Keys")),
+        help_area,
+    );
+}
+
+async fn poll(device: Arc<Device>, tx: watch::Sender<Status>) {
+    loop {
+        let app = device.active_app().await.ok().map(|active| active.app.name);
+        let playback = device
+            .media_player()
+            .await
+            .ok()
+            .map(|player| playback(&player));
+        if tx.send(Status { app, playback }).is_err() {
+            break;
+        }
+        sleep(Duration::from_secs(1)).await;
+    }
+}
+
+async fn name(device: &Device) -> String {
+    match device.device_info().await {
+        Ok(info) => info.user_device_name,
+        Err(_) => device.url().to_string(),
+    }
+}
+
+fn playback(player: &MediaPlayer) -> String {
+    match (&player.position, &player.duration) {
+        (Some(position), Some(duration)) => format!(
+            "{} {} / {}",
+            player.state,
+            timestamp(position),
+            timestamp(duration)
+        ),
+        (Some(position), None) => format!("{} {}", player.state, timestamp(position)),
+        _ => player.state.clone(),
+    }
+}
+
+fn timestamp(value: &str) -> String {
+    match value.trim_end_matches("ms").trim().parse::<u64>() {
+        Ok(ms) => {
+            let secs = ms / 1000;
+            format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+        }
+        Err(_) => value.to_string(),
+    }
+}
@@ -0,0 +1,216 @@
+use roku::{App, Device, Key};
+use rustyline::{
+    completion::{Completer, Pair},
+    error::ReadlineError,
+    highlight::Highlighter,
+    hint::Hinter,
+    history::DefaultHistory,
+    validate::Validator,
+    Context, Editor, Helper,
+};
+use std::{error::Error, fs, path::Path, time::Duration};
+
+const PROMPT: &str = "roku> ";
+const TYPE_INTERVAL: Duration = Duration::from_millis(100);
+
+const COMMANDS: &[&str] = &[
+    "active", "apps", "help", "info", "launch", "player", "quit", "type",
+];
+
+const KEYS: &[&str] = &[
+    "Back",
+    "Backspace",
+    "ButtonA",
+    "ButtonB",
+    "ChannelDown",
+    "ChannelUp",
+    "Down",
+    "Enter",
+    "FindRemote",
+    "Fwd",
+    "Home",
+    "Info",
+    "InputAV1",
+    "InputHDMI1",
+    "InputHDMI2",
+    "InputHDMI3",
+    "InputHDMI4",
+    "InputTuner",
+    "InstantReplay",
+    "Left",
+    "Pause",
+    "Play",
+    "Power",
+    "PowerOff",
+    "PowerOn",
+    "Rev",
+    "Right",
+    "Search",
+    "Select",
+    "Stop",
+    "Up",
+    "VolumeDown",
+    "VolumeMute",
+    "VolumeUp",
+];
+
+const HELP: &str = "  <key> [key...]   press keys in order (tab completes key names)
+  launch <app>     launch a channel by name or id
+  type <text>      type text into the focused field
+  active           show the active channel
+  apps             list installed channels
+  player           show the media player state
+  info             show device information
+  quit             leave the prompt (or Ctrl-D)";
+
+struct ReplHelper {
+    apps: Vec<App>,
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let line = &line[..pos];
+        if let Some(prefix) = line.strip_prefix("launch ") {
+            let prefix = prefix.to_lowercase();
+            let candidates = self
+                .apps
+                .iter()
+                .filter(|app| app.name.to_lowercase().starts_with(&prefix))
+                .map(|app| pair(&app.name))
+                .collect();
+            return Ok(("launch ".len(), candidates));
+        }
+        let start = line.rfind(' ').map_or(0, |space| space + 1);
+        let word = line[start..].to_lowercase();
+        let commands = if start == 0 { COMMANDS } else { &[] };
+        let candidates = commands
+            .iter()
+            .chain(KEYS)
+            .filter(|name| name.to_lowercase().starts_with(&word))
+            .map(|name| pair(name))
+            .collect();
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+pub async fn run(device: &Device, history: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let apps = device
+        .apps()
+        .await
+        .map(|apps| apps.apps)
+        .unwrap_or_default();
+    let mut editor = Editor::<ReplHelper, DefaultHistory>::new()?;
+    editor.set_helper(Some(ReplHelper { apps }));
+    if let Some(history) = history {
+        let _ = editor.load_history(history);
+    }
+    println!("connected to {}, type help for commands", device.url());
+    loop {
+        let line = match tokio::task::block_in_place(|| editor.readline(PROMPT)) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err.into()),
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line)?;
+        let apps = editor
+            .helper()
+            .map(|helper| helper.apps.as_slice())
+            .unwrap_or_default();
+        match execute(device, apps, line).await {
+            Ok(true) => break,
+            Ok(false) => {}
+            Err(err) => eprintln!("error: {}", err),
+        }
+    }
+    if let Some(history) = history {
+        if let Some(dir) = history.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        editor.save_history(history)?;
+    }
+    Ok(())
+}
+
+async fn execute(device: &Device, apps: &[App], line: &str) -> Result<bool, Box<dyn Error>> {
+    let (command, rest) = match line.split_once(' ') {
+        Some((command, rest)) => (command, rest.trim()),
+        None => (line, ""),
+    };
+    match command {
+        "quit" | "exit" => return Ok(true),
+        "help" => println!("{}", HELP),
+        "launch" => {
+            let app = apps
+                .iter()
+                .find(|app| app.id.as_deref() == Some(rest) || app.name.eq_ignore_ascii_case(rest))
+                .ok_or_else(|| format!("no channel named {}", rest))?;
+            device.launch(app).await?;
+            println!("launched {}", app.name);
+        }
+        "type" => device.type_text(rest, TYPE_INTERVAL).await?,
+        "active" => {
+            let active = device.active_app().await?;
+            println!("{}", active.app.name);
+        }
+        "apps" => {
+            for app in &device.apps().await?.apps {
+                println!("{:>8}  {}", app.id.as_deref().unwrap_or_default(), app.name);
+            }
+        }
+        "player" => {
+            let player = device.media_player().await?;
+            match &player.position {
+                Some(position) => println!("{} {}", player.state, position),
+                None => println!("{}", player.state),
+            }
+        }
+        "info" => {
+            let info = device.device_info().await?;
+            println!(
+                "{} ({} {})",
+                info.user_device_name, info.model_name, info.software_version
+            );
+        }
+        _ => {
+            let keys = line
+                .split_whitespace()
+                .map(|key| key.parse())
+                .collect::<Result<Vec<Key>, _>>()?;
+            for key in &keys {
+                device.keypress(key).await?;
+            }
+            let names: Vec<String> = keys.iter().map(|key| key.to_string()).collect();
+            println!("{}", names.join(" "));
+        }
+    }
+    Ok(false)
+}
+
+fn pair(name: &str) -> Pair {
+    Pair {
+        display: name.to_string(),
+        replacement: name.to_string(),
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{Bitmap, R2d2Bitmaps};
+use std::collections::BTreeMap;
+
+impl R2d2Bitmaps {
+    pub fn iter(&self) -> impl Iterator<Item = &Bitmap> {
+        self.bitmaps.bitmaps.iter()
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.iter().map(|bitmap| bitmap.size).sum()
+    }
+
+    pub fn largest(&self, count: usize) -> Vec<&Bitmap> {
+        let mut bitmaps: Vec<_> = self.iter().collect();
+        bitmaps.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+        bitmaps.truncate(count);
+        bitmaps
+    }
+
+    pub fn size_by_name(&self) -> BTreeMap<String, u64> {
+        let mut sizes = BTreeMap::new();
+        for bitmap in self.iter() {
+            *sizes.entry(bitmap.name.clone()).or_insert(0) += bitmap.size;
+        }
+        sizes
+    }
+
+    pub fn diff_sizes(&self, later: &R2d2Bitmaps) -> BTreeMap<String, i64> {
+        let mut diff: BTreeMap<String, i64> = BTreeMap::new();
+        for (name, size) in self.size_by_name() {
+            *diff.entry(name).or_insert(0) -= size as i64;
+        }
+        for (name, size) in later.size_by_name() {
+            *diff.entry(name).or_insert(0) += size as i64;
+        }
+        diff.retain(|_, delta| *delta != 0);
+        diff
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{consts, Device, Image, Result};
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::time::Instant;
+
+const ENDPOINTS: &[Endpoint] = &[
+    Endpoint::DeviceInfo,
+    Endpoint::Apps,
+    Endpoint::TvChannels,
+    Endpoint::Icons,
+];
+
+pub trait Cache: fmt::Debug + Send + Sync {
+    fn get(&self, key: &str) -> Option<Vec<u8>>;
+    fn insert(&self, key: String, value: Vec<u8>, ttl: Duration);
+    fn remove(&self, key: &str);
+    fn remove_prefix(&self, _prefix: &str) {}
+}
+
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    entries: Mutex<HashMap<String, (Instant, Vec<u8>)>>,
+}
+
+impl MemoryCache {
+    pub fn new() -> MemoryCache {
+        MemoryCache::default()
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+impl Cache for MemoryCache {
+    fn get(&self, key: &str) -> Option<Vec<u8>> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some((expires, value)) if *expires > Instant::now() => Some(value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: String, value: Vec<u8>, ttl: Duration) {
+        let expires = Instant::now() + ttl;
+        self.entries.lock().unwrap().insert(key, (expires, value));
+    }
+
+    fn remove(&self, key: &str) {
+        self.entries.lock().unwrap().remove(key);
+    }
+
+    fn remove_prefix(&self, prefix: &str) {
+        self.entries
+            .lock()
+            .unwrap()
+            .retain(|key, _| !key.starts_with(prefix));
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    DeviceInfo,
+    Apps,
+    TvChannels,
+    Icons,
+}
+
+impl Endpoint {
+    fn path(self) -> &'static str {
+        match self {
+            Endpoint::DeviceInfo => consts::QUERY_DEVICE_INFO,
+            Endpoint::Apps => consts::QUERY_APPS,
+            Endpoint::TvChannels => consts::QUERY_TV_CHANNELS,
+            Endpoint::Icons => consts::QUERY_ICON,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CachePolicy {
+    ttls: HashMap<Endpoint, Duration>,
+}
+
+impl Default for CachePolicy {
+    fn default() -> CachePolicy {
+        let mut policy = CachePolicy {
+            ttls: HashMap::new(),
+        };
+        policy
+            .ttl(Endpoint::DeviceInfo, Duration::from_secs(60))
+            .ttl(Endpoint::Apps, Duration::from_secs(5 * 60))
+            .ttl(Endpoint::TvChannels, Duration::from_secs(5 * 60))
+            .ttl(Endpoint::Icons, Duration::from_secs(60 * 60));
+        policy
+    }
+}
+
+impl CachePolicy {
+    pub fn new() -> CachePolicy {
+        CachePolicy::default()
+    }
+
+    pub fn ttl(&mut self, endpoint: Endpoint, ttl: Duration) -> &mut Self {
+        if ttl.is_zero() {
+            self.ttls.remove(&endpoint);
+        } else {
+            self.ttls.insert(endpoint, ttl);
+        }
+        self
+    }
+
+    pub fn get(&self, endpoint: Endpoint) -> Option<Duration> {
+        self.ttls.get(&endpoint).copied()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct ResponseCache {
+    cache: Arc<dyn Cache>,
+    policy: CachePolicy,
+}
+
+impl Device {
+    pub fn with_cache(mut self, cache: Arc<dyn Cache>, policy: CachePolicy) -> Device {
+        self.cache = Some(ResponseCache { cache, policy });
+        self
+    }
+
+    pub fn with_memory_cache(self) -> Device {
+        self.with_cache(Arc::new(MemoryCache::new()), CachePolicy::default())
+    }
+
+    pub fn invalidate(&self, endpoint: Endpoint) {
+        if let (Some(cache), Ok(url)) = (&self.cache, self.url.join(endpoint.path())) {
+            match endpoint {
+                Endpoint::Icons => cache.cache.remove_prefix(&format!("{}/", url)),
+                _ => cache.cache.remove(url.as_str()),
+            }
+        }
+    }
+
+    pub fn invalidate_icon(&self, app_id: &str) {
+        let path = format!("{}/{}", consts::QUERY_ICON, app_id);
+        if let (Some(cache), Ok(url)) = (&self.cache, self.url.join(&path)) {
+            cache.cache.remove(url.as_str());
+        }
+    }
+
+    pub fn invalidate_all(&self) {
+        for endpoint in ENDPOINTS {
+            self.invalidate(*endpoint);
+        }
+    }
+
+    pub(crate) async fn query(&self, endpoint: Endpoint) -> Result<String> {
+        let url = self.url.join(endpoint.path())?;
+        let cached = self.cached(endpoint);
+        if let Some((cache, _)) = cached {
+            if let Some(body) = cache.cache.get(url.as_str()) {
+                return Ok(String::from_utf8_lossy(&body).into_owned());
+            }
+        }
+        let res = self.get(url.clone()).await?;
+        let text = res.text().await?;
+        if let Some((cache, ttl)) = cached {
+            cache
+                .cache
+                .insert(url.to_string(), text.clone().into_bytes(), ttl);
+        }
+        Ok(text)
+    }
+
+    pub(crate) async fn query_icon(&self, app_id: &str) -> Result<Image> {
+        let url = self
+            .url
+            .join(&format!("{}/{}", consts::QUERY_ICON, app_id))?;
+        let cached = self.cached(Endpoint::Icons);
+        if let Some((cache, _)) = cached {
+            if let Some(value) = cache.cache.get(url.as_str()) {
+                let split = value.iter().position(|b| *b == b'\n').unwrap_or(0);
+                let content_type = String::from_utf8_lossy(&value[..split]).into_owned();
+                return Ok(Image {
+                    content_type: Some(content_type)
+                        .filter(|content_type| !content_type.is_empty()),
+                    data: value[(split + 1).min(value.len())..].to_vec(),
+                });
+            }
+        }
+        let image = Image::from_response(self.get(url.clone()).await?).await?;
+        if let Some((cache, ttl)) = cached {
+            let mut value = image.content_type.clone().unwrap_or_default().into_bytes();
+            value.push(b'\n');
+            value.extend_from_slice(&image.data);
+            cache.cache.insert(url.to_string(), value, ttl);
+        }
+        Ok(image)
+    }
+
+    fn cached(&self, endpoint: Endpoint) -> Option<(&ResponseCache, Duration)> {
+        self.cache
+            .as_ref()
+            .and_then(|cache| Some((cache, cache.policy.get(endpoint)?)))
+    }
+}
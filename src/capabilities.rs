@@ -0,0 +1,135 @@
+use crate::{consts, Device, Error, Result};
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt, future::Future};
+
+const CAPABILITIES: &[Capability] = &[
+    Capability::ActiveApp,
+    Capability::MediaPlayer,
+    Capability::ChanPerf,
+    Capability::SgNodes,
+    Capability::TvChannels,
+    Capability::TvActiveChannel,
+    Capability::FwBeacons,
+    Capability::ExitApp,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Capability {
+    ActiveApp,
+    MediaPlayer,
+    #[serde(rename = "chanperf")]
+    ChanPerf,
+    #[serde(rename = "sgnodes")]
+    SgNodes,
+    TvChannels,
+    TvActiveChannel,
+    #[serde(rename = "fwbeacons")]
+    FwBeacons,
+    ExitApp,
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let path = self.path().trim_start_matches("query/");
+        write!(f, "{}", path.split('/').next().unwrap_or(path))
+    }
+}
+
+impl Capability {
+    fn path(self) -> &'static str {
+        match self {
+            Capability::ActiveApp => consts::QUERY_ACTIVE_APP,
+            Capability::MediaPlayer => consts::QUERY_MEDIA_PLAYER,
+            Capability::ChanPerf => consts::QUERY_CHANPERF,
+            Capability::SgNodes => consts::QUERY_SGNODES_ROOTS,
+            Capability::TvChannels => consts::QUERY_TV_CHANNELS,
+            Capability::TvActiveChannel => consts::QUERY_TV_ACTIVE_CHANNEL,
+            Capability::FwBeacons => consts::QUERY_FWBEACONS,
+            Capability::ExitApp => consts::EXIT_APP,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Capabilities {
+    supported: BTreeMap<Capability, bool>,
+}
+
+impl Capabilities {
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.supported.get(&capability).copied().unwrap_or(false)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Capability, bool)> + '_ {
+        self.supported
+            .iter()
+            .map(|(capability, supported)| (*capability, *supported))
+    }
+}
+
+impl Device {
+    pub async fn capabilities_probe(&self) -> Result<Capabilities> {
+        let mut capabilities = Capabilities::default();
+        for capability in CAPABILITIES {
+            let supported = self.probe(*capability).await?;
+            capabilities.supported.insert(*capability, supported);
+        }
+        *self.capabilities.lock().unwrap() = Some(capabilities.clone());
+        Ok(capabilities)
+    }
+
+    pub fn capabilities(&self) -> Option<Capabilities> {
+        self.capabilities.lock().unwrap().clone()
+    }
+
+    pub async fn supports(&self, capability: Capability) -> Result<bool> {
+        let capabilities = match self.capabilities() {
+            Some(capabilities) => capabilities,
+            None => self.capabilities_probe().await?,
+        };
+        Ok(capabilities.supports(capability))
+    }
+
+    pub(crate) async fn optional<T>(
+        &self,
+        capability: Capability,
+        request: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        if let Some(capabilities) = self.capabilities() {
+            if !capabilities.supports(capability) {
+                return Err(not_supported(capability));
+            }
+        }
+        match request.await {
+            Err(Error::Ecp(err)) if unsupported(err.code) => {
+                if let Some(capabilities) = self.capabilities.lock().unwrap().as_mut() {
+                    capabilities.supported.insert(capability, false);
+                }
+                Err(not_supported(capability))
+            }
+            result => result,
+        }
+    }
+
+    async fn probe(&self, capability: Capability) -> Result<bool> {
+        let url = self.url.join(capability.path())?;
+        match self.get(url).await {
+            Ok(_) => Ok(true),
+            Err(Error::Ecp(err)) if capability == Capability::ExitApp && err.code == 405 => {
+                Ok(true)
+            }
+            Err(Error::Ecp(err)) if unsupported(err.code) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn unsupported(code: u16) -> bool {
+    matches!(code, 400 | 404 | 405 | 501)
+}
+
+fn not_supported(capability: Capability) -> Error {
+    Error::NotSupported(format!("{} is not supported by this device", capability))
+}
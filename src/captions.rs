@@ -0,0 +1,80 @@
+use crate::{Error, Key, Result, RokuControl, Script, Step};
+use std::{collections::HashMap, time::Duration};
+
+const PLAYBACK_STATES: [&str; 3] = ["play", "pause", "buffer"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaptionToggle {
+    offset: u32,
+    presses: u32,
+    delay: Duration,
+    offsets: HashMap<String, u32>,
+}
+
+impl Default for CaptionToggle {
+    fn default() -> CaptionToggle {
+        CaptionToggle {
+            offset: 0,
+            presses: 1,
+            delay: Duration::from_millis(500),
+            offsets: HashMap::new(),
+        }
+    }
+}
+
+impl CaptionToggle {
+    pub fn new() -> CaptionToggle {
+        CaptionToggle::default()
+    }
+
+    pub fn offset(&mut self, offset: u32) -> &mut Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn channel_offset(&mut self, app_id: String, offset: u32) -> &mut Self {
+        self.offsets.insert(app_id, offset);
+        self
+    }
+
+    pub fn presses(&mut self, presses: u32) -> &mut Self {
+        self.presses = presses;
+        self
+    }
+
+    pub fn delay(&mut self, delay: Duration) -> &mut Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn script(&self, app_id: Option<&str>) -> Script {
+        let offset = app_id
+            .and_then(|app_id| self.offsets.get(app_id))
+            .copied()
+            .unwrap_or(self.offset);
+        let mut steps = vec![Step::Key(Key::Info), Step::Sleep(self.delay)];
+        for _ in 0..offset {
+            steps.push(Step::Key(Key::Down));
+        }
+        for _ in 0..self.presses {
+            steps.push(Step::Key(Key::Right));
+        }
+        steps.push(Step::Sleep(self.delay));
+        steps.push(Step::Key(Key::Info));
+        Script::new(steps)
+    }
+
+    pub async fn run(&self, device: &(impl RokuControl + ?Sized)) -> Result<Option<String>> {
+        let player = device.media_player().await?;
+        if !PLAYBACK_STATES.contains(&player.state.as_str()) {
+            return Err(Error::Argument(format!(
+                "captions can only be toggled during playback, player is {}",
+                player.state
+            )));
+        }
+        let active = device.active_app().await?;
+        self.script(active.app.id.as_deref()).run(device).await?;
+        let player = device.media_player().await?;
+        Ok(player.format.map(|format| format.captions))
+    }
+}
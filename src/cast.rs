@@ -0,0 +1,169 @@
+use crate::{Error, Result};
+use serde::Serialize;
+use std::path::Path;
+use url::Url;
+
+#[cfg(feature = "serve")]
+pub use serve::FileServer;
+pub use session::{CastSession, CastStatus};
+pub use slideshow::{Order, Slide, Slideshow, SlideshowHandle};
+
+#[cfg(feature = "serve")]
+mod serve;
+mod session;
+mod slideshow;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MediaType {
+    Video,
+    Audio,
+    Photo,
+}
+
+impl MediaType {
+    pub fn from_extension(extension: &str) -> Option<MediaType> {
+        Some(match extension.to_lowercase().as_str() {
+            "mp4" | "m4v" | "mov" | "mkv" | "webm" | "ts" | "m3u8" | "mpd" => MediaType::Video,
+            "mp3" | "m4a" | "aac" | "flac" | "wav" | "wma" | "ogg" => MediaType::Audio,
+            "jpg" | "jpeg" | "png" | "gif" | "bmp" => MediaType::Photo,
+            _ => return None,
+        })
+    }
+
+    pub fn from_path(path: &Path) -> Option<MediaType> {
+        MediaType::from_extension(path.extension()?.to_str()?)
+    }
+
+    pub fn from_mime(mime: &str) -> Option<MediaType> {
+        let mime = essence(mime);
+        let (kind, subtype) = mime.split_once('/')?;
+        Some(match (kind, subtype) {
+            ("video", _) => MediaType::Video,
+            ("application", "vnd.apple.mpegurl")
+            | ("application", "x-mpegurl")
+            | ("application", "dash+xml") => MediaType::Video,
+            ("audio", "mpegurl") | ("audio", "x-mpegurl") => MediaType::Video,
+            ("audio", _) => MediaType::Audio,
+            ("image", _) => MediaType::Photo,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Media {
+    url: Url,
+    media_type: MediaType,
+    format: String,
+    title: Option<String>,
+    artwork: Option<Url>,
+}
+
+impl Media {
+    pub fn new(url: Url, media_type: MediaType, format: String) -> Media {
+        Media {
+            url,
+            media_type,
+            format,
+            title: None,
+            artwork: None,
+        }
+    }
+
+    pub fn from_url(url: Url) -> Result<Media> {
+        let path = Path::new(url.path());
+        let media_type = MediaType::from_path(path)
+            .ok_or_else(|| Error::Argument(format!("unknown media type for {}", url)))?;
+        let format = format_for(path);
+        Ok(Media::new(url, media_type, format))
+    }
+
+    pub fn from_mime(url: Url, mime: &str) -> Result<Media> {
+        let media_type = MediaType::from_mime(mime)
+            .ok_or_else(|| Error::Argument(format!("unknown media type {}", mime)))?;
+        let format = format_for_mime(mime);
+        Ok(Media::new(url, media_type, format))
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn media_type(&self) -> MediaType {
+        self.media_type
+    }
+
+    pub fn title(&mut self, title: String) -> &mut Media {
+        self.title = Some(title);
+        self
+    }
+
+    pub fn artwork(&mut self, artwork: Url) -> &mut Media {
+        self.artwork = Some(artwork);
+        self
+    }
+
+    pub(crate) fn build(&self) -> Vec<(String, String)> {
+        let title = self.title.clone().unwrap_or_default();
+        let mut ret = vec![("u", self.url.to_string())];
+        match self.media_type {
+            MediaType::Video => {
+                ret.push(("t", "v".to_string()));
+                ret.push(("videoName", title));
+                ret.push(("videoFormat", self.format.clone()));
+            }
+            MediaType::Audio => {
+                ret.push(("t", "a".to_string()));
+                ret.push(("songname", title));
+                ret.push(("songformat", self.format.clone()));
+                if let Some(artwork) = &self.artwork {
+                    ret.push(("albumarturl", artwork.to_string()));
+                }
+            }
+            MediaType::Photo => {
+                ret.push(("t", "p".to_string()));
+            }
+        }
+        ret.into_iter().map(|(k, v)| (k.to_string(), v)).collect()
+    }
+}
+
+fn format_for(path: &Path) -> String {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    match extension.as_str() {
+        "m3u8" => "hls".to_string(),
+        "mpd" => "dash".to_string(),
+        "m4v" => "mp4".to_string(),
+        "jpeg" => "jpg".to_string(),
+        _ => extension,
+    }
+}
+
+fn format_for_mime(mime: &str) -> String {
+    let mime = essence(mime);
+    let subtype = mime.split_once('/').map_or("", |(_, subtype)| subtype);
+    match subtype {
+        "vnd.apple.mpegurl" | "x-mpegurl" | "mpegurl" => "hls".to_string(),
+        "dash+xml" => "dash".to_string(),
+        "mpeg" if mime.starts_with("audio/") => "mp3".to_string(),
+        "mp2t" => "ts".to_string(),
+        "quicktime" => "mov".to_string(),
+        "x-matroska" => "mkv".to_string(),
+        "jpeg" => "jpg".to_string(),
+        "mp4" if mime.starts_with("audio/") => "m4a".to_string(),
+        subtype => subtype.trim_start_matches("x-").to_string(),
+    }
+}
+
+fn essence(mime: &str) -> String {
+    mime.split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
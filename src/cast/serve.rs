@@ -0,0 +1,174 @@
+use crate::{Error, Result};
+use std::{
+    fs,
+    io::{self, SeekFrom},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::{
+    fs::File,
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+use url::Url;
+
+pub struct FileServer {
+    url: Url,
+    task: JoinHandle<()>,
+}
+
+impl FileServer {
+    pub async fn start<P: AsRef<Path>>(path: P, device: &Url, port: u16) -> Result<FileServer> {
+        let path = path.as_ref().to_path_buf();
+        if !fs::metadata(&path)?.is_file() {
+            return Err(Error::Argument(format!("{} is not a file", path.display())));
+        }
+        let ip = local_ip(device)?;
+        let listener = TcpListener::bind(SocketAddr::new(ip, port)).await?;
+        let mut url = Url::parse(&format!("http://{}/", listener.local_addr()?))?;
+        if let (Some(name), Ok(mut segments)) = (path.file_name(), url.path_segments_mut()) {
+            segments.pop_if_empty().push(&name.to_string_lossy());
+        }
+        let path = Arc::new(path);
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle(stream, Arc::clone(&path)));
+            }
+        });
+        Ok(FileServer { url, task })
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+}
+
+impl Drop for FileServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+fn local_ip(device: &Url) -> io::Result<IpAddr> {
+    let addr = device
+        .socket_addrs(|| Some(8060))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "device has no address"))?;
+    let unspecified = match addr {
+        SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    };
+    let socket = UdpSocket::bind((unspecified, 0))?;
+    socket.connect(addr)?;
+    Ok(socket.local_addr()?.ip())
+}
+
+async fn handle(stream: TcpStream, path: Arc<PathBuf>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    let head = line.starts_with("HEAD ");
+    let mut range = None;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("range") {
+                range = parse_range(value.trim());
+            }
+        }
+    }
+    let mut file = File::open(&*path).await?;
+    let len = file.metadata().await?.len();
+    let last = len.saturating_sub(1);
+    let (partial, start, end) = match range {
+        Some((start, end)) => {
+            let end = end.unwrap_or(last).min(last);
+            if start >= len || end < start {
+                let mut stream = reader.into_inner();
+                let header = format!(
+                    "HTTP/1.1 416 Range Not Satisfiable\r\nContent-Range: bytes */{}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    len
+                );
+                stream.write_all(header.as_bytes()).await?;
+                return stream.shutdown().await;
+            }
+            (true, start, end)
+        }
+        None => (false, 0, last),
+    };
+    let status = if partial {
+        "206 Partial Content"
+    } else {
+        "200 OK"
+    };
+    let length = if len == 0 { 0 } else { end - start + 1 };
+    let mut header = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccept-Ranges: bytes\r\nConnection: close\r\n",
+        status,
+        mime_type(&path),
+        length
+    );
+    if partial {
+        header.push_str(&format!(
+            "Content-Range: bytes {}-{}/{}\r\n",
+            start, end, len
+        ));
+    }
+    header.push_str("\r\n");
+    let mut stream = reader.into_inner();
+    stream.write_all(header.as_bytes()).await?;
+    if !head {
+        file.seek(SeekFrom::Start(start)).await?;
+        tokio::io::copy(&mut file.take(length), &mut stream).await?;
+    }
+    stream.shutdown().await
+}
+
+fn parse_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let (start, end) = value.strip_prefix("bytes=")?.split_once('-')?;
+    let start = start.trim().parse().ok()?;
+    let end = match end.trim() {
+        "" => None,
+        end => Some(end.parse().ok()?),
+    };
+    Some((start, end))
+}
+
+fn mime_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    match extension.as_str() {
+        "mp4" | "m4v" => "video/mp4",
+        "mov" => "video/quicktime",
+        "mkv" => "video/x-matroska",
+        "webm" => "video/webm",
+        "ts" => "video/mp2t",
+        "m3u8" => "application/vnd.apple.mpegurl",
+        "mpd" => "application/dash+xml",
+        "mp3" => "audio/mpeg",
+        "m4a" => "audio/mp4",
+        "aac" => "audio/aac",
+        "flac" => "audio/flac",
+        "wav" => "audio/wav",
+        "wma" => "audio/x-ms-wma",
+        "ogg" => "audio/ogg",
+        "jpg" | "jpeg" => "image/jpeg",
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        _ => "application/octet-stream",
+    }
+}
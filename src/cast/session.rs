@@ -0,0 +1,183 @@
+use crate::{millis, Device, Error, Key, Media, MediaPlayer, Result};
+use futures_util::stream::Stream;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::mpsc,
+    task::JoinHandle,
+    time::{sleep, Instant},
+};
+
+#[cfg(feature = "serve")]
+use super::FileServer;
+
+const CAPACITY: usize = 16;
+const VERIFY_POLL: Duration = Duration::from_millis(250);
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(5);
+const SEEK_TIMEOUT: Duration = Duration::from_secs(60);
+const SEEK_TOLERANCE: Duration = Duration::from_secs(2);
+
+pub struct CastSession {
+    device: Device,
+    media: Media,
+    #[cfg(feature = "serve")]
+    server: Option<FileServer>,
+}
+
+impl CastSession {
+    pub(crate) fn new(device: &Device, media: &Media) -> CastSession {
+        CastSession {
+            device: device.clone(),
+            media: media.clone(),
+            #[cfg(feature = "serve")]
+            server: None,
+        }
+    }
+
+    #[cfg(feature = "serve")]
+    pub(crate) fn serving(mut self, server: FileServer) -> CastSession {
+        self.server = Some(server);
+        self
+    }
+
+    pub fn device(&self) -> &Device {
+        &self.device
+    }
+
+    pub fn media(&self) -> &Media {
+        &self.media
+    }
+
+    #[cfg(feature = "serve")]
+    pub fn file_server(&self) -> Option<&FileServer> {
+        self.server.as_ref()
+    }
+
+    pub async fn status(&self) -> Result<MediaPlayer> {
+        self.device.media_player().await
+    }
+
+    pub async fn position(&self) -> Result<Option<Duration>> {
+        Ok(millis(self.status().await?.position.as_deref()))
+    }
+
+    pub async fn pause(&self) -> Result<()> {
+        self.device.set_playback("pause").await
+    }
+
+    pub async fn resume(&self) -> Result<()> {
+        self.device.set_playback("play").await
+    }
+
+    pub async fn seek(&self, position: Duration) -> Result<()> {
+        let current = self
+            .position()
+            .await?
+            .ok_or_else(|| Error::Response("media player reports no position".to_string()))?;
+        let forward = position > current;
+        let distance = if forward {
+            position - current
+        } else {
+            current - position
+        };
+        if distance <= SEEK_TOLERANCE {
+            return Ok(());
+        }
+        self.device
+            .keypress(if forward { &Key::Fwd } else { &Key::Rev })
+            .await?;
+        let deadline = Instant::now() + SEEK_TIMEOUT;
+        loop {
+            sleep(VERIFY_POLL).await;
+            let reached = match self.position().await? {
+                Some(current) if forward => current + SEEK_TOLERANCE >= position,
+                Some(current) => current <= position + SEEK_TOLERANCE,
+                None => false,
+            };
+            if reached {
+                break;
+            }
+            if Instant::now() >= deadline {
+                self.device.keypress(&Key::Play).await?;
+                return Err(Error::Response(format!(
+                    "seek to {}s timed out",
+                    position.as_secs()
+                )));
+            }
+        }
+        self.device.keypress(&Key::Play).await
+    }
+
+    pub async fn stop(self) -> Result<()> {
+        self.device.keypress(&Key::Back).await
+    }
+
+    pub fn watch(&self, interval: Duration) -> CastStatus {
+        let device = self.device.clone();
+        let (tx, rx) = mpsc::channel(CAPACITY);
+        let task = tokio::spawn(async move {
+            let mut last = None;
+            loop {
+                let status = device.media_player().await;
+                let changed = match (&status, &last) {
+                    (Ok(player), Some(previous)) => player != previous,
+                    _ => true,
+                };
+                if changed {
+                    last = status.as_ref().ok().cloned();
+                    let done = matches!(&status, Ok(player) if player.state == "close");
+                    if tx.send(status).await.is_err() || done {
+                        return;
+                    }
+                }
+                sleep(interval).await;
+            }
+        });
+        CastStatus { rx, task }
+    }
+}
+
+pub struct CastStatus {
+    rx: mpsc::Receiver<Result<MediaPlayer>>,
+    task: JoinHandle<()>,
+}
+
+impl Stream for CastStatus {
+    type Item = Result<MediaPlayer>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Result<MediaPlayer>>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for CastStatus {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl Device {
+    pub(crate) async fn set_playback(&self, state: &str) -> Result<()> {
+        if self.media_player().await?.state == state {
+            return Ok(());
+        }
+        self.keypress(&Key::Play).await?;
+        let deadline = Instant::now() + VERIFY_TIMEOUT;
+        loop {
+            let current = self.media_player().await?.state;
+            if current == state {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::Response(format!(
+                    "media player stayed in {} instead of {}",
+                    current, state
+                )));
+            }
+            sleep(VERIFY_POLL).await;
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{CastSession, Device, Error, Media, MediaType, Result};
+#[cfg(feature = "serve")]
+use std::path::PathBuf;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    sync::mpsc,
+    task::JoinHandle,
+    time::{sleep_until, Instant},
+};
+use url::Url;
+
+const CAPACITY: usize = 16;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Slide {
+    Url(Url),
+    #[cfg(feature = "serve")]
+    Path(PathBuf),
+}
+
+impl From<Url> for Slide {
+    fn from(url: Url) -> Slide {
+        Slide::Url(url)
+    }
+}
+
+#[cfg(feature = "serve")]
+impl From<PathBuf> for Slide {
+    fn from(path: PathBuf) -> Slide {
+        Slide::Path(path)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Order {
+    Sequential,
+    Reverse,
+    Shuffle(u64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Slideshow {
+    slides: Vec<Slide>,
+    duration: Duration,
+    order: Order,
+    repeat: bool,
+}
+
+impl Slideshow {
+    pub fn new<S: Into<Slide>>(slides: impl IntoIterator<Item = S>) -> Slideshow {
+        Slideshow {
+            slides: slides.into_iter().map(Into::into).collect(),
+            duration: Duration::from_secs(5),
+            order: Order::Sequential,
+            repeat: false,
+        }
+    }
+
+    pub fn duration(&mut self, duration: Duration) -> &mut Self {
+        self.duration = duration;
+        self
+    }
+
+    pub fn order(&mut self, order: Order) -> &mut Self {
+        self.order = order;
+        self
+    }
+
+    pub fn repeat(&mut self, repeat: bool) -> &mut Self {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn slides(&self) -> Vec<&Slide> {
+        self.indices()
+            .into_iter()
+            .map(|index| &self.slides[index])
+            .collect()
+    }
+
+    pub fn start(&self, device: &Device) -> Result<SlideshowHandle> {
+        if self.slides.is_empty() {
+            return Err(Error::Argument("slideshow has no slides".to_string()));
+        }
+        let (tx, rx) = mpsc::channel(CAPACITY);
+        let current = Arc::new(Mutex::new(None));
+        let slides = self.slides().into_iter().cloned().collect();
+        let task = tokio::spawn(run(
+            device.clone(),
+            slides,
+            self.duration,
+            self.repeat,
+            rx,
+            current.clone(),
+        ));
+        Ok(SlideshowHandle { tx, task, current })
+    }
+
+    fn indices(&self) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.slides.len()).collect();
+        match self.order {
+            Order::Sequential => {}
+            Order::Reverse => indices.reverse(),
+            Order::Shuffle(seed) => {
+                let mut state = seed | 1;
+                for i in (1..indices.len()).rev() {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    indices.swap(i, (state % (i as u64 + 1)) as usize);
+                }
+            }
+        }
+        indices
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Control {
+    Pause,
+    Resume,
+    Skip,
+    Previous,
+    Stop,
+}
+
+pub struct SlideshowHandle {
+    tx: mpsc::Sender<Control>,
+    task: JoinHandle<Result<()>>,
+    current: Arc<Mutex<Option<usize>>>,
+}
+
+impl SlideshowHandle {
+    pub fn current(&self) -> Option<usize> {
+        *self.current.lock().unwrap()
+    }
+
+    pub async fn pause(&self) {
+        let _ = self.tx.send(Control::Pause).await;
+    }
+
+    pub async fn resume(&self) {
+        let _ = self.tx.send(Control::Resume).await;
+    }
+
+    pub async fn skip(&self) {
+        let _ = self.tx.send(Control::Skip).await;
+    }
+
+    pub async fn previous(&self) {
+        let _ = self.tx.send(Control::Previous).await;
+    }
+
+    pub async fn stop(self) -> Result<()> {
+        let _ = self.tx.send(Control::Stop).await;
+        self.finished().await
+    }
+
+    pub async fn finished(mut self) -> Result<()> {
+        (&mut self.task)
+            .await
+            .map_err(|err| Error::Response(format!("slideshow task failed: {}", err)))?
+    }
+}
+
+impl Drop for SlideshowHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn run(
+    device: Device,
+    slides: Vec<Slide>,
+    duration: Duration,
+    repeat: bool,
+    mut rx: mpsc::Receiver<Control>,
+    current: Arc<Mutex<Option<usize>>>,
+) -> Result<()> {
+    let mut index = 0;
+    loop {
+        if index >= slides.len() {
+            if !repeat {
+                return Ok(());
+            }
+            index = 0;
+        }
+        let session = show(&device, &slides[index]).await?;
+        *current.lock().unwrap() = Some(index);
+        let mut deadline = Instant::now() + duration;
+        let mut paused: Option<Duration> = None;
+        loop {
+            tokio::select! {
+                _ = sleep_until(deadline), if paused.is_none() => {
+                    index += 1;
+                    break;
+                }
+                control = rx.recv() => match control {
+                    Some(Control::Pause) if paused.is_none() => {
+                        paused = Some(deadline.saturating_duration_since(Instant::now()));
+                    }
+                    Some(Control::Resume) => {
+                        if let Some(remaining) = paused.take() {
+                            deadline = Instant::now() + remaining;
+                        }
+                    }
+                    Some(Control::Skip) => {
+                        index += 1;
+                        break;
+                    }
+                    Some(Control::Previous) => {
+                        index = index.checked_sub(1).unwrap_or(slides.len() - 1);
+                        break;
+                    }
+                    Some(Control::Stop) | None => return session.stop().await,
+                    Some(Control::Pause) => {}
+                },
+            }
+        }
+    }
+}
+
+async fn show(device: &Device, slide: &Slide) -> Result<CastSession> {
+    match slide {
+        Slide::Url(url) => {
+            let media = match Media::from_url(url.clone()) {
+                Ok(media) if media.media_type() == MediaType::Photo => media,
+                _ => Media::new(url.clone(), MediaType::Photo, "jpg".to_string()),
+            };
+            device.cast(&media).await
+        }
+        #[cfg(feature = "serve")]
+        Slide::Path(path) => device.cast_file(path).await,
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{CancellationToken, Device, Result};
+use serde::Serialize;
+use std::{fmt::Write, time::Duration};
+use tokio::time::{sleep, Instant};
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Sample {
+    pub elapsed_ms: u64,
+    pub cpu_user: f64,
+    pub cpu_sys: f64,
+    pub memory_used: u64,
+    pub memory_res: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Stats {
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+}
+
+impl Stats {
+    fn from_values(values: impl Iterator<Item = f64>) -> Option<Stats> {
+        let mut count = 0;
+        let mut stats = Stats {
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            avg: 0.0,
+        };
+        for value in values {
+            count += 1;
+            stats.min = stats.min.min(value);
+            stats.max = stats.max.max(value);
+            stats.avg += value;
+        }
+        if count == 0 {
+            return None;
+        }
+        stats.avg /= count as f64;
+        Some(stats)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Summary {
+    pub samples: usize,
+    pub cpu_user: Stats,
+    pub cpu_sys: Stats,
+    pub memory_used: Stats,
+    pub memory_res: Stats,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct PerfSession {
+    samples: Vec<Sample>,
+}
+
+impl PerfSession {
+    pub fn new() -> PerfSession {
+        PerfSession::default()
+    }
+
+    pub async fn record(
+        device: &Device,
+        interval: Duration,
+        duration: Duration,
+        cancel: &CancellationToken,
+    ) -> Result<PerfSession> {
+        let mut session = PerfSession::new();
+        let start = Instant::now();
+        while start.elapsed() < duration && !cancel.is_cancelled() {
+            let perf = device.chanperf().await?;
+            if let Some(plugin) = perf.plugin {
+                session.push(Sample {
+                    elapsed_ms: start.elapsed().as_millis() as u64,
+                    cpu_user: plugin.cpu_percent.user,
+                    cpu_sys: plugin.cpu_percent.sys,
+                    memory_used: plugin.memory.used,
+                    memory_res: plugin.memory.res,
+                });
+            }
+            tokio::select! {
+                _ = sleep(interval) => {}
+                _ = cancel.cancelled() => break,
+            }
+        }
+        Ok(session)
+    }
+
+    pub fn push(&mut self, sample: Sample) {
+        self.samples.push(sample);
+    }
+
+    pub fn samples(&self) -> &[Sample] {
+        &self.samples
+    }
+
+    pub fn summary(&self) -> Option<Summary> {
+        let stats = |value: fn(&Sample) -> f64| Stats::from_values(self.samples.iter().map(value));
+        Some(Summary {
+            samples: self.samples.len(),
+            cpu_user: stats(|sample| sample.cpu_user)?,
+            cpu_sys: stats(|sample| sample.cpu_sys)?,
+            memory_used: stats(|sample| sample.memory_used as f64)?,
+            memory_res: stats(|sample| sample.memory_res as f64)?,
+        })
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = "elapsed_ms,cpu_user,cpu_sys,memory_used,memory_res\n".to_string();
+        for sample in &self.samples {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{}",
+                sample.elapsed_ms,
+                sample.cpu_user,
+                sample.cpu_sys,
+                sample.memory_used,
+                sample.memory_res
+            );
+        }
+        csv
+    }
+}
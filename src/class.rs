@@ -0,0 +1,93 @@
+use crate::{Device, DeviceInfo, Endpoint, Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_xml_rs::from_str;
+use std::fmt;
+
+const AUDIO_MODELS: &[&str] = &["soundbar", "streambar", "speaker", "subwoofer"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeviceClass {
+    Player,
+    Tv,
+    Audio,
+}
+
+impl DeviceClass {
+    pub fn from_info(info: &DeviceInfo) -> DeviceClass {
+        DeviceClass::classify(info.is_tv, &[&info.model_name, &info.friendly_model_name])
+    }
+
+    fn classify(is_tv: bool, names: &[&str]) -> DeviceClass {
+        if is_tv {
+            return DeviceClass::Tv;
+        }
+        let audio = names
+            .iter()
+            .map(|name| name.to_lowercase())
+            .any(|name| AUDIO_MODELS.iter().any(|model| name.contains(model)));
+        if audio {
+            DeviceClass::Audio
+        } else {
+            DeviceClass::Player
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            DeviceClass::Player => "a streaming player",
+            DeviceClass::Tv => "a Roku TV",
+            DeviceClass::Audio => "an audio device",
+        }
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+struct ClassInfo {
+    is_tv: bool,
+    model_name: String,
+    friendly_model_name: String,
+}
+
+impl fmt::Display for DeviceClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                DeviceClass::Player => "player",
+                DeviceClass::Tv => "tv",
+                DeviceClass::Audio => "audio",
+            }
+        )
+    }
+}
+
+impl Device {
+    pub async fn device_class(&self) -> Result<DeviceClass> {
+        if let Some(class) = *self.class.lock().unwrap() {
+            return Ok(class);
+        }
+        self.invalidate(Endpoint::DeviceInfo);
+        let info: ClassInfo = from_str(&self.query(Endpoint::DeviceInfo).await?)?;
+        let class =
+            DeviceClass::classify(info.is_tv, &[&info.model_name, &info.friendly_model_name]);
+        *self.class.lock().unwrap() = Some(class);
+        Ok(class)
+    }
+
+    pub(crate) async fn require_class(&self, required: DeviceClass, feature: &str) -> Result<()> {
+        let class = self.device_class().await?;
+        if class == required {
+            Ok(())
+        } else {
+            Err(Error::NotSupported(format!(
+                "{} requires {}, this is {}",
+                feature,
+                required.description(),
+                class.description()
+            )))
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{Device, Discoverer, Error, Result, SsdpDiscoverer};
+use reqwest::Client;
+use std::{
+    fmt,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+use url::Url;
+
+static GLOBAL: RwLock<Option<RokuConfig>> = RwLock::new(None);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    attempts: u32,
+    backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub fn new(attempts: u32, backoff: Duration) -> RetryPolicy {
+        RetryPolicy {
+            attempts: attempts.max(1),
+            backoff,
+        }
+    }
+
+    pub fn none() -> RetryPolicy {
+        RetryPolicy::new(1, Duration::ZERO)
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.backoff * 2u32.saturating_pow(attempt.saturating_sub(1))
+    }
+
+    pub(crate) fn should_retry(&self, attempt: u32, err: &Error) -> bool {
+        attempt < self.attempts
+            && match err {
+                Error::Request(err) => err.is_connect() || err.is_timeout(),
+                Error::Ecp(err) => err.code == 503,
+                _ => false,
+            }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy::none()
+    }
+}
+
+#[derive(Clone)]
+pub struct RokuConfig {
+    timeout: Option<Duration>,
+    retry: RetryPolicy,
+    pacing: Duration,
+    user_agent: Option<String>,
+    discovery: Arc<dyn Discoverer>,
+}
+
+impl Default for RokuConfig {
+    fn default() -> RokuConfig {
+        RokuConfig {
+            timeout: None,
+            retry: RetryPolicy::default(),
+            pacing: Duration::ZERO,
+            user_agent: None,
+            discovery: Arc::new(SsdpDiscoverer::new()),
+        }
+    }
+}
+
+impl fmt::Debug for RokuConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("RokuConfig")
+            .field("timeout", &self.timeout)
+            .field("retry", &self.retry)
+            .field("pacing", &self.pacing)
+            .field("user_agent", &self.user_agent)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RokuConfig {
+    pub fn new() -> RokuConfig {
+        RokuConfig::default()
+    }
+
+    pub fn global() -> RokuConfig {
+        GLOBAL.read().unwrap().clone().unwrap_or_default()
+    }
+
+    pub fn set_global(config: RokuConfig) -> Result<()> {
+        config.client(None)?;
+        *GLOBAL.write().unwrap() = Some(config);
+        Ok(())
+    }
+
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn retry(&mut self, retry: RetryPolicy) -> &mut Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn pacing(&mut self, pacing: Duration) -> &mut Self {
+        self.pacing = pacing;
+        self
+    }
+
+    pub fn user_agent(&mut self, user_agent: &str) -> &mut Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    pub fn discovery(&mut self, discovery: Arc<dyn Discoverer>) -> &mut Self {
+        self.discovery = discovery;
+        self
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry
+    }
+
+    pub fn discoverer(&self) -> Arc<dyn Discoverer> {
+        Arc::clone(&self.discovery)
+    }
+
+    pub fn device(&self, url: Url) -> Result<Device> {
+        Device::with_config(url, self)
+    }
+
+    #[cfg(any(feature = "content", feature = "dev", feature = "store"))]
+    pub(crate) fn global_client() -> Client {
+        RokuConfig::global()
+            .client(None)
+            .expect("set_global only accepts configs that build a client")
+    }
+
+    pub(crate) fn pacing_interval(&self) -> Duration {
+        self.pacing
+    }
+
+    pub(crate) fn client(&self, timeout: Option<Duration>) -> Result<Client> {
+        let mut builder = Client::builder();
+        if let Some(timeout) = timeout.or(self.timeout) {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent.as_str());
+        }
+        Ok(builder.build()?)
+    }
+}
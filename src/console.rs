@@ -0,0 +1,114 @@
+use crate::{Error, Result};
+use std::time::Duration;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    time::timeout,
+};
+
+pub const PORT: u16 = 8080;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FreeMemory {
+    pub total: u64,
+    pub used: u64,
+    pub free: u64,
+}
+
+pub struct Console {
+    stream: TcpStream,
+}
+
+impl Console {
+    pub async fn connect(host: &str) -> Result<Console> {
+        let stream = TcpStream::connect((host, PORT)).await?;
+        let mut console = Console { stream };
+        console.read_prompt().await?;
+        Ok(console)
+    }
+
+    pub async fn command(&mut self, command: &str) -> Result<String> {
+        self.stream
+            .write_all(format!("{}\r\n", command).as_bytes())
+            .await?;
+        let output = self.read_prompt().await?;
+        let mut lines = output.lines();
+        let echo = lines.clone().next().map(str::trim);
+        if echo == Some(command) {
+            lines.next();
+        }
+        Ok(lines.collect::<Vec<_>>().join("\n").trim_end().to_string())
+    }
+
+    pub async fn free(&mut self) -> Result<FreeMemory> {
+        let output = self.command("free").await?;
+        parse_free(&output).ok_or_else(|| Error::Response(output))
+    }
+
+    pub async fn loaded_textures(&mut self) -> Result<String> {
+        self.command("loaded_textures").await
+    }
+
+    pub async fn toggle_fps_display(&mut self) -> Result<()> {
+        self.command("fps_display").await?;
+        Ok(())
+    }
+
+    pub async fn sgperf_start(&mut self) -> Result<()> {
+        self.command("sgperf start").await?;
+        Ok(())
+    }
+
+    pub async fn sgperf_stop(&mut self) -> Result<()> {
+        self.command("sgperf stop").await?;
+        Ok(())
+    }
+
+    pub async fn sgperf_clear(&mut self) -> Result<()> {
+        self.command("sgperf clear").await?;
+        Ok(())
+    }
+
+    pub async fn sgperf_report(&mut self) -> Result<String> {
+        self.command("sgperf report").await
+    }
+
+    async fn read_prompt(&mut self) -> Result<String> {
+        let mut output = vec![];
+        let mut buffer = [0; 4096];
+        loop {
+            let read = match timeout(READ_TIMEOUT, self.stream.read(&mut buffer)).await {
+                Ok(read) => read?,
+                Err(_) => break,
+            };
+            if read == 0 {
+                break;
+            }
+            output.extend_from_slice(&buffer[..read]);
+            let text = String::from_utf8_lossy(&output);
+            let text = text.trim_end_matches(' ');
+            if text == ">" || text.ends_with("\n>") {
+                output.truncate(text.len() - 1);
+                break;
+            }
+        }
+        Ok(String::from_utf8_lossy(&output).replace('\r', ""))
+    }
+}
+
+fn parse_free(output: &str) -> Option<FreeMemory> {
+    let line = output
+        .lines()
+        .find(|line| line.trim_start().starts_with("Mem:"))?;
+    let mut values = line
+        .split_whitespace()
+        .skip(1)
+        .map(|value| value.parse::<u64>().ok());
+    Some(FreeMemory {
+        total: values.next()??,
+        used: values.next()??,
+        free: values.next()??,
+    })
+}
@@ -0,0 +1,36 @@
+pub const APP_CHANNEL_STORE: &str = "11";
+pub const APP_DEV: &str = "dev";
+pub const APP_LIVE_TV: &str = "tvinput.dtv";
+pub const APP_MEDIA_PLAYER: &str = "2213";
+pub const APP_NETFLIX: &str = "12";
+pub const APP_PLAY_ON_ROKU: &str = "15985";
+pub const APP_YOUTUBE: &str = "837";
+
+pub const QUERY_ACTIVE_APP: &str = "query/active-app";
+pub const QUERY_APPS: &str = "query/apps";
+pub const QUERY_CHANPERF: &str = "query/chanperf";
+pub const QUERY_DEVICE_INFO: &str = "query/device-info";
+pub const QUERY_FWBEACONS: &str = "query/fwbeacons";
+pub const QUERY_ICON: &str = "query/icon";
+pub const QUERY_MEDIA_PLAYER: &str = "query/media-player";
+pub const QUERY_R2D2_BITMAPS: &str = "query/r2d2-bitmaps";
+pub const QUERY_REGISTRY: &str = "query/registry";
+pub const QUERY_SGNODES_ALL: &str = "query/sgnodes/all";
+pub const QUERY_SGNODES_NODES: &str = "query/sgnodes/nodes";
+pub const QUERY_SGNODES_ROOTS: &str = "query/sgnodes/roots";
+pub const QUERY_SGRENDEZVOUS: &str = "query/sgrendezvous";
+pub const QUERY_TV_ACTIVE_CHANNEL: &str = "query/tv-active-channel";
+pub const QUERY_TV_CHANNELS: &str = "query/tv-channels";
+
+pub const EXIT_APP: &str = "exit-app";
+pub const FWBEACONS_TRACK: &str = "fwbeacons/track";
+pub const FWBEACONS_UNTRACK: &str = "fwbeacons/untrack";
+pub const INPUT: &str = "input";
+pub const INSTALL: &str = "install";
+pub const KEYDOWN: &str = "keydown";
+pub const KEYPRESS: &str = "keypress";
+pub const KEYUP: &str = "keyup";
+pub const LAUNCH: &str = "launch";
+pub const SEARCH: &str = "search";
+pub const SGRENDEZVOUS_TRACK: &str = "sgrendezvous/track";
+pub const SGRENDEZVOUS_UNTRACK: &str = "sgrendezvous/untrack";
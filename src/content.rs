@@ -0,0 +1,171 @@
+use crate::{DeepLink, Device, Error, Result, RokuConfig};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+const BASE_URL: &str = "https://www.roku.com/api/v1/sow/";
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ContentResult {
+    pub id: Option<String>,
+    pub title: String,
+    pub content_type: String,
+    pub release_year: Option<u32>,
+    pub providers: Vec<Provider>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Provider {
+    pub app_id: String,
+    pub name: Option<String>,
+    pub license: Option<String>,
+    pub content_id: Option<String>,
+    pub media_type: Option<String>,
+}
+
+impl Provider {
+    pub async fn launch(&self, device: &Device) -> Result<()> {
+        let content_id = self
+            .content_id
+            .as_deref()
+            .ok_or_else(|| Error::Argument(format!("no content id for channel {}", self.app_id)))?;
+        let mut link = DeepLink::new(content_id.to_string());
+        link.launch(self.app_id.clone());
+        if let Some(media_type) = &self.media_type {
+            match media_type.parse() {
+                Ok(media_type) => link.media_type(media_type),
+                Err(_) => link.param("mediaType".to_string(), media_type.clone()),
+            };
+        }
+        device.deep_link(&link).await
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    view: Vec<View>,
+}
+
+#[derive(Deserialize)]
+struct View {
+    content: Content,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Content {
+    title: String,
+    #[serde(rename = "type")]
+    content_type: String,
+    meta: Option<Meta>,
+    release_year: Option<u32>,
+    #[serde(default)]
+    view_options: Vec<ViewOption>,
+}
+
+#[derive(Deserialize)]
+struct Meta {
+    id: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ViewOption {
+    channel_id: String,
+    provider_name: Option<String>,
+    license: Option<String>,
+    media: Option<ViewMedia>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ViewMedia {
+    content_id: Option<String>,
+    media_type: Option<String>,
+}
+
+pub struct ContentSearch {
+    client: Client,
+    base_url: Url,
+}
+
+impl Default for ContentSearch {
+    fn default() -> ContentSearch {
+        ContentSearch::with_base_url(Url::parse(BASE_URL).unwrap())
+    }
+}
+
+impl ContentSearch {
+    pub fn new() -> ContentSearch {
+        ContentSearch::default()
+    }
+
+    pub fn with_base_url(base_url: Url) -> ContentSearch {
+        ContentSearch {
+            client: RokuConfig::global_client(),
+            base_url,
+        }
+    }
+
+    pub async fn search(&self, query: &str) -> Result<Vec<ContentResult>> {
+        let res = self
+            .client
+            .get(self.base_url.join("search")?)
+            .query(&[("query", query)])
+            .send()
+            .await?;
+        let text = res.error_for_status()?.text().await?;
+        let response: SearchResponse = serde_json::from_str(&text)
+            .map_err(|err| Error::Response(format!("invalid content search response: {}", err)))?;
+        Ok(response
+            .view
+            .into_iter()
+            .map(|view| {
+                let content = view.content;
+                ContentResult {
+                    id: content.meta.and_then(|meta| meta.id),
+                    title: content.title,
+                    content_type: content.content_type,
+                    release_year: content.release_year,
+                    providers: content
+                        .view_options
+                        .into_iter()
+                        .map(|option| {
+                            let media = option.media;
+                            Provider {
+                                app_id: option.channel_id,
+                                name: option.provider_name,
+                                license: option.license,
+                                content_id: media
+                                    .as_ref()
+                                    .and_then(|media| media.content_id.clone()),
+                                media_type: media.and_then(|media| media.media_type),
+                            }
+                        })
+                        .collect(),
+                }
+            })
+            .collect())
+    }
+
+    pub async fn search_and_play(&self, device: &Device, query: &str) -> Result<ContentResult> {
+        let installed = device.apps().await?.apps;
+        for result in self.search(query).await? {
+            let provider = result.providers.iter().find(|provider| {
+                provider.content_id.is_some()
+                    && installed
+                        .iter()
+                        .any(|app| app.id.as_deref() == Some(provider.app_id.as_str()))
+            });
+            if let Some(provider) = provider {
+                provider.launch(device).await?;
+                return Ok(result);
+            }
+        }
+        Err(Error::Response(format!(
+            "no installed channel offers {}",
+            query
+        )))
+    }
+}
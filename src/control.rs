@@ -0,0 +1,93 @@
+use crate::{
+    ActiveApp, App, Apps, Device, DeviceInfo, Endpoint, Key, Media, MediaPlayer, Result, Search,
+};
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[async_trait]
+pub trait RokuControl: Send + Sync {
+    async fn apps(&self) -> Result<Apps>;
+    async fn active_app(&self) -> Result<ActiveApp>;
+    async fn media_player(&self) -> Result<MediaPlayer>;
+    async fn device_info(&self) -> Result<DeviceInfo>;
+    async fn keydown(&self, key: &Key) -> Result<()>;
+    async fn keyup(&self, key: &Key) -> Result<()>;
+    async fn keypress(&self, key: &Key) -> Result<()>;
+    async fn launch(&self, app: &App) -> Result<()>;
+    async fn install(&self, app: &App) -> Result<()>;
+    async fn input(&self, input: &[(String, String)]) -> Result<()>;
+    async fn search(&self, search: Search) -> Result<()>;
+    async fn cast(&self, media: &Media) -> Result<()>;
+
+    fn invalidate(&self, _endpoint: Endpoint) {}
+
+    async fn type_text(&self, text: &str, interval: Duration) -> Result<()> {
+        for (i, c) in text.chars().enumerate() {
+            if i > 0 {
+                sleep(interval).await;
+            }
+            let key = match c {
+                '\n' => Key::Enter,
+                c => Key::Lit(c),
+            };
+            self.keypress(&key).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RokuControl for Device {
+    async fn apps(&self) -> Result<Apps> {
+        Device::apps(self).await
+    }
+
+    async fn active_app(&self) -> Result<ActiveApp> {
+        Device::active_app(self).await
+    }
+
+    async fn media_player(&self) -> Result<MediaPlayer> {
+        Device::media_player(self).await
+    }
+
+    async fn device_info(&self) -> Result<DeviceInfo> {
+        Device::device_info(self).await
+    }
+
+    async fn keydown(&self, key: &Key) -> Result<()> {
+        Device::keydown(self, key).await
+    }
+
+    async fn keyup(&self, key: &Key) -> Result<()> {
+        Device::keyup(self, key).await
+    }
+
+    async fn keypress(&self, key: &Key) -> Result<()> {
+        Device::keypress(self, key).await
+    }
+
+    async fn launch(&self, app: &App) -> Result<()> {
+        Device::launch(self, app).await
+    }
+
+    async fn install(&self, app: &App) -> Result<()> {
+        Device::install(self, app).await
+    }
+
+    async fn input(&self, input: &[(String, String)]) -> Result<()> {
+        Device::input(self, input).await
+    }
+
+    async fn search(&self, search: Search) -> Result<()> {
+        Device::search(self, search).await
+    }
+
+    async fn cast(&self, media: &Media) -> Result<()> {
+        Device::cast(self, media).await.map(drop)
+    }
+
+    fn invalidate(&self, endpoint: Endpoint) {
+        Device::invalidate(self, endpoint)
+    }
+}
@@ -0,0 +1,565 @@
+use crate::{Error, Result};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpStream,
+    },
+    sync::{mpsc, oneshot, Mutex as AsyncMutex},
+    task::JoinHandle,
+};
+
+pub const PORT: u16 = 8081;
+
+const MAGIC: u64 = 0x0067_7562_6564_7362;
+const CAPACITY: usize = 64;
+
+const STOP: u32 = 1;
+const CONTINUE: u32 = 2;
+const THREADS: u32 = 3;
+const STACKTRACE: u32 = 4;
+const VARIABLES: u32 = 5;
+const STEP: u32 = 6;
+const ADD_BREAKPOINTS: u32 = 7;
+const LIST_BREAKPOINTS: u32 = 8;
+const REMOVE_BREAKPOINTS: u32 = 9;
+const EXIT_CHANNEL: u32 = 122;
+
+const GET_CHILD_KEYS: u8 = 0x01;
+
+const IS_CHILD_KEY: u8 = 0x01;
+const IS_CONST: u8 = 0x02;
+const IS_CONTAINER: u8 = 0x04;
+const IS_NAME_HERE: u8 = 0x08;
+const IS_REF_COUNTED: u8 = 0x10;
+const IS_VALUE_HERE: u8 = 0x20;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StopReason {
+    Undefined,
+    NotStopped,
+    NormalExit,
+    StopStatement,
+    Break,
+    RuntimeError,
+}
+
+impl StopReason {
+    fn from_u8(value: u8) -> StopReason {
+        match value {
+            1 => StopReason::NotStopped,
+            2 => StopReason::NormalExit,
+            3 => StopReason::StopStatement,
+            4 => StopReason::Break,
+            5 => StopReason::RuntimeError,
+            _ => StopReason::Undefined,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepType {
+    Line,
+    Out,
+    Over,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "update", rename_all = "kebab-case")]
+pub enum Update {
+    ConnectIoPort {
+        port: u32,
+    },
+    AllThreadsStopped {
+        primary_thread: i32,
+        stop_reason: StopReason,
+        detail: String,
+    },
+    ThreadAttached {
+        thread: i32,
+        stop_reason: StopReason,
+        detail: String,
+    },
+    Other {
+        update_type: u32,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Thread {
+    pub primary: bool,
+    pub stop_reason: StopReason,
+    pub detail: String,
+    pub line_number: u32,
+    pub function_name: String,
+    pub file_name: String,
+    pub code_snippet: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Frame {
+    pub line_number: u32,
+    pub function_name: String,
+    pub file_path: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum Value {
+    Boolean(bool),
+    Double(f64),
+    Float(f32),
+    Integer(i32),
+    LongInteger(i64),
+    String(String),
+    Function(String),
+    Subroutine(String),
+    Interface(String),
+    Object(String),
+    SubtypedObject(String, String),
+    Container {
+        container_type: String,
+        element_count: u32,
+    },
+    Invalid,
+    Uninitialized,
+    Unknown(u8),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Variable {
+    pub name: Option<String>,
+    pub child_key: bool,
+    pub constant: bool,
+    pub ref_count: Option<u32>,
+    pub value: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Breakpoint {
+    pub file_path: String,
+    pub line_number: u32,
+    pub ignore_count: u32,
+}
+
+impl Breakpoint {
+    pub fn new(file_path: String, line_number: u32) -> Breakpoint {
+        Breakpoint {
+            file_path,
+            line_number,
+            ignore_count: 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct BreakpointInfo {
+    pub id: u32,
+    pub error_code: u32,
+    pub ignore_count: u32,
+}
+
+type Pending = Arc<Mutex<HashMap<u32, oneshot::Sender<Result<Vec<u8>>>>>>;
+
+pub struct Debugger {
+    writer: AsyncMutex<OwnedWriteHalf>,
+    pending: Pending,
+    next_id: AtomicU32,
+    updates: AsyncMutex<mpsc::Receiver<Update>>,
+    version: Version,
+    task: JoinHandle<()>,
+}
+
+impl Debugger {
+    pub async fn attach(host: &str) -> Result<Debugger> {
+        Debugger::attach_to(host, PORT).await
+    }
+
+    pub async fn attach_to(host: &str, port: u16) -> Result<Debugger> {
+        let mut stream = TcpStream::connect((host, port)).await?;
+        stream.write_all(&MAGIC.to_le_bytes()).await?;
+        if stream.read_u64_le().await? != MAGIC {
+            return Err(Error::Response("bad debugger handshake".to_string()));
+        }
+        let version = Version {
+            major: stream.read_u32_le().await?,
+            minor: stream.read_u32_le().await?,
+            patch: stream.read_u32_le().await?,
+        };
+        if version.major < 3 {
+            return Err(Error::Response(format!(
+                "unsupported debug protocol {}.{}.{}",
+                version.major, version.minor, version.patch
+            )));
+        }
+        let remaining = stream.read_u32_le().await?;
+        let mut rest = vec![0; remaining.saturating_sub(4) as usize];
+        stream.read_exact(&mut rest).await?;
+        let (reader, writer) = stream.into_split();
+        let pending: Pending = Arc::default();
+        let (tx, rx) = mpsc::channel(CAPACITY);
+        let task = tokio::spawn(read_packets(reader, Arc::clone(&pending), tx));
+        Ok(Debugger {
+            writer: AsyncMutex::new(writer),
+            pending,
+            next_id: AtomicU32::new(1),
+            updates: AsyncMutex::new(rx),
+            version,
+            task,
+        })
+    }
+
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    pub async fn next_update(&self) -> Option<Update> {
+        self.updates.lock().await.recv().await
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        self.request(STOP, vec![]).await?;
+        Ok(())
+    }
+
+    pub async fn resume(&self) -> Result<()> {
+        self.request(CONTINUE, vec![]).await?;
+        Ok(())
+    }
+
+    pub async fn exit_channel(&self) -> Result<()> {
+        self.request(EXIT_CHANNEL, vec![]).await?;
+        Ok(())
+    }
+
+    pub async fn threads(&self) -> Result<Vec<Thread>> {
+        let payload = self.request(THREADS, vec![]).await?;
+        let mut reader = Reader::new(&payload);
+        let count = reader.u32()?;
+        (0..count)
+            .map(|_| {
+                Ok(Thread {
+                    primary: reader.u8()? & 0x01 != 0,
+                    stop_reason: StopReason::from_u8(reader.u8()?),
+                    detail: reader.string()?,
+                    line_number: reader.u32()?,
+                    function_name: reader.string()?,
+                    file_name: reader.string()?,
+                    code_snippet: reader.string()?,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn stack_trace(&self, thread: u32) -> Result<Vec<Frame>> {
+        let payload = self
+            .request(STACKTRACE, thread.to_le_bytes().to_vec())
+            .await?;
+        let mut reader = Reader::new(&payload);
+        let count = reader.u32()?;
+        (0..count)
+            .map(|_| {
+                Ok(Frame {
+                    line_number: reader.u32()?,
+                    function_name: reader.string()?,
+                    file_path: reader.string()?,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn variables(
+        &self,
+        thread: u32,
+        frame: u32,
+        path: &[&str],
+        child_keys: bool,
+    ) -> Result<Vec<Variable>> {
+        let mut data = vec![if child_keys { GET_CHILD_KEYS } else { 0 }];
+        data.extend(thread.to_le_bytes());
+        data.extend(frame.to_le_bytes());
+        data.extend((path.len() as u32).to_le_bytes());
+        for entry in path {
+            push_string(&mut data, entry);
+        }
+        let payload = self.request(VARIABLES, data).await?;
+        let mut reader = Reader::new(&payload);
+        let count = reader.u32()?;
+        (0..count).map(|_| reader.variable()).collect()
+    }
+
+    pub async fn step(&self, thread: u32, step_type: StepType) -> Result<()> {
+        let mut data = thread.to_le_bytes().to_vec();
+        data.push(match step_type {
+            StepType::Line => 1,
+            StepType::Out => 2,
+            StepType::Over => 3,
+        });
+        self.request(STEP, data).await?;
+        Ok(())
+    }
+
+    pub async fn add_breakpoints(&self, breakpoints: &[Breakpoint]) -> Result<Vec<BreakpointInfo>> {
+        let mut data = (breakpoints.len() as u32).to_le_bytes().to_vec();
+        for breakpoint in breakpoints {
+            push_string(&mut data, &breakpoint.file_path);
+            data.extend(breakpoint.line_number.to_le_bytes());
+            data.extend(breakpoint.ignore_count.to_le_bytes());
+        }
+        let payload = self.request(ADD_BREAKPOINTS, data).await?;
+        Reader::new(&payload).breakpoints()
+    }
+
+    pub async fn list_breakpoints(&self) -> Result<Vec<BreakpointInfo>> {
+        let payload = self.request(LIST_BREAKPOINTS, vec![]).await?;
+        Reader::new(&payload).breakpoints()
+    }
+
+    pub async fn remove_breakpoints(&self, ids: &[u32]) -> Result<Vec<BreakpointInfo>> {
+        let mut data = (ids.len() as u32).to_le_bytes().to_vec();
+        for id in ids {
+            data.extend(id.to_le_bytes());
+        }
+        let payload = self.request(REMOVE_BREAKPOINTS, data).await?;
+        Reader::new(&payload).breakpoints()
+    }
+
+    async fn request(&self, command: u32, data: Vec<u8>) -> Result<Vec<u8>> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id, tx);
+        let mut packet = ((data.len() + 12) as u32).to_le_bytes().to_vec();
+        packet.extend(id.to_le_bytes());
+        packet.extend(command.to_le_bytes());
+        packet.extend(data);
+        if let Err(err) = self.writer.lock().await.write_all(&packet).await {
+            self.pending.lock().unwrap().remove(&id);
+            return Err(err.into());
+        }
+        rx.await
+            .map_err(|_| Error::Response("debugger disconnected".to_string()))?
+    }
+}
+
+impl Drop for Debugger {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn read_packets(mut reader: OwnedReadHalf, pending: Pending, updates: mpsc::Sender<Update>) {
+    loop {
+        let packet = match read_packet(&mut reader).await {
+            Ok(packet) => packet,
+            Err(_) => break,
+        };
+        let mut packet = Reader::new(&packet);
+        let (id, error_code) = match (packet.u32(), packet.u32()) {
+            (Ok(id), Ok(error_code)) => (id, error_code),
+            _ => break,
+        };
+        if id == 0 {
+            if let Ok(update) = packet.update() {
+                let _ = updates.send(update).await;
+            }
+            continue;
+        }
+        let result = match error_code {
+            0 => Ok(packet.rest().to_vec()),
+            code => Err(Error::Response(format!("debugger error {}", code))),
+        };
+        if let Some(tx) = pending.lock().unwrap().remove(&id) {
+            let _ = tx.send(result);
+        }
+    }
+    pending.lock().unwrap().clear();
+}
+
+async fn read_packet(reader: &mut OwnedReadHalf) -> Result<Vec<u8>> {
+    let length = reader.read_u32_le().await?;
+    if length < 12 {
+        return Err(Error::Response(format!("short debugger packet {}", length)));
+    }
+    let mut packet = vec![0; length as usize - 4];
+    reader.read_exact(&mut packet).await?;
+    Ok(packet)
+}
+
+fn push_string(data: &mut Vec<u8>, value: &str) {
+    data.extend(value.as_bytes());
+    data.push(0);
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Reader<'a> {
+        Reader { data }
+    }
+
+    fn take(&mut self, count: usize) -> Result<&'a [u8]> {
+        if self.data.len() < count {
+            return Err(Error::Response("truncated debugger packet".to_string()));
+        }
+        let (head, tail) = self.data.split_at(count);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        std::mem::take(&mut self.data)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        Ok(i32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn i64(&mut self) -> Result<i64> {
+        Ok(i64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn f32(&mut self) -> Result<f32> {
+        Ok(f32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f64(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let end = self
+            .data
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| Error::Response("unterminated debugger string".to_string()))?;
+        let value = String::from_utf8_lossy(&self.data[..end]).into_owned();
+        self.data = &self.data[end + 1..];
+        Ok(value)
+    }
+
+    fn update(&mut self) -> Result<Update> {
+        Ok(match self.u32()? {
+            1 => Update::ConnectIoPort { port: self.u32()? },
+            2 => Update::AllThreadsStopped {
+                primary_thread: self.i32()?,
+                stop_reason: StopReason::from_u8(self.u8()?),
+                detail: self.string()?,
+            },
+            3 => Update::ThreadAttached {
+                thread: self.i32()?,
+                stop_reason: StopReason::from_u8(self.u8()?),
+                detail: self.string()?,
+            },
+            update_type => Update::Other { update_type },
+        })
+    }
+
+    fn breakpoints(&mut self) -> Result<Vec<BreakpointInfo>> {
+        let count = self.u32()?;
+        (0..count)
+            .map(|_| {
+                let id = self.u32()?;
+                let error_code = self.u32()?;
+                let ignore_count = if id != 0 { self.u32()? } else { 0 };
+                Ok(BreakpointInfo {
+                    id,
+                    error_code,
+                    ignore_count,
+                })
+            })
+            .collect()
+    }
+
+    fn variable(&mut self) -> Result<Variable> {
+        let flags = self.u8()?;
+        let variable_type = self.u8()?;
+        let name = if flags & IS_NAME_HERE != 0 {
+            Some(self.string()?)
+        } else {
+            None
+        };
+        let ref_count = if flags & IS_REF_COUNTED != 0 {
+            Some(self.u32()?)
+        } else {
+            None
+        };
+        let value = if flags & IS_CONTAINER != 0 {
+            let _key_type = self.u8()?;
+            Value::Container {
+                container_type: container_type(variable_type).to_string(),
+                element_count: self.u32()?,
+            }
+        } else if flags & IS_VALUE_HERE != 0 {
+            self.value(variable_type)?
+        } else {
+            match variable_type {
+                9 => Value::Invalid,
+                16 => Value::Uninitialized,
+                other => Value::Unknown(other),
+            }
+        };
+        Ok(Variable {
+            name,
+            child_key: flags & IS_CHILD_KEY != 0,
+            constant: flags & IS_CONST != 0,
+            ref_count,
+            value,
+        })
+    }
+
+    fn value(&mut self, variable_type: u8) -> Result<Value> {
+        Ok(match variable_type {
+            3 => Value::Boolean(self.u8()? != 0),
+            4 => Value::Double(self.f64()?),
+            5 => Value::Float(self.f32()?),
+            6 => Value::Function(self.string()?),
+            7 => Value::Integer(self.i32()?),
+            8 => Value::Interface(self.string()?),
+            9 => Value::Invalid,
+            11 => Value::LongInteger(self.i64()?),
+            12 => Value::Object(self.string()?),
+            13 => Value::String(self.string()?),
+            14 => Value::Subroutine(self.string()?),
+            15 => Value::SubtypedObject(self.string()?, self.string()?),
+            16 => Value::Uninitialized,
+            other => Value::Unknown(other),
+        })
+    }
+}
+
+fn container_type(variable_type: u8) -> &'static str {
+    match variable_type {
+        1 => "roAssociativeArray",
+        2 => "roArray",
+        10 => "roList",
+        12 => "roSGNode",
+        15 => "SubtypedObject",
+        _ => "Object",
+    }
+}
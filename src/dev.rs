@@ -0,0 +1,383 @@
+use crate::{consts, App, Device, Error, Image, Result, RokuConfig};
+use digest_auth::{AuthContext, HttpMethod};
+use reqwest::{
+    header::{AUTHORIZATION, WWW_AUTHENTICATE},
+    multipart::{Form, Part},
+    Client, Method, RequestBuilder, Response, StatusCode,
+};
+use serde::Serialize;
+use std::{
+    borrow::Cow,
+    fs as std_fs,
+    io::{self, Cursor, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    fs,
+    time::{sleep, Instant},
+};
+use url::{Position, Url};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+pub const USERNAME: &str = "rokudev";
+pub const DEV_APP_ID: &str = consts::APP_DEV;
+pub const IGNORE_FILE: &str = ".rokuignore";
+
+const LAUNCH_POLL: Duration = Duration::from_millis(500);
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "result", content = "messages", rename_all = "kebab-case")]
+pub enum SideloadResult {
+    Installed,
+    Identical,
+    CompilationFailed(Vec<String>),
+    Failed(Vec<String>),
+}
+
+impl SideloadResult {
+    pub fn parse(html: &str) -> SideloadResult {
+        let messages = messages(html);
+        let any = |text: &str| messages.iter().any(|message| message.contains(text));
+        if any("Compilation Failed") {
+            SideloadResult::CompilationFailed(messages)
+        } else if any("Install Failure") {
+            SideloadResult::Failed(messages)
+        } else if any("Identical to previous version") {
+            SideloadResult::Identical
+        } else if any("Install Success") || any("Received new package") {
+            SideloadResult::Installed
+        } else {
+            SideloadResult::Failed(messages)
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self, SideloadResult::Installed | SideloadResult::Identical)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DevServer {
+    url: Url,
+    username: String,
+    password: String,
+    client: Client,
+}
+
+impl DevServer {
+    pub fn new(url: Url, password: String) -> DevServer {
+        DevServer {
+            url,
+            username: USERNAME.to_string(),
+            password,
+            client: RokuConfig::global_client(),
+        }
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub async fn screenshot(&self) -> Result<Image> {
+        let res = self
+            .send(Method::POST, "plugin_inspect", |req| {
+                req.multipart(
+                    Form::new()
+                        .text("mysubmit", "Screenshot")
+                        .part("archive", Part::bytes(vec![]).file_name("")),
+                )
+            })
+            .await?;
+        let html = res.text().await?;
+        let path = links(&html, "pkgs/dev.")
+            .next()
+            .ok_or_else(|| Error::Response("no screenshot in plugin_inspect".to_string()))?;
+        let res = self.send(Method::GET, path, |req| req).await?;
+        Image::from_response(res).await
+    }
+
+    pub async fn profile(&self) -> Result<Vec<u8>> {
+        let res = self
+            .send(Method::POST, "plugin_inspect", |req| {
+                req.multipart(
+                    Form::new()
+                        .text("mysubmit", "Profiler")
+                        .part("archive", Part::bytes(vec![]).file_name("")),
+                )
+            })
+            .await?;
+        let html = res.text().await?;
+        let path = links(&html, "pkgs/")
+            .find(|link| link.contains(".bsprof"))
+            .ok_or_else(|| Error::Response("no profile in plugin_inspect".to_string()))?;
+        let res = self.send(Method::GET, path, |req| req).await?;
+        Ok(res.bytes().await?.to_vec())
+    }
+
+    pub async fn sideload(&self, path: impl AsRef<Path>) -> Result<SideloadResult> {
+        let path = path.as_ref();
+        let archive = fs::read(path).await?;
+        let file_name = path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "channel.zip".to_string());
+        self.sideload_archive(archive, file_name).await
+    }
+
+    pub async fn sideload_archive(
+        &self,
+        archive: Vec<u8>,
+        file_name: String,
+    ) -> Result<SideloadResult> {
+        let res = self
+            .send(Method::POST, "plugin_install", |req| {
+                req.multipart(Form::new().text("mysubmit", "Install").part(
+                    "archive",
+                    Part::bytes(archive.clone()).file_name(file_name.clone()),
+                ))
+            })
+            .await?;
+        Ok(SideloadResult::parse(&res.text().await?))
+    }
+
+    pub async fn delete_sideloaded(&self) -> Result<()> {
+        self.send(Method::POST, "plugin_install", |req| {
+            req.multipart(
+                Form::new()
+                    .text("mysubmit", "Delete")
+                    .part("archive", Part::bytes(vec![]).file_name("")),
+            )
+        })
+        .await?;
+        Ok(())
+    }
+
+    pub async fn package(&self, app_name: &str, password: &str) -> Result<Vec<u8>> {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+            .to_string();
+        let res = self
+            .send(Method::POST, "plugin_package", |req| {
+                req.multipart(
+                    Form::new()
+                        .text("mysubmit", "Package")
+                        .text("app_name", app_name.to_string())
+                        .text("passwd", password.to_string())
+                        .text("pkg_time", time.clone()),
+                )
+            })
+            .await?;
+        let html = res.text().await?;
+        let path = links(&html, "pkgs/")
+            .find(|link| link.ends_with(".pkg"))
+            .ok_or_else(|| Error::Response("no package in plugin_package".to_string()))?;
+        let res = self.send(Method::GET, path, |req| req).await?;
+        Ok(res.bytes().await?.to_vec())
+    }
+
+    pub async fn rekey(&self, pkg: Vec<u8>, password: &str) -> Result<()> {
+        let res = self
+            .send(Method::POST, "plugin_inspect", |req| {
+                req.multipart(
+                    Form::new()
+                        .text("mysubmit", "Rekey")
+                        .text("passwd", password.to_string())
+                        .part("archive", Part::bytes(pkg.clone()).file_name("rekey.pkg")),
+                )
+            })
+            .await?;
+        let html = res.text().await?;
+        if !html.contains("Success") {
+            return Err(Error::Response("rekey failed".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn send<F>(&self, method: Method, path: &str, build: F) -> Result<Response>
+    where
+        F: Fn(RequestBuilder) -> RequestBuilder,
+    {
+        let url = self.url.join(path)?;
+        let res = build(self.client.request(method.clone(), url.clone()))
+            .send()
+            .await?;
+        if res.status() != StatusCode::UNAUTHORIZED {
+            return Ok(res.error_for_status()?);
+        }
+        let challenge = res
+            .headers()
+            .get(WWW_AUTHENTICATE)
+            .and_then(|challenge| challenge.to_str().ok())
+            .ok_or_else(|| Error::Response("missing digest challenge".to_string()))?;
+        let mut prompt = digest_auth::parse(challenge)?;
+        let context = AuthContext::new_with_method(
+            self.username.as_str(),
+            self.password.as_str(),
+            &url[Position::BeforePath..],
+            None::<&[u8]>,
+            HttpMethod(Cow::Owned(method.to_string())),
+        );
+        let answer = prompt.respond(&context)?;
+        let res = build(self.client.request(method, url))
+            .header(AUTHORIZATION, answer.to_header_string())
+            .send()
+            .await?;
+        Ok(res.error_for_status()?)
+    }
+}
+
+pub async fn deploy(
+    source_dir: impl AsRef<Path>,
+    device: &Device,
+    password: String,
+) -> Result<SideloadResult> {
+    let archive = zip_channel(source_dir.as_ref())?;
+    let result = device
+        .dev_server(password)?
+        .sideload_archive(archive, "channel.zip".to_string())
+        .await?;
+    match &result {
+        SideloadResult::CompilationFailed(messages) | SideloadResult::Failed(messages) => {
+            return Err(Error::Response(messages.join("\n")));
+        }
+        SideloadResult::Installed | SideloadResult::Identical => {}
+    }
+    let dev = App {
+        id: Some(DEV_APP_ID.to_string()),
+        name: String::new(),
+        version: None,
+    };
+    device.launch(&dev).await?;
+    let start = Instant::now();
+    loop {
+        let active = device.active_app().await?;
+        if active.app.id.as_deref() == Some(DEV_APP_ID) {
+            return Ok(result);
+        }
+        if start.elapsed() > LAUNCH_TIMEOUT {
+            return Err(Error::Response(format!(
+                "dev channel not active after {}s",
+                LAUNCH_TIMEOUT.as_secs()
+            )));
+        }
+        sleep(LAUNCH_POLL).await;
+    }
+}
+
+pub fn zip_channel(source_dir: &Path) -> Result<Vec<u8>> {
+    if !source_dir.join("manifest").is_file() {
+        return Err(Error::Argument(format!(
+            "no manifest in {}",
+            source_dir.display()
+        )));
+    }
+    let ignore = match std_fs::read_to_string(source_dir.join(IGNORE_FILE)) {
+        Ok(text) => text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.trim_matches('/').to_string())
+            .collect(),
+        Err(_) => vec![],
+    };
+    let mut files = vec![];
+    collect_files(source_dir, source_dir, &ignore, &mut files)?;
+    files.sort();
+    let mut writer = ZipWriter::new(Cursor::new(vec![]));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, path) in files {
+        writer.start_file(name, options).map_err(io::Error::from)?;
+        writer.write_all(&std_fs::read(path)?)?;
+    }
+    Ok(writer.finish().map_err(io::Error::from)?.into_inner())
+}
+
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    ignore: &[String],
+    files: &mut Vec<(String, PathBuf)>,
+) -> Result<()> {
+    for entry in std_fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let hidden = path
+            .file_name()
+            .is_some_and(|file_name| file_name.to_string_lossy().starts_with('.'));
+        if hidden || ignored(&name, ignore) {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(root, &path, ignore, files)?;
+        } else {
+            files.push((name, path));
+        }
+    }
+    Ok(())
+}
+
+fn ignored(name: &str, ignore: &[String]) -> bool {
+    ignore
+        .iter()
+        .any(|pattern| match pattern.strip_prefix('*') {
+            Some(suffix) => name.ends_with(suffix),
+            None => name == pattern || name.starts_with(&format!("{}/", pattern)),
+        })
+}
+
+fn messages(html: &str) -> Vec<String> {
+    const CONTENT: &str = "'Set message content', '";
+    let mut messages = vec![];
+    for (start, _) in html.match_indices(CONTENT) {
+        let rest = &html[start + CONTENT.len()..];
+        let mut message = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('n') => message.push('\n'),
+                    Some(c) => message.push(c),
+                    None => break,
+                },
+                '\'' => break,
+                c => message.push(c),
+            }
+        }
+        messages.push(message);
+    }
+    for (start, _) in html.match_indices("<font color=\"red\">") {
+        let rest = &html[start..];
+        let rest = &rest[rest.find('>').unwrap_or(0) + 1..];
+        if let Some(end) = rest.find("</font>") {
+            messages.push(rest[..end].trim().to_string());
+        }
+    }
+    messages
+        .into_iter()
+        .flat_map(|message| {
+            message
+                .split('\n')
+                .map(|line| line.trim().to_string())
+                .collect::<Vec<_>>()
+        })
+        .filter(|message| !message.is_empty())
+        .collect()
+}
+
+fn links<'a>(html: &'a str, prefix: &'a str) -> impl Iterator<Item = &'a str> {
+    html.match_indices(prefix).filter_map(move |(start, _)| {
+        let rest = &html[start..];
+        let end = rest.find(['"', '\''])?;
+        Some(&rest[..end])
+    })
+}
@@ -0,0 +1,253 @@
+use crate::{
+    ssdp::{self, Message},
+    Device, DeviceIdentity, Error, Result,
+};
+use async_trait::async_trait;
+use futures_util::{future::join_all, stream::Stream};
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    net::UdpSocket,
+    sync::mpsc,
+    task::JoinHandle,
+    time::{timeout_at, Instant},
+};
+use url::Url;
+
+const SEARCH_TARGET: &str = "roku:ecp";
+const CAPACITY: usize = 64;
+const BUFFER: usize = 2048;
+
+#[async_trait]
+pub trait Discoverer: Send + Sync {
+    async fn discover(&self) -> Result<Vec<Device>>;
+}
+
+#[derive(Clone, Debug)]
+pub struct SsdpDiscoverer {
+    timeout: Duration,
+    mx: usize,
+    ttl: u32,
+    interface: Option<Ipv4Addr>,
+    ipv6: bool,
+    ipv6_interface: u32,
+    destination: Option<SocketAddr>,
+}
+
+impl SsdpDiscoverer {
+    pub fn new() -> SsdpDiscoverer {
+        SsdpDiscoverer {
+            timeout: Duration::from_secs(3),
+            mx: 2,
+            ttl: 2,
+            interface: None,
+            ipv6: false,
+            ipv6_interface: 0,
+            destination: None,
+        }
+    }
+
+    pub fn timeout(&mut self, timeout: Duration) -> &mut SsdpDiscoverer {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn mx(&mut self, mx: usize) -> &mut SsdpDiscoverer {
+        self.mx = mx;
+        self
+    }
+
+    pub fn ttl(&mut self, ttl: u32) -> &mut SsdpDiscoverer {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn interface(&mut self, interface: Ipv4Addr) -> &mut SsdpDiscoverer {
+        self.interface = Some(interface);
+        self
+    }
+
+    pub fn ipv6(&mut self, ipv6: bool) -> &mut SsdpDiscoverer {
+        self.ipv6 = ipv6;
+        self
+    }
+
+    pub fn ipv6_interface(&mut self, index: u32) -> &mut SsdpDiscoverer {
+        self.ipv6 = true;
+        self.ipv6_interface = index;
+        self
+    }
+
+    pub fn destination(&mut self, destination: SocketAddr) -> &mut SsdpDiscoverer {
+        self.destination = Some(destination);
+        self
+    }
+
+    pub async fn listen(&self) -> Result<Notifications> {
+        let (tx, rx) = mpsc::channel(CAPACITY);
+        let mut tasks = vec![];
+        for group in self.groups() {
+            let socket = ssdp::notify_socket(&group, self.interface, self.ipv6_interface)
+                .map_err(Error::SSDPRequest)?;
+            let tx = tx.clone();
+            tasks.push(tokio::spawn(async move {
+                let mut buf = vec![0; BUFFER];
+                loop {
+                    let notification = match socket.recv_from(&mut buf).await {
+                        Ok((len, _)) => match Notification::parse(&buf[..len]) {
+                            Some(notification) => Ok(notification),
+                            None => continue,
+                        },
+                        Err(err) => Err(Error::SSDPRequest(err)),
+                    };
+                    if tx.send(notification).await.is_err() {
+                        return;
+                    }
+                }
+            }));
+        }
+        Ok(Notifications { rx, tasks })
+    }
+
+    fn groups(&self) -> Vec<SocketAddr> {
+        let mut groups = vec![ssdp::multicast_group(false)];
+        if self.ipv6 {
+            groups.push(ssdp::multicast_group(true));
+        }
+        groups
+    }
+
+    async fn search(&self, destination: SocketAddr, deadline: Instant) -> Result<Vec<Device>> {
+        let socket =
+            ssdp::search_socket(&destination, self.interface, self.ipv6_interface, self.ttl)
+                .map_err(Error::SSDPRequest)?;
+        let request = ssdp::search_request(&destination, SEARCH_TARGET, self.mx);
+        socket
+            .send_to(request.as_bytes(), destination)
+            .await
+            .map_err(Error::SSDPRequest)?;
+        receive(&socket, deadline).await
+    }
+}
+
+impl Default for SsdpDiscoverer {
+    fn default() -> SsdpDiscoverer {
+        SsdpDiscoverer::new()
+    }
+}
+
+#[async_trait]
+impl Discoverer for SsdpDiscoverer {
+    async fn discover(&self) -> Result<Vec<Device>> {
+        let deadline = Instant::now() + self.timeout;
+        let destinations = match self.destination {
+            Some(destination) => vec![destination],
+            None => self.groups(),
+        };
+        let searches = destinations
+            .iter()
+            .map(|destination| self.search(*destination, deadline));
+        let mut devices: Vec<Device> = vec![];
+        for (destination, found) in destinations.iter().zip(join_all(searches).await) {
+            let found = match found {
+                Err(_) if self.destination.is_none() && destination.is_ipv6() => vec![],
+                found => found?,
+            };
+            for device in found {
+                if !devices.iter().any(|known| same_device(known, &device)) {
+                    devices.push(device);
+                }
+            }
+        }
+        Ok(devices)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Notification {
+    Alive(Device),
+    ByeBye(DeviceIdentity),
+}
+
+impl Notification {
+    fn parse(data: &[u8]) -> Option<Notification> {
+        let message = Message::parse(data)?;
+        if !message.is_notify() || message.header("NT") != Some(SEARCH_TARGET) {
+            return None;
+        }
+        let identity = DeviceIdentity::from_usn(message.header("USN")?);
+        match message.header("NTS")? {
+            "ssdp:alive" => {
+                let device = Device::new(Url::parse(message.header("LOCATION")?).ok()?);
+                Some(Notification::Alive(match identity {
+                    Some(identity) => device.with_identity(identity),
+                    None => device,
+                }))
+            }
+            "ssdp:byebye" => Some(Notification::ByeBye(identity?)),
+            _ => None,
+        }
+    }
+}
+
+pub struct Notifications {
+    rx: mpsc::Receiver<Result<Notification>>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Stream for Notifications {
+    type Item = Result<Notification>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Result<Notification>>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for Notifications {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+async fn receive(socket: &UdpSocket, deadline: Instant) -> Result<Vec<Device>> {
+    let mut devices = vec![];
+    let mut buf = vec![0; BUFFER];
+    while let Ok(received) = timeout_at(deadline, socket.recv_from(&mut buf)).await {
+        let (len, _) = received.map_err(Error::SSDPRequest)?;
+        let message = match Message::parse(&buf[..len]) {
+            Some(message) => message,
+            None => continue,
+        };
+        if !message.is_response() || message.header("ST") != Some(SEARCH_TARGET) {
+            continue;
+        }
+        let location = match message.header("LOCATION") {
+            Some(location) => location,
+            None => continue,
+        };
+        let device = match Url::parse(location) {
+            Ok(url) => Device::new(url),
+            Err(_) => continue,
+        };
+        devices.push(
+            match message.header("USN").and_then(DeviceIdentity::from_usn) {
+                Some(identity) => device.with_identity(identity),
+                None => device,
+            },
+        );
+    }
+    Ok(devices)
+}
+
+fn same_device(a: &Device, b: &Device) -> bool {
+    match (&a.identity, &b.identity) {
+        (Some(a), Some(b)) => a == b,
+        _ => a.url() == b.url(),
+    }
+}
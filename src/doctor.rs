@@ -0,0 +1,177 @@
+use crate::{Device, Discoverer, Error, RokuConfig};
+use serde::Serialize;
+use std::time::Duration;
+use tokio::{net::TcpStream, time::timeout};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+const LIMITED_HINT: &str = "set Settings > System > Advanced system settings > \
+                            Control by mobile apps > Network access to Default or Permissive";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &str, status: CheckStatus, detail: String) -> Check {
+        Check {
+            name: name.to_string(),
+            status,
+            detail,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Report {
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    pub fn is_ok(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|check| check.status != CheckStatus::Fail)
+    }
+}
+
+pub async fn doctor(device: Option<&Device>) -> Report {
+    doctor_with(&*RokuConfig::global().discoverer(), device).await
+}
+
+pub async fn doctor_with(discoverer: &dyn Discoverer, device: Option<&Device>) -> Report {
+    let mut report = Report::default();
+    let discovered = multicast(&mut report, discoverer).await;
+    let device = match device.or_else(|| discovered.first()) {
+        Some(device) => device,
+        None => return report,
+    };
+    if !port(&mut report, device).await {
+        return report;
+    }
+    ecp(&mut report, device).await;
+    report
+}
+
+async fn multicast(report: &mut Report, discoverer: &dyn Discoverer) -> Vec<Device> {
+    let result = discoverer.discover().await;
+    let (status, detail, devices) = match result {
+        Ok(devices) if devices.is_empty() => (
+            CheckStatus::Warn,
+            "no device answered the SSDP search; multicast may be blocked by a VPN, \
+             firewall or access point isolation"
+                .to_string(),
+            devices,
+        ),
+        Ok(devices) => (
+            CheckStatus::Pass,
+            format!("{} device(s) answered the SSDP search", devices.len()),
+            devices,
+        ),
+        Err(err) => (
+            CheckStatus::Fail,
+            format!("failed to send the SSDP search: {}", err),
+            vec![],
+        ),
+    };
+    report.checks.push(Check::new("multicast", status, detail));
+    devices
+}
+
+async fn port(report: &mut Report, device: &Device) -> bool {
+    let url = device.url();
+    let host = url.host_str().unwrap_or_default();
+    let port = url.port_or_known_default().unwrap_or(8060);
+    let (status, detail) = match timeout(CONNECT_TIMEOUT, TcpStream::connect((host, port))).await {
+        Ok(Ok(_)) => (
+            CheckStatus::Pass,
+            format!("{}:{} accepts connections", host, port),
+        ),
+        Ok(Err(err)) => (
+            CheckStatus::Fail,
+            format!("{}:{} refused the connection: {}", host, port, err),
+        ),
+        Err(_) => (
+            CheckStatus::Fail,
+            format!(
+                "{}:{} did not answer within {}s",
+                host,
+                port,
+                CONNECT_TIMEOUT.as_secs()
+            ),
+        ),
+    };
+    report.checks.push(Check::new("port", status, detail));
+    status == CheckStatus::Pass
+}
+
+async fn ecp(report: &mut Report, device: &Device) {
+    let info = match device.device_info().await {
+        Ok(info) => info,
+        Err(err) => {
+            report.checks.push(Check::new(
+                "ecp",
+                CheckStatus::Fail,
+                format!("query/device-info failed: {}", err),
+            ));
+            return;
+        }
+    };
+    let limited = match device.apps().await {
+        Err(Error::Ecp(err)) if err.code == 403 => true,
+        _ => info.ecp_setting_mode.as_deref() == Some("limited"),
+    };
+    report.checks.push(if limited {
+        Check::new(
+            "ecp",
+            CheckStatus::Fail,
+            format!("ECP is in limited mode; {}", LIMITED_HINT),
+        )
+    } else {
+        Check::new(
+            "ecp",
+            CheckStatus::Pass,
+            format!("{} answers ECP queries", info.user_device_name),
+        )
+    });
+    report
+        .checks
+        .push(firmware(&info.software_version, &info.software_build));
+}
+
+fn firmware(version: &str, build: &str) -> Check {
+    let major = version
+        .split('.')
+        .next()
+        .and_then(|major| major.parse::<u32>().ok());
+    match major {
+        Some(major) if major < 9 => Check::new(
+            "firmware",
+            CheckStatus::Warn,
+            format!(
+                "Roku OS {} build {} predates 9.0; tv-channels, sgnodes and keydown/keyup may be missing",
+                version, build
+            ),
+        ),
+        Some(_) => Check::new(
+            "firmware",
+            CheckStatus::Pass,
+            format!("Roku OS {} build {}", version, build),
+        ),
+        None => Check::new(
+            "firmware",
+            CheckStatus::Warn,
+            format!("unrecognized software version {}", version),
+        ),
+    }
+}
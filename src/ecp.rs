@@ -0,0 +1,104 @@
+use crate::{Error, Result};
+use reqwest::Response;
+use serde::Serialize;
+use std::fmt;
+use xml::reader::{EventReader, XmlEvent};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct EcpError {
+    pub code: u16,
+    pub message: String,
+}
+
+impl fmt::Display for EcpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.code, self.message)
+    }
+}
+
+impl EcpError {
+    pub fn parse(code: u16, body: &str) -> EcpError {
+        let message = message(body).unwrap_or_else(|| {
+            reqwest::StatusCode::from_u16(code)
+                .ok()
+                .and_then(|status| status.canonical_reason())
+                .unwrap_or("request failed")
+                .to_string()
+        });
+        EcpError { code, message }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct RawResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+impl RawResponse {
+    pub(crate) async fn read(res: Response) -> Result<RawResponse> {
+        let status = res.status().as_u16();
+        let content_type = res
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = res.text().await?;
+        Ok(RawResponse {
+            status,
+            content_type,
+            body,
+        })
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn error_for_status(self) -> Result<RawResponse> {
+        if self.status >= 400 {
+            return Err(Error::Ecp(EcpError::parse(self.status, &self.body)));
+        }
+        Ok(self)
+    }
+}
+
+pub(crate) async fn check(res: Response) -> Result<Response> {
+    let status = res.status();
+    if !status.is_client_error() && !status.is_server_error() {
+        return Ok(res);
+    }
+    let body = res.text().await.unwrap_or_default();
+    Err(Error::Ecp(EcpError::parse(status.as_u16(), &body)))
+}
+
+fn message(body: &str) -> Option<String> {
+    let body = body.trim();
+    if body.is_empty() {
+        return None;
+    }
+    if !body.starts_with('<') {
+        return Some(body.to_string());
+    }
+    let mut texts = vec![];
+    let mut error = None;
+    let mut in_error = false;
+    for event in EventReader::from_str(body) {
+        match event.ok()? {
+            XmlEvent::StartElement { name, .. } if name.local_name == "error" => in_error = true,
+            XmlEvent::EndElement { name } if name.local_name == "error" => in_error = false,
+            XmlEvent::Characters(text) | XmlEvent::CData(text) => {
+                let text = text.trim().to_string();
+                if in_error {
+                    error = Some(text.clone());
+                }
+                texts.push(text);
+            }
+            _ => {}
+        }
+    }
+    error
+        .or_else(|| Some(texts.join(" ")))
+        .filter(|message| !message.is_empty())
+}
@@ -0,0 +1,289 @@
+use crate::{App, Device, Result};
+use futures_util::stream::{self, BoxStream, Stream};
+use serde::Serialize;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{sync::Notify, task::JoinHandle, time::sleep};
+
+const CAPACITY: usize = 64;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum Event {
+    AppChanged {
+        previous: Option<App>,
+        current: App,
+    },
+    PlaybackChanged {
+        previous: Option<String>,
+        current: String,
+        position: Option<String>,
+    },
+    PowerChanged {
+        previous: Option<String>,
+        current: String,
+    },
+}
+
+impl Event {
+    fn coalesce(&mut self, later: &Event) -> bool {
+        match (self, later) {
+            (
+                Event::AppChanged { current, .. },
+                Event::AppChanged {
+                    current: latest, ..
+                },
+            ) => *current = latest.clone(),
+            (
+                Event::PlaybackChanged {
+                    current, position, ..
+                },
+                Event::PlaybackChanged {
+                    current: latest,
+                    position: latest_position,
+                    ..
+                },
+            ) => {
+                *current = latest.clone();
+                *position = latest_position.clone();
+            }
+            (
+                Event::PowerChanged { current, .. },
+                Event::PowerChanged {
+                    current: latest, ..
+                },
+            ) => *current = latest.clone(),
+            _ => return false,
+        }
+        true
+    }
+
+    fn is_noop(&self) -> bool {
+        match self {
+            Event::AppChanged { previous, current } => previous.as_ref() == Some(current),
+            Event::PlaybackChanged {
+                previous, current, ..
+            } => previous.as_ref() == Some(current),
+            Event::PowerChanged { previous, current } => previous.as_ref() == Some(current),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Overflow {
+    DropOldest,
+    Coalesce,
+    Block,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventOptions {
+    interval: Duration,
+    capacity: usize,
+    overflow: Overflow,
+}
+
+impl Default for EventOptions {
+    fn default() -> EventOptions {
+        EventOptions {
+            interval: Duration::from_secs(1),
+            capacity: CAPACITY,
+            overflow: Overflow::Block,
+        }
+    }
+}
+
+impl EventOptions {
+    pub fn new() -> EventOptions {
+        EventOptions::default()
+    }
+
+    pub fn interval(&mut self, interval: Duration) -> &mut Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn capacity(&mut self, capacity: usize) -> &mut Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    pub fn overflow(&mut self, overflow: Overflow) -> &mut Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn watch(&self, device: &Device) -> Events {
+        let buffer = Arc::new(Buffer {
+            queue: Mutex::new(VecDeque::new()),
+            available: Notify::new(),
+            space: Notify::new(),
+            capacity: self.capacity,
+            overflow: self.overflow,
+            dropped: AtomicU64::new(0),
+        });
+        let device = device.clone();
+        let interval = self.interval;
+        let task = tokio::spawn({
+            let buffer = buffer.clone();
+            async move {
+                let mut state = State::default();
+                loop {
+                    for event in poll(&device, &mut state).await {
+                        buffer.push(event).await;
+                    }
+                    sleep(interval).await;
+                }
+            }
+        });
+        let stream = stream::unfold(buffer.clone(), |buffer| async move {
+            let event = buffer.pop().await;
+            Some((event, buffer))
+        });
+        Events {
+            stream: Box::pin(stream),
+            buffer,
+            task,
+        }
+    }
+}
+
+pub struct Events {
+    stream: BoxStream<'static, Result<Event>>,
+    buffer: Arc<Buffer>,
+    task: JoinHandle<()>,
+}
+
+impl Events {
+    pub fn dropped(&self) -> u64 {
+        self.buffer.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn pending(&self) -> usize {
+        self.buffer.queue.lock().unwrap().len()
+    }
+}
+
+impl Stream for Events {
+    type Item = Result<Event>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Result<Event>>> {
+        self.stream.as_mut().poll_next(cx)
+    }
+}
+
+struct Buffer {
+    queue: Mutex<VecDeque<Result<Event>>>,
+    available: Notify,
+    space: Notify,
+    capacity: usize,
+    overflow: Overflow,
+    dropped: AtomicU64,
+}
+
+impl Buffer {
+    async fn push(&self, event: Result<Event>) {
+        loop {
+            {
+                let mut queue = self.queue.lock().unwrap();
+                if self.overflow == Overflow::Coalesce && queue.len() >= self.capacity {
+                    if let Ok(event) = &event {
+                        let index = queue.iter_mut().position(|queued| match queued {
+                            Ok(queued) => queued.coalesce(event),
+                            Err(_) => false,
+                        });
+                        if let Some(index) = index {
+                            if matches!(&queue[index], Ok(queued) if queued.is_noop()) {
+                                queue.remove(index);
+                            }
+                            return;
+                        }
+                    }
+                }
+                if queue.len() < self.capacity || self.overflow != Overflow::Block {
+                    if queue.len() >= self.capacity {
+                        queue.pop_front();
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                    queue.push_back(event);
+                    self.available.notify_one();
+                    return;
+                }
+            }
+            self.space.notified().await;
+        }
+    }
+
+    async fn pop(&self) -> Result<Event> {
+        loop {
+            if let Some(event) = self.queue.lock().unwrap().pop_front() {
+                self.space.notify_one();
+                return event;
+            }
+            self.available.notified().await;
+        }
+    }
+}
+
+impl Drop for Events {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[derive(Default)]
+struct State {
+    app: Option<App>,
+    playback: Option<String>,
+    power: Option<String>,
+}
+
+async fn poll(device: &Device, state: &mut State) -> Vec<Result<Event>> {
+    let mut events = vec![];
+    match device.device_info().await {
+        Ok(info) => {
+            if state.power.as_ref() != Some(&info.power_mode) {
+                events.push(Ok(Event::PowerChanged {
+                    previous: state.power.replace(info.power_mode.clone()),
+                    current: info.power_mode,
+                }));
+            }
+        }
+        Err(err) => {
+            events.push(Err(err));
+            return events;
+        }
+    }
+    match device.active_app().await {
+        Ok(active) => {
+            if state.app.as_ref() != Some(&active.app) {
+                events.push(Ok(Event::AppChanged {
+                    previous: state.app.replace(active.app.clone()),
+                    current: active.app,
+                }));
+            }
+        }
+        Err(err) => events.push(Err(err)),
+    }
+    match device.media_player().await {
+        Ok(player) => {
+            if state.playback.as_ref() != Some(&player.state) {
+                events.push(Ok(Event::PlaybackChanged {
+                    previous: state.playback.replace(player.state.clone()),
+                    current: player.state,
+                    position: player.position,
+                }));
+            }
+        }
+        Err(err) => events.push(Err(err)),
+    }
+    events
+}
@@ -0,0 +1,247 @@
+use crate::{Device, DeviceSnapshot, Error, FieldChange, Result};
+use futures_util::stream::{self, Stream, StreamExt};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::{mpsc, Mutex},
+    task::JoinHandle,
+    time::{interval_at, sleep_until, timeout, Instant, MissedTickBehavior},
+};
+
+const CAPACITY: usize = 64;
+const MIN_INTERVAL: Duration = Duration::from_millis(1);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FanOut {
+    concurrency: usize,
+    timeout: Option<Duration>,
+}
+
+impl Default for FanOut {
+    fn default() -> FanOut {
+        FanOut {
+            concurrency: 8,
+            timeout: None,
+        }
+    }
+}
+
+impl FanOut {
+    pub fn new() -> FanOut {
+        FanOut::default()
+    }
+
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub async fn run<T, F, Fut>(&self, devices: &[Device], f: F) -> FanOutResult<T>
+    where
+        F: Fn(Device) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let limit = self.timeout;
+        let results: Vec<_> = stream::iter(devices.iter().cloned())
+            .map(|device| {
+                let future = f(device.clone());
+                async move {
+                    let result = match limit {
+                        Some(limit) => timeout(limit, future).await.unwrap_or_else(|_| {
+                            Err(Error::Response(format!(
+                                "timed out after {}ms",
+                                limit.as_millis()
+                            )))
+                        }),
+                        None => future.await,
+                    };
+                    (device, result)
+                }
+            })
+            .buffered(self.concurrency)
+            .collect()
+            .await;
+        let mut fan_out = FanOutResult {
+            successes: vec![],
+            failures: vec![],
+        };
+        for (device, result) in results {
+            match result {
+                Ok(value) => fan_out.successes.push((device, value)),
+                Err(err) => fan_out.failures.push((device, err)),
+            }
+        }
+        fan_out
+    }
+}
+
+#[derive(Debug)]
+pub struct FanOutResult<T> {
+    pub successes: Vec<(Device, T)>,
+    pub failures: Vec<(Device, Error)>,
+}
+
+impl<T> FanOutResult<T> {
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.successes.len() + self.failures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+pub async fn fan_out<T, F, Fut>(devices: &[Device], f: F) -> FanOutResult<T>
+where
+    F: Fn(Device) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    FanOut::new().run(devices, f).await
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FleetMonitor {
+    interval: Duration,
+    max_per_second: u32,
+    stagger: bool,
+}
+
+impl Default for FleetMonitor {
+    fn default() -> FleetMonitor {
+        FleetMonitor {
+            interval: Duration::from_secs(5),
+            max_per_second: 10,
+            stagger: true,
+        }
+    }
+}
+
+impl FleetMonitor {
+    pub fn new() -> FleetMonitor {
+        FleetMonitor::default()
+    }
+
+    pub fn interval(&mut self, interval: Duration) -> &mut Self {
+        self.interval = interval.max(MIN_INTERVAL);
+        self
+    }
+
+    pub fn max_per_second(&mut self, max_per_second: u32) -> &mut Self {
+        self.max_per_second = max_per_second.max(1);
+        self
+    }
+
+    pub fn stagger(&mut self, stagger: bool) -> &mut Self {
+        self.stagger = stagger;
+        self
+    }
+
+    pub fn watch(&self, devices: &[Device]) -> FleetUpdates {
+        let (tx, rx) = mpsc::channel(CAPACITY);
+        let limiter = Arc::new(RateLimiter {
+            gap: Duration::from_secs(1) / self.max_per_second,
+            next: Mutex::new(Instant::now()),
+        });
+        let start = Instant::now();
+        let count = devices.len().max(1) as u32;
+        let tasks = devices
+            .iter()
+            .enumerate()
+            .map(|(index, device)| {
+                let offset = if self.stagger {
+                    self.interval * index as u32 / count
+                } else {
+                    Duration::ZERO
+                };
+                let mut ticks = interval_at(start + offset, self.interval);
+                ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                let device = device.clone();
+                let limiter = limiter.clone();
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    let mut previous: Option<DeviceSnapshot> = None;
+                    loop {
+                        ticks.tick().await;
+                        limiter.acquire().await;
+                        let snapshot = device.snapshot().await;
+                        let changes = match (&previous, &snapshot) {
+                            (Some(previous), Ok(snapshot)) => previous.diff(snapshot),
+                            _ => vec![],
+                        };
+                        if let Ok(snapshot) = &snapshot {
+                            previous = Some(snapshot.clone());
+                        }
+                        let update = FleetUpdate {
+                            device: device.clone(),
+                            snapshot,
+                            changes,
+                        };
+                        if tx.send(update).await.is_err() {
+                            return;
+                        }
+                    }
+                })
+            })
+            .collect();
+        FleetUpdates { rx, tasks }
+    }
+}
+
+#[derive(Debug)]
+pub struct FleetUpdate {
+    pub device: Device,
+    pub snapshot: Result<DeviceSnapshot>,
+    pub changes: Vec<FieldChange>,
+}
+
+pub struct FleetUpdates {
+    rx: mpsc::Receiver<FleetUpdate>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl Stream for FleetUpdates {
+    type Item = FleetUpdate;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<FleetUpdate>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for FleetUpdates {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+struct RateLimiter {
+    gap: Duration,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    async fn acquire(&self) {
+        let at = {
+            let mut next = self.next.lock().await;
+            let at = (*next).max(Instant::now());
+            *next = at + self.gap;
+            at
+        };
+        sleep_until(at).await;
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{Device, Error, Result};
+use std::{fmt, str::FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sensor {
+    Acceleration,
+    Orientation,
+    Rotation,
+    Magnetic,
+}
+
+impl fmt::Display for Sensor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Sensor::Acceleration => "acceleration",
+                Sensor::Orientation => "orientation",
+                Sensor::Rotation => "rotation",
+                Sensor::Magnetic => "magnetic",
+            }
+        )
+    }
+}
+
+impl FromStr for Sensor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Sensor> {
+        Ok(match s.to_lowercase().as_str() {
+            "acceleration" => Sensor::Acceleration,
+            "orientation" => Sensor::Orientation,
+            "rotation" => Sensor::Rotation,
+            "magnetic" => Sensor::Magnetic,
+            _ => return Err(Error::Argument(format!("invalid sensor {}", s))),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TouchOp {
+    Down,
+    Up,
+    Press,
+    Move,
+    Cancel,
+}
+
+impl fmt::Display for TouchOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                TouchOp::Down => "down",
+                TouchOp::Up => "up",
+                TouchOp::Press => "press",
+                TouchOp::Move => "move",
+                TouchOp::Cancel => "cancel",
+            }
+        )
+    }
+}
+
+impl FromStr for TouchOp {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<TouchOp> {
+        Ok(match s.to_lowercase().as_str() {
+            "down" => TouchOp::Down,
+            "up" => TouchOp::Up,
+            "press" => TouchOp::Press,
+            "move" => TouchOp::Move,
+            "cancel" => TouchOp::Cancel,
+            _ => return Err(Error::Argument(format!("invalid touch op {}", s))),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GameInput {
+    sensors: Vec<(Sensor, [f64; 3])>,
+    touches: Vec<(f64, f64, TouchOp)>,
+}
+
+impl GameInput {
+    pub fn new() -> GameInput {
+        GameInput::default()
+    }
+
+    pub fn sensor(&mut self, sensor: Sensor, x: f64, y: f64, z: f64) -> &mut Self {
+        self.sensors.retain(|(existing, _)| *existing != sensor);
+        self.sensors.push((sensor, [x, y, z]));
+        self
+    }
+
+    pub fn acceleration(&mut self, x: f64, y: f64, z: f64) -> &mut Self {
+        self.sensor(Sensor::Acceleration, x, y, z)
+    }
+
+    pub fn orientation(&mut self, x: f64, y: f64, z: f64) -> &mut Self {
+        self.sensor(Sensor::Orientation, x, y, z)
+    }
+
+    pub fn rotation(&mut self, x: f64, y: f64, z: f64) -> &mut Self {
+        self.sensor(Sensor::Rotation, x, y, z)
+    }
+
+    pub fn magnetic(&mut self, x: f64, y: f64, z: f64) -> &mut Self {
+        self.sensor(Sensor::Magnetic, x, y, z)
+    }
+
+    pub fn touch(&mut self, x: f64, y: f64, op: TouchOp) -> &mut Self {
+        self.touches.push((x, y, op));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sensors.is_empty() && self.touches.is_empty()
+    }
+
+    pub fn into_query_pairs(self) -> Vec<(String, String)> {
+        let mut ret = vec![];
+        for (sensor, values) in self.sensors {
+            for (axis, value) in ["x", "y", "z"].iter().zip(values.iter()) {
+                ret.push((format!("{}.{}", sensor, axis), number(*value)));
+            }
+        }
+        for (index, (x, y, op)) in self.touches.into_iter().enumerate() {
+            ret.push((format!("touch.{}.x", index), number(x)));
+            ret.push((format!("touch.{}.y", index), number(y)));
+            ret.push((format!("touch.{}.op", index), op.to_string()));
+        }
+        ret
+    }
+}
+
+impl Device {
+    pub async fn game_input(&self, input: GameInput) -> Result<()> {
+        if input.is_empty() {
+            return Err(Error::Argument("no game input events".to_string()));
+        }
+        self.input(&input.into_query_pairs()).await
+    }
+}
+
+fn number(value: f64) -> String {
+    format!("{:?}", value)
+}
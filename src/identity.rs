@@ -0,0 +1,59 @@
+use crate::DeviceInfo;
+use serde::Serialize;
+use std::hash::{Hash, Hasher};
+
+const USN_PREFIX: &str = "uuid:roku:ecp:";
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DeviceIdentity {
+    serial_number: String,
+    udn: Option<String>,
+}
+
+impl DeviceIdentity {
+    pub fn new(serial_number: &str) -> DeviceIdentity {
+        DeviceIdentity {
+            serial_number: serial_number.trim().to_uppercase(),
+            udn: None,
+        }
+    }
+
+    pub fn from_usn(usn: &str) -> Option<DeviceIdentity> {
+        let serial_number = usn.strip_prefix(USN_PREFIX)?.split("::").next()?;
+        if serial_number.is_empty() {
+            return None;
+        }
+        Some(DeviceIdentity::new(serial_number))
+    }
+
+    pub fn serial_number(&self) -> &str {
+        &self.serial_number
+    }
+
+    pub fn udn(&self) -> Option<&str> {
+        self.udn.as_deref()
+    }
+}
+
+impl From<&DeviceInfo> for DeviceIdentity {
+    fn from(info: &DeviceInfo) -> DeviceIdentity {
+        DeviceIdentity {
+            udn: Some(info.udn.clone()).filter(|udn| !udn.is_empty()),
+            ..DeviceIdentity::new(&info.serial_number)
+        }
+    }
+}
+
+impl PartialEq for DeviceIdentity {
+    fn eq(&self, other: &DeviceIdentity) -> bool {
+        self.serial_number == other.serial_number
+    }
+}
+
+impl Eq for DeviceIdentity {}
+
+impl Hash for DeviceIdentity {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.serial_number.hash(state);
+    }
+}
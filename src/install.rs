@@ -0,0 +1,222 @@
+use crate::{consts, App, Device, Endpoint, Error, Key, Result, RokuControl};
+use serde::Serialize;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "status", rename_all = "kebab-case")]
+pub enum InstallStatus {
+    Skipped,
+    Installing,
+    Confirming,
+    Installed,
+    Failed { error: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InstallProgress {
+    pub app_id: String,
+    pub index: usize,
+    pub total: usize,
+    #[serde(flatten)]
+    pub status: InstallStatus,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InstallFailure {
+    pub app_id: String,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct InstallReport {
+    pub installed: Vec<String>,
+    pub skipped: Vec<String>,
+    pub failed: Vec<InstallFailure>,
+}
+
+impl InstallReport {
+    pub fn is_ok(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstallQueue {
+    confirm: bool,
+    interval: Duration,
+    timeout: Duration,
+}
+
+impl Default for InstallQueue {
+    fn default() -> InstallQueue {
+        InstallQueue {
+            confirm: true,
+            interval: Duration::from_secs(1),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl InstallQueue {
+    pub fn new() -> InstallQueue {
+        InstallQueue::default()
+    }
+
+    pub fn confirm(&mut self, confirm: bool) -> &mut Self {
+        self.confirm = confirm;
+        self
+    }
+
+    pub fn interval(&mut self, interval: Duration) -> &mut Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub async fn run<S: AsRef<str>, F: FnMut(&InstallProgress)>(
+        &self,
+        device: &(impl RokuControl + ?Sized),
+        app_ids: &[S],
+        mut on_progress: F,
+    ) -> Result<InstallReport> {
+        let mut installed = installed_ids(device).await?;
+        let mut report = InstallReport::default();
+        let total = app_ids.len();
+        for (index, app_id) in app_ids.iter().enumerate() {
+            let app_id = app_id.as_ref().to_string();
+            let mut progress = |status| {
+                on_progress(&InstallProgress {
+                    app_id: app_id.clone(),
+                    index,
+                    total,
+                    status,
+                })
+            };
+            if installed.contains(&app_id) {
+                progress(InstallStatus::Skipped);
+                report.skipped.push(app_id.clone());
+                continue;
+            }
+            progress(InstallStatus::Installing);
+            match self.install(device, &app_id, &mut progress).await {
+                Ok(_) => {
+                    progress(InstallStatus::Installed);
+                    installed.push(app_id.clone());
+                    report.installed.push(app_id);
+                }
+                Err(err) => {
+                    let error = err.to_string();
+                    progress(InstallStatus::Failed {
+                        error: error.clone(),
+                    });
+                    report.failed.push(InstallFailure { app_id, error });
+                }
+            }
+        }
+        if self.confirm && report.skipped.len() < total {
+            device.keypress(&Key::Home).await?;
+        }
+        Ok(report)
+    }
+
+    pub async fn install_one(
+        &self,
+        device: &(impl RokuControl + ?Sized),
+        app_id: &str,
+    ) -> Result<App> {
+        if let Some(app) = installed_app(device, app_id).await? {
+            return Ok(app);
+        }
+        let app = self.install(device, app_id, &mut |_| {}).await?;
+        if self.confirm {
+            device.keypress(&Key::Home).await?;
+        }
+        Ok(app)
+    }
+
+    async fn install<F: FnMut(InstallStatus)>(
+        &self,
+        device: &(impl RokuControl + ?Sized),
+        app_id: &str,
+        progress: &mut F,
+    ) -> Result<App> {
+        device
+            .install(&App {
+                id: Some(app_id.to_string()),
+                name: String::new(),
+                version: None,
+            })
+            .await?;
+        let deadline = Instant::now() + self.timeout;
+        if self.confirm {
+            progress(InstallStatus::Confirming);
+            while device.active_app().await?.app.id.as_deref() != Some(consts::APP_CHANNEL_STORE) {
+                if Instant::now() >= deadline {
+                    return Err(timed_out("channel store did not open", self.timeout));
+                }
+                sleep(self.interval).await;
+            }
+            device.keypress(&Key::Select).await?;
+        }
+        loop {
+            if let Some(app) = installed_app(device, app_id).await? {
+                return Ok(app);
+            }
+            if Instant::now() >= deadline {
+                return Err(timed_out("channel was not installed", self.timeout));
+            }
+            sleep(self.interval).await;
+        }
+    }
+}
+
+impl Device {
+    pub async fn install_all<S: AsRef<str>, F: FnMut(&InstallProgress)>(
+        &self,
+        app_ids: &[S],
+        on_progress: F,
+    ) -> Result<InstallReport> {
+        InstallQueue::new().run(self, app_ids, on_progress).await
+    }
+
+    pub async fn install_and_wait(&self, app: &App, confirm: bool) -> Result<App> {
+        let app_id = app
+            .id
+            .as_ref()
+            .ok_or_else(|| Error::Argument("app.id required".to_string()))?;
+        InstallQueue::new()
+            .confirm(confirm)
+            .install_one(self, app_id)
+            .await
+    }
+}
+
+async fn installed_ids(device: &(impl RokuControl + ?Sized)) -> Result<Vec<String>> {
+    device.invalidate(Endpoint::Apps);
+    Ok(device
+        .apps()
+        .await?
+        .apps
+        .into_iter()
+        .filter_map(|app| app.id)
+        .collect())
+}
+
+async fn installed_app(device: &(impl RokuControl + ?Sized), app_id: &str) -> Result<Option<App>> {
+    device.invalidate(Endpoint::Apps);
+    Ok(device
+        .apps()
+        .await?
+        .apps
+        .into_iter()
+        .find(|app| app.id.as_deref() == Some(app_id)))
+}
+
+fn timed_out(message: &str, timeout: Duration) -> Error {
+    Error::Response(format!("{} within {}s", message, timeout.as_secs()))
+}
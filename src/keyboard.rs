@@ -0,0 +1,136 @@
+use crate::{Error, Key, Result, RokuControl};
+use std::{iter::repeat_n, time::Duration};
+use tokio::time::sleep;
+
+pub const SHIFT: char = '⇧';
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridKeyboard {
+    rows: Vec<Vec<char>>,
+    start: (usize, usize),
+    wrap: bool,
+    interval: Duration,
+}
+
+impl GridKeyboard {
+    pub fn new<S: AsRef<str>>(rows: &[S]) -> GridKeyboard {
+        GridKeyboard {
+            rows: rows
+                .iter()
+                .map(|row| row.as_ref().chars().collect())
+                .filter(|row: &Vec<char>| !row.is_empty())
+                .collect(),
+            start: (0, 0),
+            wrap: false,
+            interval: Duration::from_millis(150),
+        }
+    }
+
+    pub fn start(&mut self, row: usize, column: usize) -> &mut Self {
+        self.start = (row, column);
+        self
+    }
+
+    pub fn wrap(&mut self, wrap: bool) -> &mut Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn interval(&mut self, interval: Duration) -> &mut Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn keys(&self, text: &str) -> Result<Vec<Key>> {
+        let mut keys = vec![];
+        let mut cursor = self.start;
+        let mut shifted = false;
+        for c in text.chars() {
+            let (target, shift) = self.locate(c)?;
+            if let Some(shift) = shift.filter(|shift| *shift != shifted) {
+                let toggle = self
+                    .find(SHIFT)
+                    .ok_or_else(|| Error::Argument(format!("no shift key to type {}", c)))?;
+                self.moves(&mut cursor, toggle, &mut keys);
+                keys.push(Key::Select);
+                shifted = shift;
+            }
+            self.moves(&mut cursor, target, &mut keys);
+            keys.push(Key::Select);
+        }
+        Ok(keys)
+    }
+
+    pub async fn type_text(&self, device: &(impl RokuControl + ?Sized), text: &str) -> Result<()> {
+        for (i, key) in self.keys(text)?.iter().enumerate() {
+            if i > 0 {
+                sleep(self.interval).await;
+            }
+            device.keypress(key).await?;
+        }
+        Ok(())
+    }
+
+    fn locate(&self, c: char) -> Result<((usize, usize), Option<bool>)> {
+        if let Some(position) = self.find(c) {
+            let cased = c.is_uppercase() || c.is_lowercase();
+            return Ok((position, if cased { Some(false) } else { None }));
+        }
+        let lower = c.to_lowercase().next().filter(|lower| *lower != c);
+        let upper = c.to_uppercase().next().filter(|upper| *upper != c);
+        match (
+            lower.and_then(|lower| self.find(lower)),
+            upper.and_then(|upper| self.find(upper)),
+        ) {
+            (Some(position), _) => Ok((position, Some(true))),
+            (_, Some(position)) => Ok((position, Some(true))),
+            _ => Err(Error::Argument(format!("{:?} is not on the keyboard", c))),
+        }
+    }
+
+    fn find(&self, c: char) -> Option<(usize, usize)> {
+        self.rows.iter().enumerate().find_map(|(row, keys)| {
+            keys.iter()
+                .position(|key| *key == c)
+                .map(|column| (row, column))
+        })
+    }
+
+    fn moves(&self, cursor: &mut (usize, usize), target: (usize, usize), keys: &mut Vec<Key>) {
+        let (vertical, count) = steps(
+            cursor.0,
+            target.0,
+            self.rows.len(),
+            self.wrap,
+            Key::Up,
+            Key::Down,
+        );
+        keys.extend(repeat_n(vertical, count));
+        let width = self.rows[target.0].len();
+        let column = cursor.1.min(width - 1);
+        let (horizontal, count) = steps(column, target.1, width, self.wrap, Key::Left, Key::Right);
+        keys.extend(repeat_n(horizontal, count));
+        *cursor = target;
+    }
+}
+
+fn steps(from: usize, to: usize, len: usize, wrap: bool, back: Key, forward: Key) -> (Key, usize) {
+    let direct = if to >= from {
+        (forward, to - from)
+    } else {
+        (back, from - to)
+    };
+    if !wrap {
+        return direct;
+    }
+    let around = if to >= from {
+        (back, len - (to - from))
+    } else {
+        (forward, len - (from - to))
+    };
+    if around.1 < direct.1 {
+        around
+    } else {
+        direct
+    }
+}
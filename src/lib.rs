@@ -1,172 +1,769 @@
-use futures_util::stream::StreamExt;
-use reqwest::Client;
-use serde::Deserialize;
+use cache::ResponseCache;
+use ecp::check;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+use reqwest::{Client, Response};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_xml_rs::from_str;
-use ssdp_client::{search, SearchTarget};
-use std::{fmt, time::Duration};
+use std::{
+    fmt,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 use thiserror::Error;
+use tokio::time::{sleep, sleep_until, Instant};
+pub use tokio_util::sync::CancellationToken;
 use url::Url;
 
+pub use accessibility::Shortcut;
+pub use beacons::BeaconEvent;
+pub use cache::{Cache, CachePolicy, Endpoint, MemoryCache};
+pub use capabilities::{Capabilities, Capability};
+pub use captions::CaptionToggle;
+#[cfg(feature = "serve")]
+pub use cast::FileServer;
+pub use cast::{
+    CastSession, CastStatus, Media, MediaType, Order, Slide, Slideshow, SlideshowHandle,
+};
+pub use chanperf::{PerfSession, Sample, Stats, Summary};
+pub use class::DeviceClass;
+pub use config::{RetryPolicy, RokuConfig};
+#[cfg(feature = "dev")]
+pub use console::{Console, FreeMemory};
+#[cfg(feature = "content")]
+pub use content::ContentSearch;
+pub use control::RokuControl;
+#[cfg(feature = "dev")]
+pub use debugger::Debugger;
+#[cfg(feature = "dev")]
+pub use dev::{DevServer, SideloadResult};
+pub use discover::{Discoverer, Notification, Notifications, SsdpDiscoverer};
+pub use doctor::{doctor, doctor_with, Check, CheckStatus, Report};
+pub use ecp::{EcpError, RawResponse};
+pub use events::{Event, EventOptions, Events, Overflow};
+pub use fleet::{fan_out, FanOut, FanOutResult, FleetMonitor, FleetUpdate, FleetUpdates};
+pub use game::{GameInput, Sensor, TouchOp};
+pub use identity::DeviceIdentity;
+pub use install::{InstallFailure, InstallProgress, InstallQueue, InstallReport, InstallStatus};
+pub use keyboard::GridKeyboard;
+pub use lifecycle::{AppSession, AppState, AppTransition};
+#[cfg(feature = "dev")]
+pub use logs::DebugLogs;
+pub use navigate::{Acceleration, Direction, Hold};
+pub use now_playing::NowPlaying;
+#[cfg(feature = "profiles")]
+pub use profiles::{Profile, Profiles};
+#[cfg(feature = "schedule")]
+pub use schedule::Scheduler;
+pub use script::{parse_duration, Script, Step};
+pub use secret::SecretScreen;
+pub use sgnodes::{Node, NodeTree};
+pub use snapshot::{DeviceSnapshot, FieldChange};
+#[cfg(feature = "store")]
+pub use store::ChannelStore;
+pub use tv::{ChannelScan, LineupDiff, Program};
+pub use well_known::AppId;
+
+mod accessibility;
+mod beacons;
+mod bitmaps;
+mod cache;
+mod capabilities;
+mod captions;
+mod cast;
+mod chanperf;
+mod class;
+mod config;
+#[cfg(feature = "dev")]
+pub mod console;
+pub mod consts;
+#[cfg(feature = "content")]
+pub mod content;
+mod control;
+#[cfg(feature = "dev")]
+pub mod debugger;
+#[cfg(feature = "dev")]
+pub mod dev;
+mod discover;
+mod doctor;
+mod ecp;
+mod events;
+mod fleet;
+mod game;
+mod identity;
+mod install;
+pub mod keyboard;
+mod lifecycle;
+#[cfg(feature = "dev")]
+pub mod logs;
+mod navigate;
+mod now_playing;
+#[cfg(feature = "profiles")]
+pub mod profiles;
+mod registry;
+#[cfg(feature = "schedule")]
+pub mod schedule;
+#[cfg(feature = "dev")]
+pub mod screen;
+mod script;
+mod secret;
+mod sgnodes;
+mod snapshot;
+mod ssdp;
+#[cfg(feature = "store")]
+pub mod store;
+#[cfg(feature = "test-util")]
+pub mod testing;
+mod tv;
+pub mod well_known;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("failed to send request")]
     Request(#[from] reqwest::Error),
     #[error("failed to send SSDP request")]
-    SSDPRequest(#[from] ssdp_client::Error),
+    SSDPRequest(#[source] std::io::Error),
     #[error("failed to parse URL")]
     URLParse(#[from] url::ParseError),
     #[error("failed to parse XML")]
     XMLParse(#[from] serde_xml_rs::Error),
     #[error("argument error `{0}`")]
     Argument(String),
+    #[error("unexpected response `{0}`")]
+    Response(String),
+    #[error("failed to read file")]
+    IO(#[from] std::io::Error),
+    #[error("ECP request failed `{0}`")]
+    Ecp(EcpError),
+    #[error("not supported `{0}`")]
+    NotSupported(String),
+    #[error("not found `{0}`")]
+    NotFound(String),
+    #[error("failed after {completed} keypresses")]
+    Incomplete {
+        completed: u32,
+        #[source]
+        source: Box<Error>,
+    },
+    #[cfg(feature = "dev")]
+    #[error("failed to authenticate")]
+    Auth(#[from] digest_auth::Error),
 }
 
 type Result<T> = std::result::Result<T, Error>;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Device {
     url: Url,
     client: Client,
+    volume: Option<Arc<Mutex<VolumeState>>>,
+    identity: Option<Arc<DeviceIdentity>>,
+    cache: Option<ResponseCache>,
+    capabilities: Arc<Mutex<Option<Capabilities>>>,
+    class: Arc<Mutex<Option<DeviceClass>>>,
+    config: Arc<RokuConfig>,
+    paced: Arc<Mutex<Option<Instant>>>,
+    held: Arc<Mutex<Vec<Key>>>,
 }
 
 impl Device {
     pub fn new(url: Url) -> Device {
-        Device {
+        Device::with_config(url, &RokuConfig::global())
+            .expect("set_global only accepts configs that build a client")
+    }
+
+    pub fn with_config(mut url: Url, config: &RokuConfig) -> Result<Device> {
+        if !url.path().ends_with('/') {
+            let path = format!("{}/", url.path());
+            url.set_path(&path);
+        }
+        Ok(Device {
             url,
-            client: Client::new(),
+            client: config.client(None)?,
+            volume: None,
+            identity: None,
+            cache: None,
+            capabilities: Arc::new(Mutex::new(None)),
+            class: Arc::new(Mutex::new(None)),
+            config: Arc::new(config.clone()),
+            paced: Arc::new(Mutex::new(None)),
+            held: Arc::new(Mutex::new(vec![])),
+        })
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn with_port(mut self, port: u16) -> Result<Device> {
+        self.url
+            .set_port(Some(port))
+            .map_err(|_| Error::Argument(format!("{} cannot have a port", self.url)))?;
+        Ok(self)
+    }
+
+    pub fn with_base_path(mut self, path: &str) -> Device {
+        let path = path.trim_matches('/');
+        if path.is_empty() {
+            self.url.set_path("/");
+        } else {
+            self.url.set_path(&format!("/{}/", path));
         }
+        self
     }
 
-    pub async fn discover() -> Result<Vec<Device>> {
-        let search_target = SearchTarget::Custom("roku".to_string(), "ecp".to_string());
-        let mut responses = search(&search_target, Duration::from_secs(3), 2).await?;
-        let mut devices = vec![];
-        while let Some(response) = responses.next().await {
-            let response = response?;
-            if response.search_target() != &search_target {
-                continue;
-            }
-            let url = Url::parse(response.location())?;
-            devices.push(Device {
-                url,
-                client: Client::new(),
-            });
+    pub fn with_identity(mut self, identity: DeviceIdentity) -> Device {
+        self.identity = Some(Arc::new(identity));
+        self
+    }
+
+    pub async fn identity(&self) -> Result<DeviceIdentity> {
+        if let Some(identity) = &self.identity {
+            return Ok(DeviceIdentity::clone(identity));
+        }
+        Ok(DeviceIdentity::from(&self.device_info().await?))
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Result<Device> {
+        self.client = self.config.client(Some(timeout))?;
+        Ok(self)
+    }
+
+    pub fn with_volume_tracking(mut self) -> Device {
+        self.volume = Some(Arc::new(Mutex::new(VolumeState::default())));
+        self
+    }
+
+    pub fn volume_state(&self) -> Option<VolumeState> {
+        self.volume.as_ref().map(|volume| *volume.lock().unwrap())
+    }
+
+    pub fn reset_volume_state(&self) {
+        if let Some(volume) = &self.volume {
+            *volume.lock().unwrap() = VolumeState::default();
         }
-        Ok(devices)
+    }
+
+    pub fn calibrate_volume(&self, level: u32, muted: bool) {
+        if let Some(volume) = &self.volume {
+            *volume.lock().unwrap() = VolumeState {
+                level: level.min(VolumeState::MAX) as i32,
+                calibrated: true,
+                muted,
+            };
+        }
+    }
+
+    pub async fn discover() -> Result<Vec<Device>> {
+        RokuConfig::global().discoverer().discover().await
+    }
+
+    #[cfg(feature = "dev")]
+    pub fn dev_server(&self, password: String) -> Result<DevServer> {
+        Ok(DevServer::new(
+            Url::parse(&format!("http://{}/", self.host()?))?,
+            password,
+        ))
+    }
+
+    #[cfg(feature = "dev")]
+    pub fn debug_logs(&self) -> Result<DebugLogs> {
+        Ok(logs::spawn(self.host()?.to_string(), logs::PORT))
+    }
+
+    #[cfg(feature = "dev")]
+    pub async fn console(&self) -> Result<Console> {
+        Console::connect(self.host()?).await
+    }
+
+    #[cfg(feature = "dev")]
+    pub async fn debugger(&self) -> Result<Debugger> {
+        Debugger::attach(self.host()?).await
+    }
+
+    #[cfg(feature = "dev")]
+    fn host(&self) -> Result<&str> {
+        self.url
+            .host_str()
+            .ok_or_else(|| Error::Argument("device URL has no host".to_string()))
+    }
+
+    pub fn events(&self, interval: Duration) -> Events {
+        EventOptions::new().interval(interval).watch(self)
     }
 
     pub async fn apps(&self) -> Result<Apps> {
-        let url = self.url.join("query/apps")?;
-        let res = self.client.get(url).send().await?;
-        let text = res.text().await?;
-        Ok(from_str(&text)?)
+        Ok(from_str(&self.query(Endpoint::Apps).await?)?)
     }
 
     pub async fn active_app(&self) -> Result<ActiveApp> {
-        let url = self.url.join("query/active-app")?;
-        let res = self.client.get(url).send().await?;
-        let text = res.text().await?;
-        Ok(from_str(&text)?)
+        self.query_as(consts::QUERY_ACTIVE_APP).await
+    }
+
+    pub async fn tv_channels(&self) -> Result<TvChannels> {
+        self.require_class(DeviceClass::Tv, "the Live TV lineup")
+            .await?;
+        let xml = self
+            .optional(Capability::TvChannels, self.query(Endpoint::TvChannels))
+            .await?;
+        Ok(from_str(&xml)?)
+    }
+
+    pub async fn tv_active_channel(&self) -> Result<TvActiveChannel> {
+        self.require_class(DeviceClass::Tv, "the active Live TV channel")
+            .await?;
+        self.optional(
+            Capability::TvActiveChannel,
+            self.query_as(consts::QUERY_TV_ACTIVE_CHANNEL),
+        )
+        .await
     }
 
     pub async fn media_player(&self) -> Result<MediaPlayer> {
-        let url = self.url.join("query/media-player")?;
-        let res = self.client.get(url).send().await?;
-        let text = res.text().await?;
-        Ok(from_str(&text)?)
+        self.query_as(consts::QUERY_MEDIA_PLAYER).await
     }
 
     pub async fn keydown(&self, key: &Key) -> Result<()> {
-        let url = self.url.join(&format!("keydown/{}", key.to_string()))?;
-        self.client.post(url).send().await?;
+        let url = self.url.join(&key_path(consts::KEYDOWN, key))?;
+        self.pace().await;
+        check(self.client.post(url).send().await?).await?;
+        let mut held = self.held.lock().unwrap();
+        if !held.contains(key) {
+            held.push(*key);
+        }
         Ok(())
     }
 
     pub async fn keyup(&self, key: &Key) -> Result<()> {
-        let url = self.url.join(&format!("keyup/{}", key.to_string()))?;
-        self.client.post(url).send().await?;
+        let url = self.url.join(&key_path(consts::KEYUP, key))?;
+        self.pace().await;
+        check(self.client.post(url).send().await?).await?;
+        self.held.lock().unwrap().retain(|held| held != key);
+        Ok(())
+    }
+
+    pub fn held_keys(&self) -> Vec<Key> {
+        self.held.lock().unwrap().clone()
+    }
+
+    pub async fn release_all(&self) -> Result<()> {
+        for key in self.held_keys() {
+            self.keyup(&key).await?;
+        }
         Ok(())
     }
 
     pub async fn keypress(&self, key: &Key) -> Result<()> {
-        let url = self.url.join(&format!("keypress/{}", key.to_string()))?;
-        self.client.post(url).send().await?;
+        let url = self.url.join(&key_path(consts::KEYPRESS, key))?;
+        self.pace().await;
+        check(self.client.post(url).send().await?).await?;
+        if let Some(volume) = &self.volume {
+            volume.lock().unwrap().apply(key);
+        }
         Ok(())
     }
 
+    pub async fn type_text(&self, text: &str, interval: Duration) -> Result<()> {
+        RokuControl::type_text(self, text, interval).await
+    }
+
+    pub async fn volume_ramp_up(
+        &self,
+        steps: u32,
+        interval: Duration,
+        cancel: &CancellationToken,
+    ) -> Result<u32> {
+        self.keypress_repeat(&Key::VolumeUp, steps, interval, cancel)
+            .await
+    }
+
+    pub async fn volume_ramp_down(
+        &self,
+        steps: u32,
+        interval: Duration,
+        cancel: &CancellationToken,
+    ) -> Result<u32> {
+        self.keypress_repeat(&Key::VolumeDown, steps, interval, cancel)
+            .await
+    }
+
+    pub async fn keypress_repeat(
+        &self,
+        key: &Key,
+        count: u32,
+        interval: Duration,
+        cancel: &CancellationToken,
+    ) -> Result<u32> {
+        let mut sent = 0;
+        while sent < count {
+            if cancel.is_cancelled() {
+                break;
+            }
+            self.keypress(key).await.map_err(|err| Error::Incomplete {
+                completed: sent,
+                source: Box::new(err),
+            })?;
+            sent += 1;
+            if sent < count {
+                tokio::select! {
+                    _ = sleep(interval) => {}
+                    _ = cancel.cancelled() => break,
+                }
+            }
+        }
+        Ok(sent)
+    }
+
+    pub async fn query_as<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = self.url.join(path.trim_start_matches('/'))?;
+        let text = self.get(url).await?.text().await?;
+        Ok(from_str(&text)?)
+    }
+
+    pub async fn raw_get(&self, path: &str) -> Result<RawResponse> {
+        let url = self.url.join(path.trim_start_matches('/'))?;
+        RawResponse::read(self.client.get(url).send().await?).await
+    }
+
+    pub async fn raw_post(&self, path: &str, query: &[(&str, &str)]) -> Result<RawResponse> {
+        let url = self.url.join(path.trim_start_matches('/'))?;
+        RawResponse::read(self.client.post(url).query(query).send().await?).await
+    }
+
+    pub(crate) async fn get(&self, url: Url) -> Result<Response> {
+        let retry = self.config.retry_policy();
+        let mut attempt = 1;
+        loop {
+            let result = match self.client.get(url.clone()).send().await {
+                Ok(res) => check(res).await,
+                Err(err) => Err(err.into()),
+            };
+            match result {
+                Err(err) if retry.should_retry(attempt, &err) => {
+                    sleep(retry.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn pace(&self) {
+        let pacing = self.config.pacing_interval();
+        if pacing.is_zero() {
+            return;
+        }
+        let at = {
+            let mut paced = self.paced.lock().unwrap();
+            let at = paced.map_or_else(Instant::now, |last| last + pacing);
+            *paced = Some(at.max(Instant::now()));
+            at
+        };
+        sleep_until(at).await;
+    }
+
     pub async fn launch(&self, app: &App) -> Result<()> {
         let app_id = app
             .id
             .as_ref()
             .ok_or_else(|| Error::Argument("app.id required".to_string()))?;
-        let url = self.url.join(&format!("launch/{}", app_id))?;
-        self.client.post(url).send().await?;
+        let url = self.url.join(&format!("{}/{}", consts::LAUNCH, app_id))?;
+        check(self.client.post(url).send().await?).await?;
+        Ok(())
+    }
+
+    pub async fn exit_app(&self, app_id: &str) -> Result<()> {
+        let path = format!("{}/{}", consts::EXIT_APP, app_id);
+        self.optional(Capability::ExitApp, self.post(&path)).await
+    }
+
+    pub async fn launch_by_name(&self, name: &str) -> Result<App> {
+        let app = self
+            .apps()
+            .await?
+            .apps
+            .into_iter()
+            .find(|app| app.name.eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| Error::NotFound(format!("no channel named {}", name)))?;
+        self.launch(&app).await?;
+        Ok(app)
+    }
+
+    pub async fn launch_with(&self, app: &App, params: LaunchParams) -> Result<()> {
+        let app_id = app
+            .id
+            .as_ref()
+            .ok_or_else(|| Error::Argument("app.id required".to_string()))?;
+        let url = self.url.join(&format!("{}/{}", consts::LAUNCH, app_id))?;
+        let query = params.into_query_pairs();
+        check(self.client.post(url).query(&query).send().await?).await?;
         Ok(())
     }
 
+    pub async fn launch_dev(&self, params: LaunchParams) -> Result<()> {
+        let app = App {
+            id: Some(consts::APP_DEV.to_string()),
+            name: String::new(),
+            version: None,
+        };
+        self.launch_with(&app, params).await
+    }
+
+    pub async fn deep_link(&self, link: &DeepLink) -> Result<()> {
+        match &link.app_id {
+            Some(app_id) => {
+                let app = App {
+                    id: Some(app_id.clone()),
+                    name: String::new(),
+                    version: None,
+                };
+                self.launch_with(&app, link.clone().into()).await
+            }
+            None => self.input(&link.clone().into_query_pairs()).await,
+        }
+    }
+
     pub async fn install(&self, app: &App) -> Result<()> {
         let app_id = app
             .id
             .as_ref()
             .ok_or_else(|| Error::Argument("app.id required".to_string()))?;
-        let url = self.url.join(&format!("install/{}", app_id))?;
-        self.client.post(url).send().await?;
+        let url = self.url.join(&format!("{}/{}", consts::INSTALL, app_id))?;
+        check(self.client.post(url).send().await?).await?;
+        self.invalidate(Endpoint::Apps);
         Ok(())
     }
 
+    pub async fn app_icon(&self, app: &App) -> Result<Image> {
+        let app_id = app
+            .id
+            .as_ref()
+            .ok_or_else(|| Error::Argument("app.id required".to_string()))?;
+        self.icon(app_id).await
+    }
+
+    pub async fn icon(&self, app_id: &str) -> Result<Image> {
+        self.query_icon(app_id).await
+    }
+
     pub async fn device_info(&self) -> Result<DeviceInfo> {
-        let url = self.url.join("query/device-info")?;
-        let res = self.client.get(url).send().await?;
-        let text = res.text().await?;
-        Ok(from_str(&text)?)
+        Ok(from_str(&self.query(Endpoint::DeviceInfo).await?)?)
+    }
+
+    pub async fn chanperf(&self) -> Result<ChanPerf> {
+        self.optional(Capability::ChanPerf, self.query_as(consts::QUERY_CHANPERF))
+            .await
+    }
+
+    pub async fn app_chanperf(&self, app_id: &str) -> Result<ChanPerf> {
+        let path = format!("{}/{}", consts::QUERY_CHANPERF, app_id);
+        self.optional(Capability::ChanPerf, self.query_as(&path))
+            .await
+    }
+
+    pub async fn fwbeacons(&self) -> Result<FwBeacons> {
+        self.optional(
+            Capability::FwBeacons,
+            self.query_as(consts::QUERY_FWBEACONS),
+        )
+        .await
+    }
+
+    pub async fn track_fwbeacons(&self) -> Result<()> {
+        self.optional(Capability::FwBeacons, self.post(consts::FWBEACONS_TRACK))
+            .await
+    }
+
+    pub async fn untrack_fwbeacons(&self) -> Result<()> {
+        self.optional(Capability::FwBeacons, self.post(consts::FWBEACONS_UNTRACK))
+            .await
+    }
+
+    async fn post(&self, path: &str) -> Result<()> {
+        let url = self.url.join(path)?;
+        check(self.client.post(url).send().await?).await?;
+        Ok(())
+    }
+
+    pub async fn r2d2_bitmaps(&self) -> Result<R2d2Bitmaps> {
+        self.query_as(consts::QUERY_R2D2_BITMAPS).await
+    }
+
+    pub async fn registry(&self) -> Result<PluginRegistry> {
+        self.app_registry(consts::APP_DEV).await
+    }
+
+    pub async fn app_registry(&self, app_id: &str) -> Result<PluginRegistry> {
+        self.query_as(&format!("{}/{}", consts::QUERY_REGISTRY, app_id))
+            .await
+    }
+
+    pub async fn sgrendezvous(&self) -> Result<SgRendezvous> {
+        self.query_as(consts::QUERY_SGRENDEZVOUS).await
+    }
+
+    pub async fn track_sgrendezvous(&self) -> Result<()> {
+        let url = self.url.join(consts::SGRENDEZVOUS_TRACK)?;
+        check(self.client.post(url).send().await?).await?;
+        Ok(())
+    }
+
+    pub async fn untrack_sgrendezvous(&self) -> Result<()> {
+        let url = self.url.join(consts::SGRENDEZVOUS_UNTRACK)?;
+        check(self.client.post(url).send().await?).await?;
+        Ok(())
     }
 
     pub async fn input(&self, input: &[(String, String)]) -> Result<()> {
-        let url = self.url.join("input")?;
-        self.client.post(url).query(input).send().await?;
+        let url = self.url.join(consts::INPUT)?;
+        check(self.client.post(url).query(input).send().await?).await?;
         Ok(())
     }
 
-    pub async fn search(&self, search: Search) -> Result<()> {
-        let search = search.build();
-        let url = self.url.join("search")?;
-        self.client.post(url).query(&search).send().await?;
+    pub async fn cast(&self, media: &Media) -> Result<CastSession> {
+        let url = self
+            .url
+            .join(&format!("{}/{}", consts::INPUT, consts::APP_PLAY_ON_ROKU))?;
+        check(self.client.post(url).query(&media.build()).send().await?).await?;
+        Ok(CastSession::new(self, media))
+    }
+
+    pub async fn cast_media_player(&self, media: &Media) -> Result<CastSession> {
+        let url = self
+            .url
+            .join(&format!("{}/{}", consts::LAUNCH, consts::APP_MEDIA_PLAYER))?;
+        check(self.client.post(url).query(&media.build()).send().await?).await?;
+        Ok(CastSession::new(self, media))
+    }
+
+    #[cfg(feature = "serve")]
+    pub async fn cast_file<P: AsRef<std::path::Path>>(&self, path: P) -> Result<CastSession> {
+        let path = path.as_ref();
+        let server = FileServer::start(path, &self.url, 0).await?;
+        let mut media = Media::from_url(server.url().clone())?;
+        if let Some(stem) = path.file_stem() {
+            media.title(stem.to_string_lossy().into_owned());
+        }
+        Ok(self.cast(&media).await?.serving(server))
+    }
+
+    pub async fn search(&self, mut search: Search) -> Result<()> {
+        if search.resolve_providers || search.installed_only {
+            search.resolve(&self.apps().await?.apps)?;
+        }
+        let search = search.into_query_pairs();
+        let url = self.url.join(consts::SEARCH)?;
+        check(self.client.post(url).query(&search).send().await?).await?;
         Ok(())
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub content_type: Option<String>,
+    pub data: Vec<u8>,
+}
+
+impl Image {
+    pub fn extension(&self) -> Option<&'static str> {
+        match self.content_type.as_deref()? {
+            "image/png" => Some("png"),
+            "image/jpeg" | "image/jpg" => Some("jpg"),
+            "image/gif" => Some("gif"),
+            "image/webp" => Some("webp"),
+            _ => None,
+        }
+    }
+
+    async fn from_response(res: reqwest::Response) -> Result<Image> {
+        let content_type = res
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .map(|content_type| content_type.to_string());
+        let data = res.bytes().await?.to_vec();
+        Ok(Image { content_type, data })
+    }
+}
+
+pub(crate) fn millis(value: Option<&str>) -> Option<Duration> {
+    let ms = value?.trim_end_matches("ms").trim().parse().ok()?;
+    Some(Duration::from_millis(ms))
+}
+
+fn key_path(action: &str, key: &Key) -> String {
+    match key {
+        Key::Lit(c) => format!(
+            "{}/Lit_{}",
+            action,
+            utf8_percent_encode(c.encode_utf8(&mut [0; 4]), NON_ALPHANUMERIC)
+        ),
+        Key::ButtonA => format!("{}/A", action),
+        Key::ButtonB => format!("{}/B", action),
+        key => format!("{}/{}", action, key),
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VolumeState {
+    pub level: i32,
+    pub calibrated: bool,
+    pub muted: bool,
+}
+
+impl VolumeState {
+    pub const MAX: u32 = 100;
+
+    fn apply(&mut self, key: &Key) {
+        let delta = match key {
+            Key::VolumeUp => 1,
+            Key::VolumeDown => -1,
+            Key::VolumeMute => {
+                self.muted = !self.muted;
+                return;
+            }
+            _ => return,
+        };
+        self.level += delta;
+        if self.calibrated {
+            self.level = self.level.clamp(0, VolumeState::MAX as i32);
+        }
+        self.muted = false;
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Apps {
-    #[serde(rename = "app")]
+    #[serde(default, rename(deserialize = "app"))]
     pub apps: Vec<App>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ActiveApp {
     pub app: App,
     pub screensaver: Option<Screensaver>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct App {
     pub id: Option<String>,
-    #[serde(rename = "$value")]
+    #[serde(rename(deserialize = "$value"))]
     pub name: String,
     pub version: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Screensaver {
     pub black: Option<bool>,
     pub id: String,
-    #[serde(rename = "$value")]
+    #[serde(rename(deserialize = "$value"))]
     pub name: String,
-    #[serde(rename = "type")]
+    #[serde(rename(deserialize = "type"))]
     pub screensaver_type: String,
     pub version: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct MediaPlayer {
     pub buffering: Option<Buffering>,
     pub duration: Option<String>,
@@ -181,14 +778,14 @@ pub struct MediaPlayer {
     pub stream_segment: Option<StreamSegment>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Plugin {
     pub bandwidth: String,
     pub id: String,
     pub name: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Format {
     pub audio: String,
     pub captions: String,
@@ -198,19 +795,19 @@ pub struct Format {
     pub video_res: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Buffering {
     pub current: u32,
     pub max: u32,
     pub target: u32,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct NewStream {
     pub speed: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct StreamSegment {
     pub bitrate: u32,
     pub media_sequence: u32,
@@ -218,9 +815,12 @@ pub struct StreamSegment {
     pub time: u32,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Key {
     Back,
     Backspace,
+    ButtonA,
+    ButtonB,
     ChannelDown,
     ChannelUp,
     Down,
@@ -237,12 +837,16 @@ pub enum Key {
     InputTuner,
     InstantReplay,
     Left,
+    Pause,
     Play,
+    Power,
     PowerOff,
+    PowerOn,
     Rev,
     Right,
     Search,
     Select,
+    Stop,
     Up,
     VolumeDown,
     VolumeMute,
@@ -255,6 +859,8 @@ impl fmt::Display for Key {
         match *self {
             Key::Back => write!(f, "Back"),
             Key::Backspace => write!(f, "Backspace"),
+            Key::ButtonA => write!(f, "ButtonA"),
+            Key::ButtonB => write!(f, "ButtonB"),
             Key::ChannelDown => write!(f, "ChannelDown"),
             Key::ChannelUp => write!(f, "ChannelUp"),
             Key::Down => write!(f, "Down"),
@@ -271,12 +877,16 @@ impl fmt::Display for Key {
             Key::InputTuner => write!(f, "InputTuner"),
             Key::InstantReplay => write!(f, "InstantReplay"),
             Key::Left => write!(f, "Left"),
+            Key::Pause => write!(f, "Pause"),
             Key::Play => write!(f, "Play"),
+            Key::Power => write!(f, "Power"),
             Key::PowerOff => write!(f, "PowerOff"),
+            Key::PowerOn => write!(f, "PowerOn"),
             Key::Rev => write!(f, "Rev"),
             Key::Right => write!(f, "Right"),
             Key::Search => write!(f, "Search"),
             Key::Select => write!(f, "Select"),
+            Key::Stop => write!(f, "Stop"),
             Key::Up => write!(f, "Up"),
             Key::VolumeDown => write!(f, "VolumeDown"),
             Key::VolumeMute => write!(f, "VolumeMute"),
@@ -286,34 +896,100 @@ impl fmt::Display for Key {
     }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+impl FromStr for Key {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Key> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(Key::Lit(c));
+        }
+        if let Some(lit) = s.strip_prefix("Lit_") {
+            let lit = percent_decode_str(lit).decode_utf8_lossy();
+            let mut chars = lit.chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                return Ok(Key::Lit(c));
+            }
+        }
+        Ok(match s.to_lowercase().as_str() {
+            "back" => Key::Back,
+            "backspace" => Key::Backspace,
+            "buttona" => Key::ButtonA,
+            "buttonb" => Key::ButtonB,
+            "channeldown" => Key::ChannelDown,
+            "channelup" => Key::ChannelUp,
+            "down" => Key::Down,
+            "enter" => Key::Enter,
+            "findremote" => Key::FindRemote,
+            "fwd" => Key::Fwd,
+            "home" => Key::Home,
+            "info" => Key::Info,
+            "inputav1" => Key::InputAV1,
+            "inputhdmi1" => Key::InputHDMI1,
+            "inputhdmi2" => Key::InputHDMI2,
+            "inputhdmi3" => Key::InputHDMI3,
+            "inputhdmi4" => Key::InputHDMI4,
+            "inputtuner" => Key::InputTuner,
+            "instantreplay" => Key::InstantReplay,
+            "left" => Key::Left,
+            "pause" => Key::Pause,
+            "play" => Key::Play,
+            "power" => Key::Power,
+            "poweroff" => Key::PowerOff,
+            "poweron" => Key::PowerOn,
+            "rev" => Key::Rev,
+            "right" => Key::Right,
+            "search" => Key::Search,
+            "select" => Key::Select,
+            "stop" => Key::Stop,
+            "up" => Key::Up,
+            "volumedown" => Key::VolumeDown,
+            "volumemute" => Key::VolumeMute,
+            "volumeup" => Key::VolumeUp,
+            _ => return Err(Error::Argument(format!("unknown key {}", s))),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
 pub struct DeviceInfo {
     pub advertising_id: String,
+    pub audio_guide_enabled: Option<bool>,
+    pub av_sync_calibration_enabled: Option<String>,
     pub build_number: String,
+    #[serde(default)]
     pub can_use_wifi_extender: bool,
     pub clock_format: String,
     pub country: String,
+    #[serde(default)]
     pub davinci_version: String,
     pub default_device_name: String,
     pub developer_enabled: bool,
     pub device_id: String,
+    pub ecp_setting_mode: Option<String>,
     pub ethernet_mac: Option<String>,
+    #[serde(default)]
     pub find_remote_is_possible: bool,
     pub friendly_device_name: String,
     pub friendly_model_name: String,
     pub grandcentral_version: String,
+    #[serde(default)]
     pub has_mobile_screensaver: bool,
     pub has_play_on_roku: bool,
-    #[serde(rename = "has-wifi-5G-support")]
+    #[serde(rename(deserialize = "has-wifi-5G-support"))]
     pub has_wifi_5g_support: bool,
+    #[serde(default)]
     pub has_wifi_extender: bool,
+    #[serde(default)]
     pub headphones_connected: bool,
+    #[serde(default)]
     pub is_stick: bool,
     pub is_tv: bool,
     pub keyed_developer_id: String,
     pub language: String,
     pub locale: String,
+    pub mobile_has_live_tv: Option<bool>,
     pub model_name: String,
     pub model_number: String,
     pub model_region: String,
@@ -329,11 +1005,13 @@ pub struct DeviceInfo {
     pub software_build: String,
     pub software_version: String,
     pub support_url: String,
+    pub supports_airplay: Option<bool>,
     pub supports_audio_guide: bool,
     pub supports_ecs_microphone: bool,
     pub supports_ecs_textedit: bool,
     pub supports_ethernet: bool,
     pub supports_find_remote: bool,
+    #[serde(default)]
     pub supports_private_listening: bool,
     pub supports_rva: bool,
     pub supports_suspend: bool,
@@ -343,7 +1021,10 @@ pub struct DeviceInfo {
     pub time_zone_name: String,
     pub time_zone_offset: i32,
     pub time_zone_tz: String,
+    pub trc_channel_version: Option<String>,
+    pub trc_version: Option<String>,
     pub udn: String,
+    pub ui_resolution: Option<String>,
     pub uptime: u32,
     pub user_device_location: String,
     pub user_device_name: String,
@@ -353,6 +1034,383 @@ pub struct DeviceInfo {
     pub wifi_mac: String,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ChanPerf {
+    pub plugin: Option<ChanPerfPlugin>,
+    pub status: String,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub struct ChanPerfPlugin {
+    pub id: Option<String>,
+    pub cpu_percent: CpuPercent,
+    pub memory: Memory,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub struct CpuPercent {
+    pub duration_seconds: u32,
+    pub user: f64,
+    pub sys: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Memory {
+    pub used: u64,
+    pub res: u64,
+    pub anon: u64,
+    pub swap: u64,
+    pub file: u64,
+    pub shared: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct R2d2Bitmaps {
+    pub sizes: Option<BitmapSizes>,
+    #[serde(default)]
+    pub bitmaps: BitmapList,
+    pub status: String,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct BitmapSizes {
+    pub total: Option<u64>,
+    pub used: Option<u64>,
+    pub free: Option<u64>,
+    pub max: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct BitmapList {
+    #[serde(default, rename(deserialize = "bitmap"))]
+    pub bitmaps: Vec<Bitmap>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Bitmap {
+    pub name: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub size: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub struct FwBeacons {
+    #[serde(default)]
+    pub tracking_enabled: bool,
+    #[serde(default)]
+    pub data: FwBeaconsData,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct FwBeaconsData {
+    #[serde(default, rename(deserialize = "beacon"))]
+    pub beacons: Vec<FwBeacon>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub struct FwBeacon {
+    pub event: BeaconEvent,
+    pub channel_id: Option<String>,
+    pub timestamp: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PluginRegistry {
+    pub registry: Option<Registry>,
+    pub status: String,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub struct Registry {
+    pub dev_id: Option<String>,
+    pub plugins: Option<String>,
+    pub space_available: Option<u64>,
+    #[serde(default)]
+    pub sections: RegistrySections,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct RegistrySections {
+    #[serde(default, rename(deserialize = "section"))]
+    pub sections: Vec<RegistrySection>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RegistrySection {
+    pub name: String,
+    #[serde(default)]
+    pub items: RegistryItems,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct RegistryItems {
+    #[serde(default, rename(deserialize = "item"))]
+    pub items: Vec<RegistryItem>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RegistryItem {
+    pub key: String,
+    #[serde(default)]
+    pub value: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub struct SgRendezvous {
+    #[serde(default)]
+    pub tracking_enabled: bool,
+    #[serde(default)]
+    pub data: SgRendezvousData,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SgRendezvousData {
+    #[serde(default, rename(deserialize = "item"))]
+    pub items: Vec<SgRendezvousItem>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SgRendezvousItem {
+    pub id: Option<u64>,
+    pub thread: Option<String>,
+    pub node: Option<String>,
+    pub field: Option<String>,
+    pub ms: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TvChannels {
+    #[serde(default, rename(deserialize = "channel"), alias = "channels")]
+    pub channels: Vec<TvChannel>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub struct TvChannel {
+    pub number: String,
+    pub name: String,
+    #[serde(rename(deserialize = "type"), alias = "channel_type")]
+    pub channel_type: String,
+    #[serde(default, alias = "user_hidden")]
+    pub user_hidden: bool,
+    #[serde(default, alias = "user_favorite")]
+    pub user_favorite: bool,
+    #[serde(default, alias = "physical_channel")]
+    pub physical_channel: Option<u32>,
+    #[serde(default, alias = "physical_frequency")]
+    pub physical_frequency: Option<u32>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TvActiveChannel {
+    pub channel: Option<TvChannelStatus>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all(deserialize = "kebab-case"))]
+pub struct TvChannelStatus {
+    pub number: String,
+    pub name: String,
+    #[serde(rename(deserialize = "type"), alias = "channel_type")]
+    pub channel_type: String,
+    #[serde(default, alias = "user_hidden")]
+    pub user_hidden: bool,
+    #[serde(alias = "physical_channel")]
+    pub physical_channel: Option<u32>,
+    #[serde(alias = "physical_frequency")]
+    pub physical_frequency: Option<u32>,
+    #[serde(alias = "active_input")]
+    pub active_input: Option<bool>,
+    #[serde(alias = "signal_state")]
+    pub signal_state: Option<String>,
+    #[serde(alias = "signal_mode")]
+    pub signal_mode: Option<String>,
+    #[serde(alias = "signal_quality")]
+    pub signal_quality: Option<i32>,
+    #[serde(alias = "signal_strength")]
+    pub signal_strength: Option<i32>,
+    #[serde(alias = "program_title")]
+    pub program_title: Option<String>,
+    #[serde(alias = "program_description")]
+    pub program_description: Option<String>,
+    #[serde(alias = "program_ratings")]
+    pub program_ratings: Option<String>,
+    #[serde(alias = "program_parental_rating")]
+    pub program_parental_rating: Option<String>,
+    #[serde(alias = "program_has_cc")]
+    pub program_has_cc: Option<bool>,
+    #[serde(alias = "program_analog_audio")]
+    pub program_analog_audio: Option<String>,
+    #[serde(alias = "program_digital_audio")]
+    pub program_digital_audio: Option<String>,
+    #[serde(alias = "program_audio_format")]
+    pub program_audio_format: Option<String>,
+    #[serde(alias = "program_audio_language")]
+    pub program_audio_language: Option<String>,
+    #[serde(alias = "program_audio_languages")]
+    pub program_audio_languages: Option<String>,
+    #[serde(alias = "program_start_time")]
+    pub program_start_time: Option<u64>,
+    #[serde(alias = "program_duration")]
+    pub program_duration: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeepLink {
+    app_id: Option<String>,
+    content_id: String,
+    media_type: Option<DeepLinkType>,
+    params: Vec<(String, String)>,
+}
+
+impl DeepLink {
+    pub fn new(content_id: String) -> DeepLink {
+        DeepLink {
+            app_id: None,
+            content_id,
+            media_type: None,
+            params: vec![],
+        }
+    }
+
+    pub fn into_query_pairs(self) -> Vec<(String, String)> {
+        LaunchParams::from(self).into_query_pairs()
+    }
+
+    pub fn launch(&mut self, app_id: String) -> &mut DeepLink {
+        self.app_id = Some(app_id);
+        self
+    }
+
+    pub fn media_type(&mut self, media_type: DeepLinkType) -> &mut DeepLink {
+        self.media_type = Some(media_type);
+        self
+    }
+
+    pub fn param(&mut self, key: String, value: String) -> &mut DeepLink {
+        self.params.push((key, value));
+        self
+    }
+}
+
+impl From<DeepLink> for LaunchParams {
+    fn from(link: DeepLink) -> LaunchParams {
+        let mut params = LaunchParams::new();
+        params.content_id(link.content_id);
+        if let Some(media_type) = link.media_type {
+            params.media_type(media_type.to_string());
+        }
+        for (key, value) in link.params {
+            params.param(key, value);
+        }
+        params
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeepLinkType {
+    Movie,
+    Episode,
+    Season,
+    Series,
+    ShortFormVideo,
+    TvSpecial,
+    Special,
+    Live,
+}
+
+impl fmt::Display for DeepLinkType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                DeepLinkType::Movie => "movie",
+                DeepLinkType::Episode => "episode",
+                DeepLinkType::Season => "season",
+                DeepLinkType::Series => "series",
+                DeepLinkType::ShortFormVideo => "shortFormVideo",
+                DeepLinkType::TvSpecial => "tvSpecial",
+                DeepLinkType::Special => "special",
+                DeepLinkType::Live => "live",
+            }
+        )
+    }
+}
+
+impl FromStr for DeepLinkType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<DeepLinkType> {
+        Ok(match s {
+            "movie" => DeepLinkType::Movie,
+            "episode" => DeepLinkType::Episode,
+            "season" => DeepLinkType::Season,
+            "series" => DeepLinkType::Series,
+            "shortFormVideo" => DeepLinkType::ShortFormVideo,
+            "tvSpecial" => DeepLinkType::TvSpecial,
+            "special" => DeepLinkType::Special,
+            "live" => DeepLinkType::Live,
+            _ => return Err(Error::Argument(format!("unknown media type {}", s))),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LaunchParams {
+    content_id: Option<String>,
+    media_type: Option<String>,
+    params: Vec<(String, String)>,
+}
+
+impl LaunchParams {
+    pub fn new() -> LaunchParams {
+        LaunchParams::default()
+    }
+
+    pub fn into_query_pairs(self) -> Vec<(String, String)> {
+        let mut ret = vec![];
+        if let Some(content_id) = self.content_id {
+            ret.push(("contentId".to_string(), content_id));
+        }
+        if let Some(media_type) = self.media_type {
+            ret.push(("mediaType".to_string(), media_type));
+        }
+        ret.extend(self.params);
+        ret
+    }
+
+    pub fn content_id(&mut self, content_id: String) -> &mut LaunchParams {
+        self.content_id = Some(content_id);
+        self
+    }
+
+    pub fn media_type(&mut self, media_type: String) -> &mut LaunchParams {
+        self.media_type = Some(media_type);
+        self
+    }
+
+    pub fn param(&mut self, key: String, value: String) -> &mut LaunchParams {
+        match key.as_str() {
+            "contentId" => self.content_id = Some(value),
+            "mediaType" => self.media_type = Some(value),
+            _ => self.params.push((key, value)),
+        }
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Search {
     keyword: String,
     launch: Option<bool>,
@@ -364,6 +1422,8 @@ pub struct Search {
     show_unavailable: Option<bool>,
     title: Option<String>,
     tmsid: Option<String>,
+    resolve_providers: bool,
+    installed_only: bool,
 }
 
 impl Search {
@@ -379,10 +1439,12 @@ impl Search {
             show_unavailable: None,
             title: None,
             tmsid: None,
+            resolve_providers: false,
+            installed_only: false,
         }
     }
 
-    fn build(self) -> Vec<(String, String)> {
+    pub fn into_query_pairs(self) -> Vec<(String, String)> {
         let mut ret = vec![("keyword", self.keyword)];
         if let Some(launch) = self.launch {
             ret.push(("launch", launch.to_string()));
@@ -440,7 +1502,7 @@ impl Search {
                 providers.push(provider);
             }
             None => {
-                self.providers = Some(vec![]);
+                self.providers = Some(vec![provider]);
             }
         }
         self
@@ -452,7 +1514,7 @@ impl Search {
                 provider_ids.push(provider_id);
             }
             None => {
-                self.provider_ids = Some(vec![]);
+                self.provider_ids = Some(vec![provider_id]);
             }
         }
         self
@@ -467,6 +1529,7 @@ impl Search {
         self.season = Some(season);
         self
     }
+
     pub fn show_unavailable(&mut self, show_unavailable: bool) -> &mut Search {
         self.show_unavailable = Some(show_unavailable);
         self
@@ -481,8 +1544,59 @@ impl Search {
         self.tmsid = Some(tmsid);
         self
     }
+
+    pub fn resolve_providers(&mut self, resolve_providers: bool) -> &mut Search {
+        self.resolve_providers = resolve_providers;
+        self
+    }
+
+    pub fn installed_only(&mut self, installed_only: bool) -> &mut Search {
+        self.installed_only = installed_only;
+        self
+    }
+
+    pub fn resolve(&mut self, apps: &[App]) -> Result<()> {
+        if self.resolve_providers {
+            let mut unresolved = vec![];
+            for provider in self.providers.take().unwrap_or_default() {
+                let id = apps
+                    .iter()
+                    .find(|app| app.name.eq_ignore_ascii_case(provider.trim()))
+                    .and_then(|app| app.id.clone());
+                match id {
+                    Some(id) => {
+                        self.provider_id(id);
+                    }
+                    None => unresolved.push(provider),
+                }
+            }
+            self.providers = Some(unresolved).filter(|providers| !providers.is_empty());
+        }
+        if self.installed_only {
+            let installed: Vec<String> = apps
+                .iter()
+                .filter_map(|app| app.id.clone())
+                .filter(|id| !id.starts_with("tvinput."))
+                .collect();
+            let provider_ids: Vec<String> = match self.provider_ids.take() {
+                Some(provider_ids) => provider_ids
+                    .into_iter()
+                    .filter(|id| installed.contains(id))
+                    .collect(),
+                None => installed,
+            };
+            if provider_ids.is_empty() {
+                return Err(Error::Argument(
+                    "none of the requested providers are installed".to_string(),
+                ));
+            }
+            self.provider_ids = Some(provider_ids);
+        }
+        Ok(())
+    }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SearchType {
     Movie,
     TvShow,
@@ -490,3 +1604,18 @@ pub enum SearchType {
     Channel,
     Game,
 }
+
+impl FromStr for SearchType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<SearchType> {
+        Ok(match s {
+            "movie" => SearchType::Movie,
+            "tv-show" => SearchType::TvShow,
+            "person" => SearchType::Person,
+            "channel" => SearchType::Channel,
+            "game" => SearchType::Game,
+            _ => return Err(Error::Argument(format!("unknown search type {}", s))),
+        })
+    }
+}
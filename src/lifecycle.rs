@@ -0,0 +1,136 @@
+use crate::{ActiveApp, App, Device, Result};
+use futures_util::stream::Stream;
+use serde::Serialize;
+use std::{
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::mpsc,
+    task::JoinHandle,
+    time::{sleep, Instant},
+};
+
+const CAPACITY: usize = 64;
+const LAUNCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AppState {
+    Launching,
+    Active,
+    Backgrounded,
+    Suspended,
+    Exited,
+}
+
+impl AppState {
+    pub fn is_running(self) -> bool {
+        matches!(self, AppState::Active | AppState::Suspended)
+    }
+
+    fn next(self, app_id: &str, active: &ActiveApp, launching_for: Duration) -> AppState {
+        if active.app.id.as_deref() == Some(app_id) {
+            return match active.screensaver {
+                Some(_) => AppState::Suspended,
+                None => AppState::Active,
+            };
+        }
+        let home = active.app.id.is_none();
+        match self {
+            AppState::Launching if launching_for < LAUNCH_TIMEOUT => AppState::Launching,
+            AppState::Active | AppState::Suspended if !home => AppState::Backgrounded,
+            AppState::Backgrounded if !home => AppState::Backgrounded,
+            _ => AppState::Exited,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AppTransition {
+    pub previous: AppState,
+    pub current: AppState,
+    pub foreground: App,
+}
+
+pub struct AppSession {
+    app_id: String,
+    state: Arc<Mutex<AppState>>,
+    rx: mpsc::Receiver<Result<AppTransition>>,
+    task: JoinHandle<()>,
+}
+
+impl AppSession {
+    pub fn app_id(&self) -> &str {
+        &self.app_id
+    }
+
+    pub fn state(&self) -> AppState {
+        *self.state.lock().unwrap()
+    }
+}
+
+impl Stream for AppSession {
+    type Item = Result<AppTransition>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context,
+    ) -> Poll<Option<Result<AppTransition>>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for AppSession {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl Device {
+    pub fn track_app(&self, app_id: &str, interval: Duration) -> AppSession {
+        let (tx, rx) = mpsc::channel(CAPACITY);
+        let state = Arc::new(Mutex::new(AppState::Launching));
+        let task = tokio::spawn({
+            let device = self.clone();
+            let app_id = app_id.to_string();
+            let state = state.clone();
+            async move {
+                let started = Instant::now();
+                loop {
+                    let transition = match device.active_app().await {
+                        Ok(active) => {
+                            let mut state = state.lock().unwrap();
+                            let previous = *state;
+                            *state = previous.next(&app_id, &active, started.elapsed());
+                            if *state == previous {
+                                None
+                            } else {
+                                Some(Ok(AppTransition {
+                                    previous,
+                                    current: *state,
+                                    foreground: active.app,
+                                }))
+                            }
+                        }
+                        Err(err) => Some(Err(err)),
+                    };
+                    if let Some(transition) = transition {
+                        if tx.send(transition).await.is_err() {
+                            return;
+                        }
+                    }
+                    sleep(interval).await;
+                }
+            }
+        });
+        AppSession {
+            app_id: app_id.to_string(),
+            state,
+            rx,
+            task,
+        }
+    }
+}
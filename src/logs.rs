@@ -0,0 +1,76 @@
+use crate::Result;
+use futures_util::stream::Stream;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    net::TcpStream,
+    sync::mpsc,
+    task::JoinHandle,
+    time::sleep,
+};
+
+pub const PORT: u16 = 8085;
+
+const CAPACITY: usize = 256;
+const RECONNECT: Duration = Duration::from_secs(1);
+
+pub struct DebugLogs {
+    rx: mpsc::Receiver<Result<String>>,
+    task: JoinHandle<()>,
+}
+
+impl Stream for DebugLogs {
+    type Item = Result<String>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Result<String>>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for DebugLogs {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+pub(crate) fn spawn(host: String, port: u16) -> DebugLogs {
+    let (tx, rx) = mpsc::channel(CAPACITY);
+    let task = tokio::spawn(async move {
+        loop {
+            let result = match TcpStream::connect((host.as_str(), port)).await {
+                Ok(stream) => read(stream, &tx).await,
+                Err(err) => Err(err.into()),
+            };
+            if let Err(err) = result {
+                if tx.send(Err(err)).await.is_err() {
+                    return;
+                }
+            }
+            if tx.is_closed() {
+                return;
+            }
+            sleep(RECONNECT).await;
+        }
+    });
+    DebugLogs { rx, task }
+}
+
+async fn read(stream: TcpStream, tx: &mpsc::Sender<Result<String>>) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut line = vec![];
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line).await? == 0 {
+            return Ok(());
+        }
+        let text = String::from_utf8_lossy(&line);
+        let text = text.trim_end_matches(['\r', '\n']).to_string();
+        if tx.send(Ok(text)).await.is_err() {
+            return Ok(());
+        }
+    }
+}
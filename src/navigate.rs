@@ -0,0 +1,163 @@
+use crate::{Device, Error, Key, Result, RokuControl};
+use std::{str::FromStr, time::Duration};
+use tokio::{runtime::Handle, time::sleep};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn key(self) -> Key {
+        match self {
+            Direction::Up => Key::Up,
+            Direction::Down => Key::Down,
+            Direction::Left => Key::Left,
+            Direction::Right => Key::Right,
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Direction> {
+        Ok(match s.to_lowercase().as_str() {
+            "up" => Direction::Up,
+            "down" => Direction::Down,
+            "left" => Direction::Left,
+            "right" => Direction::Right,
+            _ => return Err(Error::Argument(format!("invalid direction {}", s))),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Acceleration {
+    initial: Duration,
+    minimum: Duration,
+    factor: f64,
+    hold: Duration,
+}
+
+impl Default for Acceleration {
+    fn default() -> Acceleration {
+        Acceleration {
+            initial: Duration::from_millis(300),
+            minimum: Duration::from_millis(60),
+            factor: 0.8,
+            hold: Duration::from_millis(20),
+        }
+    }
+}
+
+impl Acceleration {
+    pub fn new() -> Acceleration {
+        Acceleration::default()
+    }
+
+    pub fn initial(&mut self, initial: Duration) -> &mut Self {
+        self.initial = initial;
+        self
+    }
+
+    pub fn minimum(&mut self, minimum: Duration) -> &mut Self {
+        self.minimum = minimum;
+        self
+    }
+
+    pub fn factor(&mut self, factor: f64) -> &mut Self {
+        self.factor = factor;
+        self
+    }
+
+    pub fn hold(&mut self, hold: Duration) -> &mut Self {
+        self.hold = hold;
+        self
+    }
+
+    pub fn interval(&self, step: u32) -> Duration {
+        let scale = self
+            .factor
+            .clamp(0.0, 1.0)
+            .powi(step.min(i32::MAX as u32) as i32);
+        self.initial.mul_f64(scale).max(self.minimum)
+    }
+
+    pub async fn scroll(
+        &self,
+        device: &(impl RokuControl + ?Sized),
+        direction: Direction,
+        count: u32,
+    ) -> Result<()> {
+        let key = direction.key();
+        for step in 0..count {
+            if step > 0 {
+                sleep(self.interval(step - 1)).await;
+            }
+            device.keydown(&key).await?;
+            sleep(self.hold).await;
+            device.keyup(&key).await?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Hold {
+    device: Device,
+    key: Key,
+    released: bool,
+}
+
+impl Hold {
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    pub async fn release(mut self) -> Result<()> {
+        self.released = true;
+        self.device.keyup(&self.key).await
+    }
+}
+
+impl Drop for Hold {
+    fn drop(&mut self) {
+        if self.released {
+            return;
+        }
+        if let Ok(handle) = Handle::try_current() {
+            let device = self.device.clone();
+            let key = self.key;
+            handle.spawn(async move { device.keyup(&key).await });
+        }
+    }
+}
+
+impl Device {
+    pub async fn scroll(&self, direction: Direction, count: u32) -> Result<()> {
+        self.scroll_with(direction, count, &Acceleration::default())
+            .await
+    }
+
+    pub async fn scroll_with(
+        &self,
+        direction: Direction,
+        count: u32,
+        acceleration: &Acceleration,
+    ) -> Result<()> {
+        acceleration.scroll(self, direction, count).await
+    }
+
+    pub async fn hold_direction(&self, direction: Direction) -> Result<Hold> {
+        let key = direction.key();
+        self.keydown(&key).await?;
+        Ok(Hold {
+            device: self.clone(),
+            key,
+            released: false,
+        })
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{
+    consts, millis, App, Capability, Device, Program, Result, Screensaver, TvChannelStatus,
+};
+use futures_util::try_join;
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct NowPlaying {
+    pub app: App,
+    pub screensaver: Option<Screensaver>,
+    pub state: String,
+    pub position: Option<Duration>,
+    pub duration: Option<Duration>,
+    pub is_live: bool,
+    pub channel: Option<TvChannelStatus>,
+}
+
+impl NowPlaying {
+    pub fn is_playing(&self) -> bool {
+        self.state == "play"
+    }
+
+    pub fn is_home(&self) -> bool {
+        self.app.id.is_none()
+    }
+
+    pub fn program(&self) -> Option<Program> {
+        self.channel.as_ref()?.program()
+    }
+}
+
+impl Device {
+    pub async fn now_playing(&self) -> Result<NowPlaying> {
+        let (active, player) = try_join!(self.active_app(), self.media_player())?;
+        let channel = if active.app.id.as_deref() == Some(consts::APP_LIVE_TV)
+            && self.supports(Capability::TvActiveChannel).await?
+        {
+            self.tv_active_channel().await?.channel
+        } else {
+            None
+        };
+        Ok(NowPlaying {
+            app: active.app,
+            screensaver: active.screensaver,
+            state: player.state,
+            position: millis(player.position.as_deref()),
+            duration: millis(player.duration.as_deref()),
+            is_live: player.is_live.unwrap_or(false),
+            channel,
+        })
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{Device, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path, time::Duration};
+use url::Url;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(from = "Entry")]
+pub struct Profile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serial: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_interval_ms: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Entry {
+    Host(String),
+    Detailed {
+        host: Option<String>,
+        serial: Option<String>,
+        mac: Option<String>,
+        password: Option<String>,
+        timeout_ms: Option<u64>,
+        key_interval_ms: Option<u64>,
+    },
+}
+
+impl From<Entry> for Profile {
+    fn from(entry: Entry) -> Profile {
+        match entry {
+            Entry::Host(host) => Profile::new(host),
+            Entry::Detailed {
+                host,
+                serial,
+                mac,
+                password,
+                timeout_ms,
+                key_interval_ms,
+            } => Profile {
+                host,
+                serial,
+                mac,
+                password,
+                timeout_ms,
+                key_interval_ms,
+            },
+        }
+    }
+}
+
+impl Profile {
+    pub fn new(host: String) -> Profile {
+        Profile {
+            host: Some(host),
+            ..Profile::default()
+        }
+    }
+
+    pub fn url(&self) -> Result<Option<Url>> {
+        self.host.as_deref().map(host_url).transpose()
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_ms.map(Duration::from_millis)
+    }
+
+    pub fn key_interval(&self) -> Option<Duration> {
+        self.key_interval_ms.map(Duration::from_millis)
+    }
+
+    pub async fn device(&self) -> Result<Device> {
+        let device = match (self.url()?, &self.serial) {
+            (Some(url), _) => Device::new(url),
+            (None, Some(serial)) => find_serial(serial).await?,
+            (None, None) => {
+                return Err(Error::Argument(
+                    "profile needs a host or serial".to_string(),
+                ))
+            }
+        };
+        match self.timeout() {
+            Some(timeout) => device.with_timeout(timeout),
+            None => Ok(device),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Profiles {
+    profiles: BTreeMap<String, Profile>,
+}
+
+impl Profiles {
+    pub fn new() -> Profiles {
+        Profiles::default()
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Profiles> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        let parsed = if is_json(path) {
+            serde_json::from_str(&text).map_err(|err| err.to_string())
+        } else {
+            toml::from_str(&text).map_err(|err| err.to_string())
+        };
+        parsed
+            .map_err(|err| Error::Argument(format!("failed to parse {}: {}", path.display(), err)))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let text = if is_json(path) {
+            serde_json::to_string_pretty(self)
+                .map(|text| text + "\n")
+                .map_err(|err| err.to_string())
+        } else {
+            toml::to_string(self).map_err(|err| err.to_string())
+        }
+        .map_err(Error::Argument)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, text)?;
+        Ok(())
+    }
+
+    pub fn get(&self, alias: &str) -> Option<&Profile> {
+        self.profiles.get(alias)
+    }
+
+    pub fn insert(&mut self, alias: String, profile: Profile) -> Option<Profile> {
+        self.profiles.insert(alias, profile)
+    }
+
+    pub fn remove(&mut self, alias: &str) -> Option<Profile> {
+        self.profiles.remove(alias)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Profile)> {
+        self.profiles.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+}
+
+pub fn host_url(host: &str) -> Result<Url> {
+    if host.contains("://") {
+        return Ok(Url::parse(host)?);
+    }
+    let (authority, path) = match host.find('/') {
+        Some(index) => host.split_at(index),
+        None => (host, ""),
+    };
+    let path = path.trim_matches('/');
+    let path = if path.is_empty() {
+        String::new()
+    } else {
+        format!("{}/", path)
+    };
+    if authority.contains(':') {
+        Ok(Url::parse(&format!("http://{}/{}", authority, path))?)
+    } else {
+        Ok(Url::parse(&format!("http://{}:8060/{}", authority, path))?)
+    }
+}
+
+async fn find_serial(serial: &str) -> Result<Device> {
+    for device in Device::discover().await? {
+        if let Ok(info) = device.device_info().await {
+            if info.serial_number.eq_ignore_ascii_case(serial) {
+                return Ok(device);
+            }
+        }
+    }
+    Err(Error::Response(format!("no device with serial {}", serial)))
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension() == Some("json".as_ref())
+}
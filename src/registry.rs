@@ -0,0 +1,37 @@
+use crate::PluginRegistry;
+use std::collections::BTreeMap;
+
+impl PluginRegistry {
+    pub fn to_map(&self) -> BTreeMap<String, BTreeMap<String, String>> {
+        let sections = match &self.registry {
+            Some(registry) => &registry.sections.sections[..],
+            None => &[],
+        };
+        sections
+            .iter()
+            .map(|section| {
+                let items = section
+                    .items
+                    .items
+                    .iter()
+                    .map(|item| (item.key.clone(), item.value.clone()))
+                    .collect();
+                (section.name.clone(), items)
+            })
+            .collect()
+    }
+
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.registry
+            .as_ref()?
+            .sections
+            .sections
+            .iter()
+            .find(|candidate| candidate.name == section)?
+            .items
+            .items
+            .iter()
+            .find(|item| item.key == key)
+            .map(|item| item.value.as_str())
+    }
+}
@@ -0,0 +1,299 @@
+use crate::{CancellationToken, Device, Error, Result, Script};
+use chrono::{Datelike, Duration as ChronoDuration, Local, NaiveDateTime, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+use tokio::time::sleep;
+use url::Url;
+
+const MAX_WAIT: Duration = Duration::from_secs(60);
+const CRON_HORIZON: i64 = 366 * 24 * 60;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum When {
+    Once(NaiveDateTime),
+    Daily(NaiveTime),
+    Cron(Cron),
+}
+
+impl When {
+    pub fn parse(text: &str) -> Result<When> {
+        let text = text.trim();
+        for format in &[
+            "%Y-%m-%d %H:%M",
+            "%Y-%m-%dT%H:%M",
+            "%Y-%m-%d %H:%M:%S",
+            "%Y-%m-%dT%H:%M:%S",
+        ] {
+            if let Ok(time) = NaiveDateTime::parse_from_str(text, format) {
+                return Ok(When::Once(time));
+            }
+        }
+        for format in &["%H:%M", "%H:%M:%S"] {
+            if let Ok(time) = NaiveTime::parse_from_str(text, format) {
+                return Ok(When::Daily(time));
+            }
+        }
+        Ok(When::Cron(text.parse()?))
+    }
+
+    pub fn next_after(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            When::Once(time) => Some(*time).filter(|time| *time > now),
+            When::Daily(time) => {
+                let today = now.date().and_time(*time);
+                if today > now {
+                    Some(today)
+                } else {
+                    Some(today + ChronoDuration::days(1))
+                }
+            }
+            When::Cron(cron) => cron.next_after(now),
+        }
+    }
+}
+
+impl FromStr for When {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<When> {
+        When::parse(s)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cron {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl Cron {
+    pub fn next_after(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        let mut time = now.date().and_hms_opt(now.hour(), now.minute(), 0)?;
+        for _ in 0..CRON_HORIZON {
+            time += ChronoDuration::minutes(1);
+            if self.matches(time) {
+                return Some(time);
+            }
+        }
+        None
+    }
+
+    fn matches(&self, time: NaiveDateTime) -> bool {
+        let day = bit(self.days, time.day());
+        let weekday = bit(self.weekdays, time.weekday().num_days_from_sunday());
+        let date = match (self.any_day, self.any_weekday) {
+            (false, false) => day || weekday,
+            _ => day && weekday,
+        };
+        date && bit(self.minutes, time.minute())
+            && bit(self.hours, time.hour())
+            && bit(self.months, time.month())
+    }
+}
+
+impl FromStr for Cron {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Cron> {
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(Error::Argument(format!(
+                "invalid schedule {}, expected YYYY-MM-DD HH:MM, HH:MM or a cron expression",
+                s
+            )));
+        }
+        let mut weekdays = field(fields[4], 0, 7)?;
+        if bit(weekdays, 7) {
+            weekdays |= 1;
+        }
+        Ok(Cron {
+            minutes: field(fields[0], 0, 59)?,
+            hours: field(fields[1], 0, 23)?,
+            days: field(fields[2], 1, 31)?,
+            months: field(fields[3], 1, 12)?,
+            weekdays,
+            any_day: fields[2] == "*",
+            any_weekday: fields[4] == "*",
+        })
+    }
+}
+
+fn bit(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+fn field(text: &str, min: u32, max: u32) -> Result<u64> {
+    let invalid = || Error::Argument(format!("invalid cron field {}", text));
+    let mut mask = 0;
+    for part in text.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                start.parse().map_err(|_| invalid())?,
+                end.parse().map_err(|_| invalid())?,
+            )
+        } else {
+            let value = range.parse().map_err(|_| invalid())?;
+            (value, if part.contains('/') { max } else { value })
+        };
+        if start < min || end > max || start > end || step == 0 {
+            return Err(invalid());
+        }
+        for value in (start..=end).step_by(step) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Job {
+    pub id: u64,
+    pub when: String,
+    pub devices: Vec<String>,
+    pub script: String,
+}
+
+impl Job {
+    fn next_after(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        When::parse(&self.when).ok()?.next_after(now)
+    }
+}
+
+#[derive(Default)]
+pub struct Scheduler {
+    jobs: Vec<Job>,
+    path: Option<PathBuf>,
+}
+
+impl Scheduler {
+    pub fn new() -> Scheduler {
+        Scheduler::default()
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Scheduler> {
+        let path = path.as_ref().to_path_buf();
+        let jobs = read_jobs(&path)?;
+        Ok(Scheduler {
+            jobs,
+            path: Some(path),
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = serde_json::to_string_pretty(&self.jobs)
+            .map_err(|err| Error::Argument(err.to_string()))?;
+        fs::write(path, text + "\n")?;
+        Ok(())
+    }
+
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    pub fn at(&mut self, when: &str, devices: &[Device], script: &Script) -> Result<u64> {
+        When::parse(when)?;
+        let id = self.jobs.iter().map(|job| job.id).max().unwrap_or(0) + 1;
+        let script: Vec<String> = script.steps().iter().map(|step| step.to_string()).collect();
+        self.jobs.push(Job {
+            id,
+            when: when.to_string(),
+            devices: devices
+                .iter()
+                .map(|device| device.url().to_string())
+                .collect(),
+            script: script.join("\n"),
+        });
+        self.save()?;
+        Ok(id)
+    }
+
+    pub fn cancel(&mut self, id: u64) -> Result<bool> {
+        let count = self.jobs.len();
+        self.jobs.retain(|job| job.id != id);
+        if self.jobs.len() == count {
+            return Ok(false);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    pub fn next_run(&self, now: NaiveDateTime) -> Option<(NaiveDateTime, u64)> {
+        self.jobs
+            .iter()
+            .filter_map(|job| Some((job.next_after(now)?, job.id)))
+            .min()
+    }
+
+    pub async fn run<F: FnMut(&Job, &str, Result<()>)>(
+        &mut self,
+        cancel: &CancellationToken,
+        mut on_result: F,
+    ) -> Result<()> {
+        loop {
+            if let Some(jobs) = self.path.as_deref().and_then(|path| read_jobs(path).ok()) {
+                self.jobs = jobs;
+            }
+            let now = Local::now().naive_local();
+            let next = self.next_run(now);
+            let wait = next
+                .and_then(|(at, _)| (at - now).to_std().ok())
+                .unwrap_or(MAX_WAIT);
+            tokio::select! {
+                _ = cancel.cancelled() => return Ok(()),
+                _ = sleep(wait.min(MAX_WAIT)) => {}
+            }
+            let id = match next {
+                Some((_, id)) if wait <= MAX_WAIT => id,
+                _ => continue,
+            };
+            let job = match self.jobs.iter().find(|job| job.id == id) {
+                Some(job) => job.clone(),
+                None => continue,
+            };
+            let script = Script::parse(&job.script).map_err(|err| err.to_string());
+            for url in &job.devices {
+                let result = match (&script, Url::parse(url)) {
+                    (Err(err), _) => Err(Error::Argument(err.clone())),
+                    (_, Err(err)) => Err(err.into()),
+                    (Ok(script), Ok(url)) => script.run(&Device::new(url)).await,
+                };
+                on_result(&job, url, result);
+            }
+            if let Ok(When::Once(_)) = When::parse(&job.when) {
+                self.cancel(job.id)?;
+            }
+        }
+    }
+}
+
+fn read_jobs(path: &Path) -> Result<Vec<Job>> {
+    match fs::read_to_string(path) {
+        Ok(text) => serde_json::from_str(&text)
+            .map_err(|err| Error::Argument(format!("failed to parse {}: {}", path.display(), err))),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(err) => Err(err.into()),
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{dev::DevServer, Error, Image, Result};
+use image::RgbaImage;
+use std::{env, path::Path};
+use tokio::fs;
+
+const UPDATE_ENV: &str = "UPDATE_GOLDEN";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Region {
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Region {
+        Region {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x && y >= self.y && x - self.x < self.width && y - self.y < self.height
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Diff {
+    pub width: u32,
+    pub height: u32,
+    pub compared: u64,
+    pub differing: u64,
+}
+
+impl Diff {
+    pub fn ratio(&self) -> f64 {
+        if self.compared == 0 {
+            0.0
+        } else {
+            self.differing as f64 / self.compared as f64
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    tolerance: u8,
+    max_ratio: f64,
+    masks: Vec<Region>,
+}
+
+impl Default for Comparison {
+    fn default() -> Comparison {
+        Comparison {
+            tolerance: 8,
+            max_ratio: 0.0,
+            masks: vec![],
+        }
+    }
+}
+
+impl Comparison {
+    pub fn new() -> Comparison {
+        Comparison::default()
+    }
+
+    pub fn tolerance(&mut self, tolerance: u8) -> &mut Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn max_ratio(&mut self, max_ratio: f64) -> &mut Self {
+        self.max_ratio = max_ratio;
+        self
+    }
+
+    pub fn mask(&mut self, region: Region) -> &mut Self {
+        self.masks.push(region);
+        self
+    }
+
+    pub fn diff(&self, actual: &Image, expected: &Image) -> Result<Diff> {
+        let actual = decode(&actual.data)?;
+        let expected = decode(&expected.data)?;
+        if actual.dimensions() != expected.dimensions() {
+            return Err(Error::Argument(format!(
+                "screen size {}x{} does not match golden {}x{}",
+                actual.width(),
+                actual.height(),
+                expected.width(),
+                expected.height()
+            )));
+        }
+        let mut diff = Diff {
+            width: actual.width(),
+            height: actual.height(),
+            compared: 0,
+            differing: 0,
+        };
+        for (x, y, pixel) in actual.enumerate_pixels() {
+            if self.masks.iter().any(|mask| mask.contains(x, y)) {
+                continue;
+            }
+            diff.compared += 1;
+            let golden = expected.get_pixel(x, y);
+            if pixel
+                .0
+                .iter()
+                .zip(golden.0.iter())
+                .any(|(a, b)| a.abs_diff(*b) > self.tolerance)
+            {
+                diff.differing += 1;
+            }
+        }
+        Ok(diff)
+    }
+
+    pub fn matches(&self, actual: &Image, expected: &Image) -> Result<bool> {
+        Ok(self.diff(actual, expected)?.ratio() <= self.max_ratio)
+    }
+}
+
+impl DevServer {
+    pub async fn expect_screen_matches<P: AsRef<Path>>(&self, golden_path: P) -> Result<()> {
+        self.expect_screen_matches_with(golden_path, &Comparison::default())
+            .await
+    }
+
+    pub async fn expect_screen_matches_with<P: AsRef<Path>>(
+        &self,
+        golden_path: P,
+        comparison: &Comparison,
+    ) -> Result<()> {
+        let golden_path = golden_path.as_ref();
+        let screen = self.screenshot().await?;
+        if env::var_os(UPDATE_ENV).is_some() {
+            fs::write(golden_path, &screen.data).await?;
+            return Ok(());
+        }
+        let golden = match fs::read(golden_path).await {
+            Ok(data) => Image {
+                content_type: None,
+                data,
+            },
+            Err(_) => {
+                return Err(Error::Argument(format!(
+                    "missing {}, run with {}=1",
+                    golden_path.display(),
+                    UPDATE_ENV
+                )))
+            }
+        };
+        let diff = comparison.diff(&screen, &golden)?;
+        if diff.ratio() > comparison.max_ratio {
+            return Err(Error::Response(format!(
+                "screen differs from {}: {} of {} pixels",
+                golden_path.display(),
+                diff.differing,
+                diff.compared
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn decode(data: &[u8]) -> Result<RgbaImage> {
+    image::load_from_memory(data)
+        .map(|image| image.to_rgba8())
+        .map_err(|err| Error::Argument(format!("failed to decode image: {}", err)))
+}
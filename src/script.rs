@@ -0,0 +1,134 @@
+use crate::{App, Error, Key, Result, RokuControl};
+use std::{fmt, str::FromStr, time::Duration};
+use tokio::time::sleep;
+
+const TYPE_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Step {
+    Key(Key),
+    KeyDown(Key),
+    KeyUp(Key),
+    Sleep(Duration),
+    Launch(String),
+    Type(String),
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Step::Key(key) => write!(f, "key {}", key),
+            Step::KeyDown(key) => write!(f, "keydown {}", key),
+            Step::KeyUp(key) => write!(f, "keyup {}", key),
+            Step::Sleep(duration) => write!(f, "sleep {}", duration.as_secs_f64()),
+            Step::Launch(app_id) => write!(f, "launch {}", app_id),
+            Step::Type(text) => write!(f, "type \"{}\"", text),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Script {
+    steps: Vec<Step>,
+}
+
+impl Script {
+    pub fn new(steps: Vec<Step>) -> Script {
+        Script { steps }
+    }
+
+    pub fn parse(text: &str) -> Result<Script> {
+        let mut steps = vec![];
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            parse_line(line, &mut steps)
+                .map_err(|err| Error::Argument(format!("line {}: {}", number + 1, err)))?;
+        }
+        Ok(Script { steps })
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub async fn run(&self, device: &(impl RokuControl + ?Sized)) -> Result<()> {
+        for step in &self.steps {
+            match step {
+                Step::Key(key) => device.keypress(key).await?,
+                Step::KeyDown(key) => device.keydown(key).await?,
+                Step::KeyUp(key) => device.keyup(key).await?,
+                Step::Sleep(duration) => sleep(*duration).await,
+                Step::Launch(app_id) => {
+                    let app = App {
+                        id: Some(app_id.clone()),
+                        name: String::new(),
+                        version: None,
+                    };
+                    device.launch(&app).await?
+                }
+                Step::Type(text) => device.type_text(text, TYPE_INTERVAL).await?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Script {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Script> {
+        Script::parse(s)
+    }
+}
+
+fn parse_line(line: &str, steps: &mut Vec<Step>) -> std::result::Result<(), String> {
+    let (command, rest) = match line.split_once(char::is_whitespace) {
+        Some((command, rest)) => (command, rest.trim()),
+        None => (line, ""),
+    };
+    let keys = || -> std::result::Result<Vec<Key>, String> {
+        if rest.is_empty() {
+            return Err(format!("{} needs at least one key", command));
+        }
+        rest.split_whitespace()
+            .map(|key| key.parse().map_err(|err: Error| err.to_string()))
+            .collect()
+    };
+    match command {
+        "key" | "press" => steps.extend(keys()?.into_iter().map(Step::Key)),
+        "keydown" => steps.extend(keys()?.into_iter().map(Step::KeyDown)),
+        "keyup" => steps.extend(keys()?.into_iter().map(Step::KeyUp)),
+        "sleep" | "wait" => steps.push(Step::Sleep(parse_duration(rest)?)),
+        "launch" if !rest.is_empty() => steps.push(Step::Launch(rest.to_string())),
+        "launch" => return Err("launch needs an app id".to_string()),
+        "type" => steps.push(Step::Type(unquote(rest).to_string())),
+        _ => return Err(format!("unknown command {}", command)),
+    }
+    Ok(())
+}
+
+pub fn parse_duration(value: &str) -> std::result::Result<Duration, String> {
+    let (number, scale) = if let Some(ms) = value.strip_suffix("ms") {
+        (ms, 0.001)
+    } else if let Some(s) = value.strip_suffix('s') {
+        (s, 1.0)
+    } else {
+        (value, 1.0)
+    };
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .and_then(|number| Duration::try_from_secs_f64(number * scale).ok())
+        .ok_or_else(|| format!("invalid duration {}", value))
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .unwrap_or(value)
+}
@@ -0,0 +1,108 @@
+use crate::{Device, DeviceClass, Error, Key, Result};
+use std::{fmt, str::FromStr, time::Duration};
+use tokio::time::sleep;
+
+const PRESS_INTERVAL: Duration = Duration::from_millis(250);
+const HOME_SETTLE: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SecretScreen {
+    Platform,
+    Wireless,
+    ChannelInfo,
+    Developer,
+    Reboot,
+    Reset,
+    BitrateOverride,
+    Hdmi,
+    Antenna,
+}
+
+impl SecretScreen {
+    pub const ALL: &'static [SecretScreen] = &[
+        SecretScreen::Platform,
+        SecretScreen::Wireless,
+        SecretScreen::ChannelInfo,
+        SecretScreen::Developer,
+        SecretScreen::Reboot,
+        SecretScreen::Reset,
+        SecretScreen::BitrateOverride,
+        SecretScreen::Hdmi,
+        SecretScreen::Antenna,
+    ];
+
+    pub fn keys(self) -> &'static [Key] {
+        use Key::*;
+        match self {
+            SecretScreen::Platform => &[Home, Home, Home, Home, Home, Fwd, Play, Rev, Play, Fwd],
+            SecretScreen::Wireless => &[Home, Home, Home, Home, Home, Up, Down, Up, Down, Up],
+            SecretScreen::ChannelInfo => {
+                &[Home, Home, Home, Up, Up, Left, Right, Left, Right, Left]
+            }
+            SecretScreen::Developer => &[Home, Home, Home, Up, Up, Right, Left, Right, Left, Right],
+            SecretScreen::Reboot => &[Home, Home, Home, Home, Home, Up, Rev, Rev, Fwd, Fwd],
+            SecretScreen::Reset => &[Home, Home, Home, Home, Home, Fwd, Fwd, Fwd, Rev, Rev],
+            SecretScreen::BitrateOverride => {
+                &[Home, Home, Home, Home, Home, Rev, Rev, Rev, Fwd, Fwd]
+            }
+            SecretScreen::Hdmi => &[Home, Home, Home, Home, Home, Down, Left, Up, Up, Up],
+            SecretScreen::Antenna => &[Home, Home, Home, Home, Home, Fwd, Down, Rev, Down, Fwd],
+        }
+    }
+
+    pub fn requires_tv(self) -> bool {
+        matches!(self, SecretScreen::Hdmi | SecretScreen::Antenna)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SecretScreen::Platform => "platform",
+            SecretScreen::Wireless => "wireless",
+            SecretScreen::ChannelInfo => "channel-info",
+            SecretScreen::Developer => "developer",
+            SecretScreen::Reboot => "reboot",
+            SecretScreen::Reset => "reset",
+            SecretScreen::BitrateOverride => "bitrate-override",
+            SecretScreen::Hdmi => "hdmi",
+            SecretScreen::Antenna => "antenna",
+        }
+    }
+}
+
+impl fmt::Display for SecretScreen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for SecretScreen {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<SecretScreen> {
+        SecretScreen::ALL
+            .iter()
+            .copied()
+            .find(|screen| screen.name() == s.to_lowercase())
+            .ok_or_else(|| Error::Argument(format!("unknown secret screen {}", s)))
+    }
+}
+
+impl Device {
+    pub async fn open_secret_screen(&self, screen: SecretScreen) -> Result<()> {
+        if screen.requires_tv() {
+            self.require_class(DeviceClass::Tv, &format!("the {} secret screen", screen))
+                .await?;
+        }
+        let mut previous = None;
+        for key in screen.keys() {
+            match previous {
+                Some(Key::Home) if *key != Key::Home => sleep(HOME_SETTLE).await,
+                Some(_) => sleep(PRESS_INTERVAL).await,
+                None => {}
+            }
+            self.keypress(key).await?;
+            previous = Some(*key);
+        }
+        Ok(())
+    }
+}
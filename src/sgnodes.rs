@@ -0,0 +1,137 @@
+use crate::{consts, Capability, Device, Error, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use url::Url;
+use xml::reader::{EventReader, XmlEvent};
+
+const WRAPPERS: &[&str] = &["sgnodes", "All_Nodes", "Root_Nodes", "Nodes"];
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Node {
+    pub node_type: String,
+    pub id: Option<String>,
+    pub fields: BTreeMap<String, String>,
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    pub fn field_count(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Node> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+
+    pub fn find_by_id(&self, id: &str) -> Option<&Node> {
+        self.iter().find(|node| node.id.as_deref() == Some(id))
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct NodeTree {
+    pub roots: Vec<Node>,
+}
+
+impl NodeTree {
+    pub fn parse(xml: &str) -> Result<NodeTree> {
+        let mut roots = vec![];
+        let mut stack: Vec<Option<Node>> = vec![];
+        for event in EventReader::from_str(xml) {
+            match event.map_err(|err| Error::Response(format!("invalid sgnodes: {}", err)))? {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => {
+                    if stack.is_empty() || WRAPPERS.contains(&name.local_name.as_str()) {
+                        stack.push(None);
+                        continue;
+                    }
+                    let fields: BTreeMap<String, String> = attributes
+                        .into_iter()
+                        .map(|attribute| (attribute.name.local_name, attribute.value))
+                        .collect();
+                    stack.push(Some(Node {
+                        node_type: name.local_name,
+                        id: fields.get("name").filter(|id| !id.is_empty()).cloned(),
+                        fields,
+                        children: vec![],
+                    }));
+                }
+                XmlEvent::EndElement { .. } => {
+                    if let Some(Some(node)) = stack.pop() {
+                        match stack.iter_mut().rev().find_map(Option::as_mut) {
+                            Some(parent) => parent.children.push(node),
+                            None => roots.push(node),
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(NodeTree { roots })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Node> {
+        self.roots.iter().flat_map(Node::iter)
+    }
+
+    pub fn find_by_id(&self, id: &str) -> Option<&Node> {
+        self.iter().find(|node| node.id.as_deref() == Some(id))
+    }
+
+    pub fn count(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn count_by_type(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for node in self.iter() {
+            *counts.entry(node.node_type.clone()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    pub fn diff_counts(&self, later: &NodeTree) -> BTreeMap<String, isize> {
+        let mut diff: BTreeMap<String, isize> = BTreeMap::new();
+        for (node_type, count) in self.count_by_type() {
+            *diff.entry(node_type).or_insert(0) -= count as isize;
+        }
+        for (node_type, count) in later.count_by_type() {
+            *diff.entry(node_type).or_insert(0) += count as isize;
+        }
+        diff.retain(|_, delta| *delta != 0);
+        diff
+    }
+}
+
+impl Device {
+    pub async fn sgnodes_all(&self) -> Result<NodeTree> {
+        self.sgnodes(self.url.join(consts::QUERY_SGNODES_ALL)?)
+            .await
+    }
+
+    pub async fn sgnodes_roots(&self) -> Result<NodeTree> {
+        self.sgnodes(self.url.join(consts::QUERY_SGNODES_ROOTS)?)
+            .await
+    }
+
+    pub async fn sgnodes_node(&self, node_id: &str) -> Result<NodeTree> {
+        let mut url = self.url.join(consts::QUERY_SGNODES_NODES)?;
+        url.query_pairs_mut().append_pair("node-id", node_id);
+        self.sgnodes(url).await
+    }
+
+    async fn sgnodes(&self, url: Url) -> Result<NodeTree> {
+        let xml = self
+            .optional(Capability::SgNodes, async {
+                Ok(self.get(url).await?.text().await?)
+            })
+            .await?;
+        NodeTree::parse(&xml)
+    }
+}
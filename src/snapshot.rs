@@ -0,0 +1,132 @@
+use crate::{ActiveApp, App, Device, DeviceInfo, MediaPlayer, Result, Screensaver};
+use futures_util::try_join;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DeviceSnapshot {
+    pub info: DeviceInfo,
+    pub active_app: ActiveApp,
+    pub media_player: MediaPlayer,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "field", rename_all = "kebab-case")]
+pub enum FieldChange {
+    PowerMode {
+        old: String,
+        new: String,
+    },
+    UserDeviceName {
+        old: String,
+        new: String,
+    },
+    SoftwareVersion {
+        old: String,
+        new: String,
+    },
+    NetworkName {
+        old: String,
+        new: String,
+    },
+    HeadphonesConnected {
+        old: bool,
+        new: bool,
+    },
+    ActiveApp {
+        old: App,
+        new: App,
+    },
+    Screensaver {
+        old: Option<Screensaver>,
+        new: Option<Screensaver>,
+    },
+    PlaybackState {
+        old: String,
+        new: String,
+    },
+    Position {
+        old: Option<String>,
+        new: Option<String>,
+    },
+}
+
+impl DeviceSnapshot {
+    pub fn diff(&self, other: &DeviceSnapshot) -> Vec<FieldChange> {
+        let mut changes = vec![];
+        let (before, after) = (&self.info, &other.info);
+        push(
+            &mut changes,
+            &before.power_mode,
+            &after.power_mode,
+            |old, new| FieldChange::PowerMode { old, new },
+        );
+        push(
+            &mut changes,
+            &before.user_device_name,
+            &after.user_device_name,
+            |old, new| FieldChange::UserDeviceName { old, new },
+        );
+        push(
+            &mut changes,
+            &before.software_version,
+            &after.software_version,
+            |old, new| FieldChange::SoftwareVersion { old, new },
+        );
+        push(
+            &mut changes,
+            &before.network_name,
+            &after.network_name,
+            |old, new| FieldChange::NetworkName { old, new },
+        );
+        push(
+            &mut changes,
+            &before.headphones_connected,
+            &after.headphones_connected,
+            |old, new| FieldChange::HeadphonesConnected { old, new },
+        );
+        let (before, after) = (&self.active_app, &other.active_app);
+        push(&mut changes, &before.app, &after.app, |old, new| {
+            FieldChange::ActiveApp { old, new }
+        });
+        push(
+            &mut changes,
+            &before.screensaver,
+            &after.screensaver,
+            |old, new| FieldChange::Screensaver { old, new },
+        );
+        let (before, after) = (&self.media_player, &other.media_player);
+        push(&mut changes, &before.state, &after.state, |old, new| {
+            FieldChange::PlaybackState { old, new }
+        });
+        push(
+            &mut changes,
+            &before.position,
+            &after.position,
+            |old, new| FieldChange::Position { old, new },
+        );
+        changes
+    }
+}
+
+impl Device {
+    pub async fn snapshot(&self) -> Result<DeviceSnapshot> {
+        let (info, active_app, media_player) =
+            try_join!(self.device_info(), self.active_app(), self.media_player())?;
+        Ok(DeviceSnapshot {
+            info,
+            active_app,
+            media_player,
+        })
+    }
+}
+
+fn push<T: Clone + PartialEq>(
+    changes: &mut Vec<FieldChange>,
+    old: &T,
+    new: &T,
+    change: impl FnOnce(T, T) -> FieldChange,
+) {
+    if old != new {
+        changes.push(change(old.clone(), new.clone()));
+    }
+}
@@ -0,0 +1,140 @@
+use socket2::{Domain, Protocol, SockAddr, Socket, Type};
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+};
+use tokio::net::UdpSocket;
+
+pub(crate) const PORT: u16 = 1900;
+pub(crate) const MULTICAST_V4: Ipv4Addr = Ipv4Addr::new(239, 255, 255, 250);
+pub(crate) const MULTICAST_V6: Ipv6Addr = Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 0xc);
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Message {
+    start: String,
+    headers: Vec<(String, String)>,
+}
+
+impl Message {
+    pub(crate) fn parse(data: &[u8]) -> Option<Message> {
+        let text = std::str::from_utf8(data).ok()?;
+        let mut lines = text.lines();
+        let start = lines.next()?.trim().to_string();
+        let headers = lines
+            .take_while(|line| !line.trim().is_empty())
+            .filter_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                Some((name.trim().to_ascii_uppercase(), value.trim().to_string()))
+            })
+            .collect();
+        Some(Message { start, headers })
+    }
+
+    pub(crate) fn is_response(&self) -> bool {
+        let mut parts = self.start.split_whitespace();
+        matches!(
+            (parts.next(), parts.next()),
+            (Some(version), Some("200")) if version.starts_with("HTTP/1.")
+        )
+    }
+
+    pub(crate) fn is_notify(&self) -> bool {
+        self.start.starts_with("NOTIFY ")
+    }
+
+    pub(crate) fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub(crate) fn search_request(destination: &SocketAddr, target: &str, mx: usize) -> String {
+    format!(
+        "M-SEARCH * HTTP/1.1\r\nHOST: {}\r\nMAN: \"ssdp:discover\"\r\nMX: {}\r\nST: {}\r\n\r\n",
+        destination, mx, target
+    )
+}
+
+pub(crate) fn multicast_group(ipv6: bool) -> SocketAddr {
+    if ipv6 {
+        SocketAddr::V6(SocketAddrV6::new(MULTICAST_V6, PORT, 0, 0))
+    } else {
+        SocketAddr::V4(SocketAddrV4::new(MULTICAST_V4, PORT))
+    }
+}
+
+pub(crate) fn search_socket(
+    destination: &SocketAddr,
+    interface: Option<Ipv4Addr>,
+    ipv6_interface: u32,
+    ttl: u32,
+) -> io::Result<UdpSocket> {
+    let socket = match destination {
+        SocketAddr::V4(destination) => {
+            let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+            let local = interface.unwrap_or(Ipv4Addr::UNSPECIFIED);
+            if destination.ip().is_multicast() {
+                socket.set_multicast_if_v4(&local)?;
+                socket.set_multicast_ttl_v4(ttl)?;
+            }
+            socket.bind(&SockAddr::from(SocketAddrV4::new(local, 0)))?;
+            socket
+        }
+        SocketAddr::V6(destination) => {
+            let socket = Socket::new(Domain::IPV6, Type::DGRAM, Some(Protocol::UDP))?;
+            socket.set_only_v6(true)?;
+            if destination.ip().is_multicast() {
+                socket.set_multicast_if_v6(ipv6_interface)?;
+                socket.set_multicast_hops_v6(ttl)?;
+            }
+            socket.bind(&SockAddr::from(SocketAddrV6::new(
+                Ipv6Addr::UNSPECIFIED,
+                0,
+                0,
+                0,
+            )))?;
+            socket
+        }
+    };
+    socket.set_nonblocking(true)?;
+    UdpSocket::from_std(socket.into())
+}
+
+pub(crate) fn notify_socket(
+    group: &SocketAddr,
+    interface: Option<Ipv4Addr>,
+    ipv6_interface: u32,
+) -> io::Result<UdpSocket> {
+    let domain = match group {
+        SocketAddr::V4(_) => Domain::IPV4,
+        SocketAddr::V6(_) => Domain::IPV6,
+    };
+    let socket = Socket::new(domain, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_reuse_address(true)?;
+    #[cfg(all(unix, not(any(target_os = "solaris", target_os = "illumos"))))]
+    socket.set_reuse_port(true)?;
+    match group.ip() {
+        IpAddr::V4(ip) => {
+            let local = interface.unwrap_or(Ipv4Addr::UNSPECIFIED);
+            socket.join_multicast_v4(&ip, &local)?;
+            socket.bind(&SockAddr::from(SocketAddrV4::new(
+                Ipv4Addr::UNSPECIFIED,
+                group.port(),
+            )))?;
+        }
+        IpAddr::V6(ip) => {
+            socket.set_only_v6(true)?;
+            socket.join_multicast_v6(&ip, ipv6_interface)?;
+            socket.bind(&SockAddr::from(SocketAddrV6::new(
+                Ipv6Addr::UNSPECIFIED,
+                group.port(),
+                0,
+                0,
+            )))?;
+        }
+    }
+    socket.set_nonblocking(true)?;
+    UdpSocket::from_std(socket.into())
+}
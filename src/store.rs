@@ -0,0 +1,99 @@
+use crate::{App, Apps, Error, Result, RokuConfig};
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+const BASE_URL: &str = "https://channelstore.roku.com/api/v6/";
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ChannelInfo {
+    pub id: String,
+    pub name: String,
+    pub icon_url: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DetailsUnion {
+    details: Details,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Details {
+    id: Option<String>,
+    name: String,
+    icon_url: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    categories: Vec<Category>,
+}
+
+#[derive(Deserialize)]
+struct Category {
+    name: String,
+}
+
+pub struct ChannelStore {
+    client: Client,
+    base_url: Url,
+}
+
+impl Default for ChannelStore {
+    fn default() -> ChannelStore {
+        ChannelStore::with_base_url(Url::parse(BASE_URL).unwrap())
+    }
+}
+
+impl ChannelStore {
+    pub fn new() -> ChannelStore {
+        ChannelStore::default()
+    }
+
+    pub fn with_base_url(base_url: Url) -> ChannelStore {
+        ChannelStore {
+            client: RokuConfig::global_client(),
+            base_url,
+        }
+    }
+
+    pub async fn details(&self, id: &str) -> Result<Option<ChannelInfo>> {
+        let path = format!(
+            "channels/detailsunion/{}",
+            utf8_percent_encode(id, NON_ALPHANUMERIC)
+        );
+        let res = self.client.get(self.base_url.join(&path)?).send().await?;
+        if res.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let text = res.error_for_status()?.text().await?;
+        let union: DetailsUnion = serde_json::from_str(&text)
+            .map_err(|err| Error::Response(format!("invalid channel store response: {}", err)))?;
+        let details = union.details;
+        Ok(Some(ChannelInfo {
+            id: details.id.unwrap_or_else(|| id.to_string()),
+            name: details.name,
+            icon_url: details.icon_url,
+            description: details.description,
+            category: details
+                .categories
+                .into_iter()
+                .next()
+                .map(|category| category.name),
+        }))
+    }
+
+    pub async fn enrich(&self, apps: &Apps) -> Result<Vec<(App, Option<ChannelInfo>)>> {
+        let mut enriched = vec![];
+        for app in &apps.apps {
+            let info = match &app.id {
+                Some(id) => self.details(id).await?,
+                None => None,
+            };
+            enriched.push((app.clone(), info));
+        }
+        Ok(enriched)
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{
+    ActiveApp, App, Apps, Device, DeviceInfo, Discoverer, Error, Key, Media, MediaPlayer, Result,
+    RokuControl, Search,
+};
+use async_trait::async_trait;
+use std::{collections::VecDeque, sync::Mutex};
+
+pub use fake::FakeRoku;
+pub use fault::{Fault, FaultProxy};
+pub use record::{load, save, Exchange, Recorder, Replay};
+
+mod fake;
+mod fault;
+pub mod models;
+mod record;
+mod server;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Call {
+    Apps,
+    ActiveApp,
+    MediaPlayer,
+    DeviceInfo,
+    KeyDown(Key),
+    KeyUp(Key),
+    KeyPress(Key),
+    Launch(App),
+    Install(App),
+    Input(Vec<(String, String)>),
+    Search(Vec<(String, String)>),
+    Cast(Media),
+}
+
+#[derive(Default)]
+struct State {
+    apps: Option<Apps>,
+    active_app: Option<ActiveApp>,
+    media_player: Option<MediaPlayer>,
+    device_info: Option<DeviceInfo>,
+    errors: VecDeque<Error>,
+    calls: Vec<Call>,
+}
+
+#[derive(Default)]
+pub struct MockDevice {
+    state: Mutex<State>,
+}
+
+impl MockDevice {
+    pub fn new() -> MockDevice {
+        MockDevice::default()
+    }
+
+    pub fn set_apps(&self, apps: Apps) -> &MockDevice {
+        self.state.lock().unwrap().apps = Some(apps);
+        self
+    }
+
+    pub fn set_active_app(&self, active_app: ActiveApp) -> &MockDevice {
+        self.state.lock().unwrap().active_app = Some(active_app);
+        self
+    }
+
+    pub fn set_media_player(&self, media_player: MediaPlayer) -> &MockDevice {
+        self.state.lock().unwrap().media_player = Some(media_player);
+        self
+    }
+
+    pub fn set_device_info(&self, device_info: DeviceInfo) -> &MockDevice {
+        self.state.lock().unwrap().device_info = Some(device_info);
+        self
+    }
+
+    pub fn fail_next(&self, error: Error) -> &MockDevice {
+        self.state.lock().unwrap().errors.push_back(error);
+        self
+    }
+
+    pub fn calls(&self) -> Vec<Call> {
+        self.state.lock().unwrap().calls.clone()
+    }
+
+    pub fn clear_calls(&self) {
+        self.state.lock().unwrap().calls.clear();
+    }
+
+    fn record(&self, call: Call) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.calls.push(call);
+        match state.errors.pop_front() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    fn respond<T: Clone>(&self, call: Call, response: fn(&State) -> &Option<T>) -> Result<T> {
+        let endpoint = format!("{:?}", call);
+        self.record(call)?;
+        response(&self.state.lock().unwrap())
+            .clone()
+            .ok_or_else(|| Error::Response(format!("no mock response for {}", endpoint)))
+    }
+}
+
+#[async_trait]
+impl RokuControl for MockDevice {
+    async fn apps(&self) -> Result<Apps> {
+        self.respond(Call::Apps, |state| &state.apps)
+    }
+
+    async fn active_app(&self) -> Result<ActiveApp> {
+        self.respond(Call::ActiveApp, |state| &state.active_app)
+    }
+
+    async fn media_player(&self) -> Result<MediaPlayer> {
+        self.respond(Call::MediaPlayer, |state| &state.media_player)
+    }
+
+    async fn device_info(&self) -> Result<DeviceInfo> {
+        self.respond(Call::DeviceInfo, |state| &state.device_info)
+    }
+
+    async fn keydown(&self, key: &Key) -> Result<()> {
+        self.record(Call::KeyDown(*key))
+    }
+
+    async fn keyup(&self, key: &Key) -> Result<()> {
+        self.record(Call::KeyUp(*key))
+    }
+
+    async fn keypress(&self, key: &Key) -> Result<()> {
+        self.record(Call::KeyPress(*key))
+    }
+
+    async fn launch(&self, app: &App) -> Result<()> {
+        self.record(Call::Launch(app.clone()))
+    }
+
+    async fn install(&self, app: &App) -> Result<()> {
+        self.record(Call::Install(app.clone()))
+    }
+
+    async fn input(&self, input: &[(String, String)]) -> Result<()> {
+        self.record(Call::Input(input.to_vec()))
+    }
+
+    async fn search(&self, search: Search) -> Result<()> {
+        self.record(Call::Search(search.into_query_pairs()))
+    }
+
+    async fn cast(&self, media: &Media) -> Result<()> {
+        self.record(Call::Cast(media.clone()))
+    }
+}
+
+#[derive(Default)]
+struct DiscovererState {
+    results: VecDeque<Vec<Device>>,
+    last: Vec<Device>,
+    errors: VecDeque<Error>,
+    discoveries: usize,
+}
+
+#[derive(Default)]
+pub struct MockDiscoverer {
+    state: Mutex<DiscovererState>,
+}
+
+impl MockDiscoverer {
+    pub fn new() -> MockDiscoverer {
+        MockDiscoverer::default()
+    }
+
+    pub fn push(&self, devices: Vec<Device>) -> &MockDiscoverer {
+        self.state.lock().unwrap().results.push_back(devices);
+        self
+    }
+
+    pub fn fail_next(&self, error: Error) -> &MockDiscoverer {
+        self.state.lock().unwrap().errors.push_back(error);
+        self
+    }
+
+    pub fn discoveries(&self) -> usize {
+        self.state.lock().unwrap().discoveries
+    }
+}
+
+#[async_trait]
+impl Discoverer for MockDiscoverer {
+    async fn discover(&self) -> Result<Vec<Device>> {
+        let mut state = self.state.lock().unwrap();
+        state.discoveries += 1;
+        if let Some(error) = state.errors.pop_front() {
+            return Err(error);
+        }
+        if let Some(devices) = state.results.pop_front() {
+            state.last = devices;
+        }
+        Ok(state.last.clone())
+    }
+}
@@ -0,0 +1,419 @@
+use super::server::Server;
+use crate::{
+    consts::{APP_CHANNEL_STORE, APP_PLAY_ON_ROKU},
+    App, Device, TvChannel,
+};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use std::{
+    collections::BTreeMap,
+    io,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use url::Url;
+
+const DEVICE_INFO: &[(&str, &str)] = &[
+    ("udn", "29380000-0800-1025-80a4-d83134a7e5b6"),
+    ("serial-number", "X004000AAAAA"),
+    ("device-id", "S0A000AAAAAA"),
+    ("advertising-id", "2cb1ba7f-d66b-5c9e-8cbd-0e7e4c2f6c2d"),
+    ("vendor-name", "Roku"),
+    ("model-name", "Roku Express"),
+    ("model-number", "3900X"),
+    ("model-region", "US"),
+    ("is-tv", "false"),
+    ("is-stick", "false"),
+    ("supports-ethernet", "false"),
+    ("wifi-mac", "d8:31:34:a7:e5:b6"),
+    ("wifi-driver", "realtek"),
+    ("has-wifi-extender", "false"),
+    ("has-wifi-5G-support", "true"),
+    ("can-use-wifi-extender", "true"),
+    ("network-type", "wifi"),
+    ("network-name", "Fake"),
+    ("friendly-device-name", "Fake Roku"),
+    ("friendly-model-name", "Roku Express"),
+    ("default-device-name", "Roku Express - X004000AAAAA"),
+    ("user-device-name", "Fake Roku"),
+    ("user-device-location", "Test Bench"),
+    ("build-number", "AEA.00E04209A"),
+    ("software-version", "9.4.0"),
+    ("software-build", "4209"),
+    ("secure-device", "true"),
+    ("language", "en"),
+    ("country", "US"),
+    ("locale", "en_US"),
+    ("time-zone-auto", "true"),
+    ("time-zone", "US/Pacific"),
+    ("time-zone-name", "United States/Pacific"),
+    ("time-zone-tz", "America/Los_Angeles"),
+    ("time-zone-offset", "-480"),
+    ("clock-format", "12-hour"),
+    ("uptime", "1000"),
+    ("power-mode", "PowerOn"),
+    ("supports-suspend", "false"),
+    ("supports-find-remote", "false"),
+    ("find-remote-is-possible", "false"),
+    ("supports-audio-guide", "true"),
+    ("supports-rva", "true"),
+    ("developer-enabled", "false"),
+    ("keyed-developer-id", ""),
+    ("search-enabled", "true"),
+    ("search-channels-enabled", "true"),
+    ("voice-search-enabled", "true"),
+    ("notifications-enabled", "true"),
+    ("notifications-first-use", "false"),
+    ("supports-private-listening", "true"),
+    ("headphones-connected", "false"),
+    ("supports-ecs-textedit", "true"),
+    ("supports-ecs-microphone", "true"),
+    ("supports-wake-on-wlan", "false"),
+    ("has-play-on-roku", "true"),
+    ("has-mobile-screensaver", "false"),
+    ("support-url", "roku.com/support"),
+    ("grandcentral-version", "2.9.42"),
+    ("davinci-version", "2.8.20"),
+];
+
+const ICON: &[u8] = b"\x89PNG\r\n\x1a\nfake-icon";
+
+struct State {
+    apps: Vec<App>,
+    store_apps: Vec<App>,
+    pending_install: Option<App>,
+    active: Option<App>,
+    device_info: BTreeMap<String, String>,
+    player_state: String,
+    player_position: Option<u64>,
+    tv_channels: Vec<TvChannel>,
+    rendezvous_tracking: bool,
+    requests: Vec<String>,
+}
+
+pub struct FakeRoku {
+    server: Server,
+    state: Arc<Mutex<State>>,
+}
+
+impl FakeRoku {
+    pub async fn start() -> io::Result<FakeRoku> {
+        let state = Arc::new(Mutex::new(State {
+            apps: vec![
+                app("12", "Netflix", "4.2.81179053"),
+                app("837", "YouTube", "2.21.2"),
+                app("2213", "Roku Media Player", "5.3.9"),
+            ],
+            store_apps: vec![],
+            pending_install: None,
+            active: None,
+            device_info: DEVICE_INFO
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            player_state: "close".to_string(),
+            player_position: None,
+            tv_channels: vec![],
+            rendezvous_tracking: false,
+            requests: vec![],
+        }));
+        let handler_state = Arc::clone(&state);
+        let server = Server::start(move |req| {
+            let res = handle(&handler_state, req);
+            async move { res }
+        })?;
+        Ok(FakeRoku { server, state })
+    }
+
+    pub fn url(&self) -> &Url {
+        self.server.url()
+    }
+
+    pub fn device(&self) -> Device {
+        Device::new(self.url().clone())
+    }
+
+    pub fn set_apps(&self, apps: Vec<App>) {
+        self.state.lock().unwrap().apps = apps;
+    }
+
+    pub fn set_store_apps(&self, apps: Vec<App>) {
+        self.state.lock().unwrap().store_apps = apps;
+    }
+
+    pub fn set_active_app(&self, app: Option<App>) {
+        self.state.lock().unwrap().active = app;
+    }
+
+    pub fn active_app(&self) -> Option<App> {
+        self.state.lock().unwrap().active.clone()
+    }
+
+    pub fn set_device_info(&self, name: &str, value: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .device_info
+            .insert(name.to_string(), value.to_string());
+    }
+
+    pub fn set_player_state(&self, player_state: &str) {
+        self.state.lock().unwrap().player_state = player_state.to_string();
+    }
+
+    pub fn set_player_position(&self, position: Option<Duration>) {
+        self.state.lock().unwrap().player_position =
+            position.map(|position| position.as_millis() as u64);
+    }
+
+    pub fn set_tv_channels(&self, tv_channels: Vec<TvChannel>) {
+        self.state.lock().unwrap().tv_channels = tv_channels;
+    }
+
+    pub fn rendezvous_tracking(&self) -> bool {
+        self.state.lock().unwrap().rendezvous_tracking
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    pub fn clear_requests(&self) {
+        self.state.lock().unwrap().requests.clear();
+    }
+}
+
+fn app(id: &str, name: &str, version: &str) -> App {
+    App {
+        id: Some(id.to_string()),
+        name: name.to_string(),
+        version: Some(version.to_string()),
+    }
+}
+
+fn handle(state: &Mutex<State>, req: Request<Body>) -> Response<Body> {
+    let mut state = state.lock().unwrap();
+    let path = req.uri().path().trim_start_matches('/').to_string();
+    let request = match req.uri().query() {
+        Some(query) => format!("{} /{}?{}", req.method(), path, query),
+        None => format!("{} /{}", req.method(), path),
+    };
+    state.requests.push(request);
+    let mut segments = path.splitn(2, '/');
+    let (first, rest) = (segments.next().unwrap_or(""), segments.next());
+    match (req.method(), first, rest) {
+        (&Method::GET, "query", Some("apps")) => xml(render_apps(&state.apps)),
+        (&Method::GET, "query", Some("active-app")) => {
+            xml(render_active_app(state.active.as_ref()))
+        }
+        (&Method::GET, "query", Some("device-info")) => xml(render_device_info(&state.device_info)),
+        (&Method::GET, "query", Some("tv-channels")) => xml(render_tv_channels(&state.tv_channels)),
+        (&Method::GET, "query", Some(query)) if query.starts_with("icon/") => {
+            let id = query.trim_start_matches("icon/");
+            if state.apps.iter().any(|app| app.id.as_deref() == Some(id)) {
+                Response::builder()
+                    .header("Content-Type", "image/png")
+                    .body(Body::from(ICON))
+                    .unwrap()
+            } else {
+                empty(StatusCode::NOT_FOUND)
+            }
+        }
+        (&Method::GET, "query", Some("sgrendezvous")) => xml(format!(
+            "<sgrendezvous><tracking-enabled>{}</tracking-enabled><data/></sgrendezvous>",
+            state.rendezvous_tracking
+        )),
+        (&Method::POST, "sgrendezvous", Some(action)) => {
+            match action {
+                "track" => state.rendezvous_tracking = true,
+                "untrack" => state.rendezvous_tracking = false,
+                _ => return empty(StatusCode::NOT_FOUND),
+            }
+            empty(StatusCode::OK)
+        }
+        (&Method::GET, "query", Some("media-player")) => xml(match state.player_position {
+            Some(position) => format!(
+                "<player error=\"false\" state=\"{}\"><position>{} ms</position></player>",
+                escape(&state.player_state),
+                position
+            ),
+            None => format!(
+                "<player error=\"false\" state=\"{}\"/>",
+                escape(&state.player_state)
+            ),
+        }),
+        (&Method::POST, "keypress", Some(key)) => {
+            match key {
+                "Home" => state.active = None,
+                "Select" => {
+                    if let Some(app) = state.pending_install.take() {
+                        state.apps.push(app);
+                    }
+                }
+                "Back" | "Stop" => state.player_state = "close".to_string(),
+                "Pause" if state.player_state == "play" => {
+                    state.player_state = "pause".to_string();
+                }
+                "Play" => {
+                    state.player_state = match state.player_state.as_str() {
+                        "play" => "pause".to_string(),
+                        "pause" => "play".to_string(),
+                        other => other.to_string(),
+                    };
+                }
+                "PowerOff" => {
+                    state
+                        .device_info
+                        .insert("power-mode".to_string(), "DisplayOff".to_string());
+                }
+                "PowerOn" => {
+                    state
+                        .device_info
+                        .insert("power-mode".to_string(), "PowerOn".to_string());
+                }
+                "Power" => {
+                    let mode = match state.device_info.get("power-mode").map(String::as_str) {
+                        Some("PowerOn") => "DisplayOff",
+                        _ => "PowerOn",
+                    };
+                    state
+                        .device_info
+                        .insert("power-mode".to_string(), mode.to_string());
+                }
+                _ => {}
+            }
+            empty(StatusCode::OK)
+        }
+        (&Method::POST, "keydown", Some(_)) | (&Method::POST, "keyup", Some(_)) => {
+            empty(StatusCode::OK)
+        }
+        (&Method::GET, "exit-app", _) => empty(StatusCode::METHOD_NOT_ALLOWED),
+        (&Method::POST, "exit-app", Some(id)) => {
+            if state.active.as_ref().and_then(|app| app.id.as_deref()) == Some(id) {
+                state.active = None;
+            }
+            empty(StatusCode::OK)
+        }
+        (&Method::POST, "launch", Some(id)) => {
+            let app = state
+                .apps
+                .iter()
+                .find(|app| app.id.as_deref() == Some(id))
+                .cloned();
+            match app {
+                Some(app) => {
+                    state.active = Some(app);
+                    state.player_state = "close".to_string();
+                    empty(StatusCode::OK)
+                }
+                None => empty(StatusCode::NOT_FOUND),
+            }
+        }
+        (&Method::POST, "install", Some(id)) => {
+            let installed = state.apps.iter().any(|app| app.id.as_deref() == Some(id));
+            let available = state
+                .store_apps
+                .iter()
+                .find(|app| app.id.as_deref() == Some(id))
+                .cloned();
+            if !installed && available.is_none() {
+                return empty(StatusCode::NOT_FOUND);
+            }
+            state.active = Some(app(APP_CHANNEL_STORE, "Channel Store", "1.0.0"));
+            if !installed {
+                state.pending_install = available;
+            }
+            empty(StatusCode::OK)
+        }
+        (&Method::POST, "input", Some(APP_PLAY_ON_ROKU)) => {
+            state.player_state = "play".to_string();
+            empty(StatusCode::OK)
+        }
+        (&Method::POST, "input", _) | (&Method::POST, "search", _) => empty(StatusCode::OK),
+        _ => empty(StatusCode::NOT_FOUND),
+    }
+}
+
+fn xml(body: String) -> Response<Body> {
+    Response::builder()
+        .header("Content-Type", "text/xml; charset=\"utf-8\"")
+        .body(Body::from(format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" ?>\n{}\n",
+            body
+        )))
+        .unwrap()
+}
+
+fn empty(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn render_app(app: &App) -> String {
+    let mut attributes = String::new();
+    if let Some(id) = &app.id {
+        attributes.push_str(&format!(" id=\"{}\" type=\"appl\"", escape(id)));
+    }
+    if let Some(version) = &app.version {
+        attributes.push_str(&format!(" version=\"{}\"", escape(version)));
+    }
+    format!("<app{}>{}</app>", attributes, escape(&app.name))
+}
+
+fn render_apps(apps: &[App]) -> String {
+    let apps: String = apps.iter().map(render_app).collect();
+    format!("<apps>{}</apps>", apps)
+}
+
+fn render_active_app(app: Option<&App>) -> String {
+    let app = match app {
+        Some(app) => render_app(app),
+        None => "<app>Roku</app>".to_string(),
+    };
+    format!("<active-app>{}</active-app>", app)
+}
+
+fn render_device_info(device_info: &BTreeMap<String, String>) -> String {
+    let fields: String = device_info
+        .iter()
+        .map(|(name, value)| format!("<{0}>{1}</{0}>", name, escape(value)))
+        .collect();
+    format!("<device-info>{}</device-info>", fields)
+}
+
+fn render_tv_channels(tv_channels: &[TvChannel]) -> String {
+    let channels: String = tv_channels
+        .iter()
+        .map(|channel| {
+            let mut physical = String::new();
+            if let Some(number) = channel.physical_channel {
+                physical.push_str(&format!("<physical-channel>{}</physical-channel>", number));
+            }
+            if let Some(frequency) = channel.physical_frequency {
+                physical.push_str(&format!(
+                    "<physical-frequency>{}</physical-frequency>",
+                    frequency
+                ));
+            }
+            format!(
+                "<channel><number>{}</number><name>{}</name><type>{}</type><user-hidden>{}</user-hidden><user-favorite>{}</user-favorite>{}</channel>",
+                escape(&channel.number),
+                escape(&channel.name),
+                escape(&channel.channel_type),
+                channel.user_hidden,
+                channel.user_favorite,
+                physical
+            )
+        })
+        .collect();
+    format!("<tv-channels>{}</tv-channels>", channels)
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
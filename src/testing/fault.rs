@@ -0,0 +1,133 @@
+use super::{
+    record::{proxy, respond, status},
+    server::Server,
+};
+use crate::Device;
+use hyper::{Body, Request, Response, StatusCode};
+use reqwest::Client;
+use std::{
+    collections::VecDeque,
+    future, io,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::time::sleep;
+use url::Url;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Fault {
+    Latency(Duration),
+    Hang,
+    Status(u16),
+    MalformedXml,
+}
+
+#[derive(Default)]
+struct State {
+    latency: Duration,
+    queue: VecDeque<Fault>,
+    persistent: Option<Fault>,
+    injected: usize,
+}
+
+impl State {
+    fn next(&mut self) -> Option<Fault> {
+        let fault = self.queue.pop_front().or_else(|| self.persistent.clone());
+        if fault.is_some() {
+            self.injected += 1;
+        }
+        fault
+    }
+}
+
+pub struct FaultProxy {
+    server: Server,
+    state: Arc<Mutex<State>>,
+}
+
+impl FaultProxy {
+    pub async fn start(target: Url) -> io::Result<FaultProxy> {
+        let state = Arc::new(Mutex::new(State::default()));
+        let client = Client::new();
+        let handler_state = Arc::clone(&state);
+        let server = Server::start(move |req| {
+            handle(
+                client.clone(),
+                target.clone(),
+                Arc::clone(&handler_state),
+                req,
+            )
+        })?;
+        Ok(FaultProxy { server, state })
+    }
+
+    pub fn url(&self) -> &Url {
+        self.server.url()
+    }
+
+    pub fn device(&self) -> Device {
+        Device::new(self.url().clone())
+    }
+
+    pub fn set_latency(&self, latency: Duration) -> &FaultProxy {
+        self.state.lock().unwrap().latency = latency;
+        self
+    }
+
+    pub fn inject(&self, fault: Fault) -> &FaultProxy {
+        self.state.lock().unwrap().queue.push_back(fault);
+        self
+    }
+
+    pub fn set_fault(&self, fault: Option<Fault>) -> &FaultProxy {
+        self.state.lock().unwrap().persistent = fault;
+        self
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.latency = Duration::ZERO;
+        state.queue.clear();
+        state.persistent = None;
+    }
+
+    pub fn injected(&self) -> usize {
+        self.state.lock().unwrap().injected
+    }
+}
+
+async fn handle(
+    client: Client,
+    target: Url,
+    state: Arc<Mutex<State>>,
+    req: Request<Body>,
+) -> Response<Body> {
+    let (latency, fault) = {
+        let mut state = state.lock().unwrap();
+        (state.latency, state.next())
+    };
+    if !latency.is_zero() {
+        sleep(latency).await;
+    }
+    match fault {
+        Some(Fault::Latency(latency)) => sleep(latency).await,
+        Some(Fault::Hang) => future::pending::<()>().await,
+        Some(Fault::Status(code)) => {
+            return status(StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR))
+        }
+        Some(Fault::MalformedXml) | None => {}
+    }
+    let mut exchange = match proxy(&client, &target, req).await {
+        Ok(exchange) => exchange,
+        Err(code) => return status(code),
+    };
+    if fault == Some(Fault::MalformedXml) {
+        let mut end = exchange.body.len() / 2;
+        while !exchange.body.is_char_boundary(end) {
+            end -= 1;
+        }
+        exchange.body.truncate(end);
+        exchange.body.push_str("<<");
+    }
+    respond(&exchange)
+}
@@ -0,0 +1,165 @@
+use crate::{
+    ActiveApp, App, Apps, Buffering, DeviceInfo, Format, MediaPlayer, Plugin, Screensaver,
+};
+use std::time::Duration;
+
+pub fn app(id: &str, name: &str) -> App {
+    App {
+        id: Some(id.to_string()),
+        name: name.to_string(),
+        version: Some("1.0.0".to_string()),
+    }
+}
+
+pub fn apps(apps: &[(&str, &str)]) -> Apps {
+    Apps {
+        apps: apps.iter().map(|(id, name)| app(id, name)).collect(),
+    }
+}
+
+pub fn home() -> App {
+    App {
+        id: None,
+        name: "Roku".to_string(),
+        version: None,
+    }
+}
+
+pub fn active_app(app: Option<App>) -> ActiveApp {
+    ActiveApp {
+        app: app.unwrap_or_else(home),
+        screensaver: None,
+    }
+}
+
+pub fn screensaver(id: &str, name: &str) -> Screensaver {
+    Screensaver {
+        black: None,
+        id: id.to_string(),
+        name: name.to_string(),
+        screensaver_type: "ssvr".to_string(),
+        version: "1.0.0".to_string(),
+    }
+}
+
+pub fn media_player(state: &str) -> MediaPlayer {
+    MediaPlayer {
+        buffering: None,
+        duration: None,
+        error: false,
+        format: None,
+        is_live: None,
+        new_stream: None,
+        plugin: None,
+        position: None,
+        runtime: None,
+        state: state.to_string(),
+        stream_segment: None,
+    }
+}
+
+pub fn playing(app: &App, position: Duration, duration: Option<Duration>) -> MediaPlayer {
+    MediaPlayer {
+        buffering: Some(Buffering {
+            current: 1000,
+            max: 1000,
+            target: 0,
+        }),
+        duration: duration.map(millis),
+        format: Some(Format {
+            audio: "aac".to_string(),
+            captions: "none".to_string(),
+            container: "mp4".to_string(),
+            drm: "none".to_string(),
+            video: "avc".to_string(),
+            video_res: "1920x1080".to_string(),
+        }),
+        is_live: Some(duration.is_none()),
+        plugin: Some(Plugin {
+            bandwidth: "10000000 bps".to_string(),
+            id: app.id.clone().unwrap_or_default(),
+            name: app.name.clone(),
+        }),
+        position: Some(millis(position)),
+        runtime: duration.map(millis),
+        ..media_player("play")
+    }
+}
+
+pub fn device_info() -> DeviceInfo {
+    DeviceInfo {
+        advertising_id: "2cb1ba7f-d66b-5c9e-8cbd-0e7e4c2f6c2d".to_string(),
+        audio_guide_enabled: None,
+        av_sync_calibration_enabled: None,
+        build_number: "AEA.00E04209A".to_string(),
+        can_use_wifi_extender: true,
+        clock_format: "12-hour".to_string(),
+        country: "US".to_string(),
+        davinci_version: "2.8.20".to_string(),
+        default_device_name: "Roku Express - X004000AAAAA".to_string(),
+        developer_enabled: false,
+        device_id: "S0A000AAAAAA".to_string(),
+        ecp_setting_mode: None,
+        ethernet_mac: None,
+        find_remote_is_possible: false,
+        friendly_device_name: "Fake Roku".to_string(),
+        friendly_model_name: "Roku Express".to_string(),
+        grandcentral_version: "2.9.42".to_string(),
+        has_mobile_screensaver: false,
+        has_play_on_roku: true,
+        has_wifi_5g_support: true,
+        has_wifi_extender: false,
+        headphones_connected: false,
+        is_stick: false,
+        is_tv: false,
+        keyed_developer_id: String::new(),
+        language: "en".to_string(),
+        locale: "en_US".to_string(),
+        mobile_has_live_tv: None,
+        model_name: "Roku Express".to_string(),
+        model_number: "3900X".to_string(),
+        model_region: "US".to_string(),
+        network_name: "Fake".to_string(),
+        network_type: "wifi".to_string(),
+        notifications_enabled: true,
+        notifications_first_use: false,
+        power_mode: "PowerOn".to_string(),
+        search_channels_enabled: true,
+        search_enabled: true,
+        secure_device: true,
+        serial_number: "X004000AAAAA".to_string(),
+        software_build: "4209".to_string(),
+        software_version: "9.4.0".to_string(),
+        support_url: "roku.com/support".to_string(),
+        supports_airplay: None,
+        supports_audio_guide: true,
+        supports_ecs_microphone: true,
+        supports_ecs_textedit: true,
+        supports_ethernet: false,
+        supports_find_remote: false,
+        supports_private_listening: true,
+        supports_rva: true,
+        supports_suspend: false,
+        supports_wake_on_wlan: false,
+        time_zone: "US/Pacific".to_string(),
+        time_zone_auto: true,
+        time_zone_name: "United States/Pacific".to_string(),
+        time_zone_offset: -480,
+        time_zone_tz: "America/Los_Angeles".to_string(),
+        trc_channel_version: None,
+        trc_version: None,
+        udn: "29380000-0800-1025-80a4-d83134a7e5b6".to_string(),
+        ui_resolution: None,
+        uptime: 1000,
+        user_device_location: "Test Bench".to_string(),
+        user_device_name: "Fake Roku".to_string(),
+        vendor_name: "Roku".to_string(),
+        voice_search_enabled: true,
+        wifi_driver: "realtek".to_string(),
+        wifi_mac: "d8:31:34:a7:e5:b6".to_string(),
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{} ms", duration.as_millis())
+}
@@ -0,0 +1,215 @@
+use super::server::Server;
+use crate::Device;
+use hyper::{body, header::CONTENT_TYPE, Body, Request, Response, StatusCode};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use url::Url;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Exchange {
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+pub fn load(path: impl AsRef<Path>) -> io::Result<Vec<Exchange>> {
+    let text = fs::read_to_string(path)?;
+    serde_json::from_str(&text).map_err(io::Error::other)
+}
+
+pub fn save(path: impl AsRef<Path>, exchanges: &[Exchange]) -> io::Result<()> {
+    let text = serde_json::to_string_pretty(exchanges).map_err(io::Error::other)?;
+    fs::write(path, text)
+}
+
+pub struct Recorder {
+    server: Server,
+    exchanges: Arc<Mutex<Vec<Exchange>>>,
+}
+
+impl Recorder {
+    pub async fn start(target: Url) -> io::Result<Recorder> {
+        let exchanges = Arc::new(Mutex::new(vec![]));
+        let client = Client::new();
+        let handler_exchanges = Arc::clone(&exchanges);
+        let server = Server::start(move |req| {
+            forward(
+                client.clone(),
+                target.clone(),
+                Arc::clone(&handler_exchanges),
+                req,
+            )
+        })?;
+        Ok(Recorder { server, exchanges })
+    }
+
+    pub fn url(&self) -> &Url {
+        self.server.url()
+    }
+
+    pub fn device(&self) -> Device {
+        Device::new(self.url().clone())
+    }
+
+    pub fn exchanges(&self) -> Vec<Exchange> {
+        self.exchanges.lock().unwrap().clone()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        save(path, &self.exchanges())
+    }
+}
+
+pub struct Replay {
+    server: Server,
+    state: Arc<Mutex<ReplayState>>,
+}
+
+struct ReplayState {
+    exchanges: Vec<Exchange>,
+    served: Vec<bool>,
+}
+
+impl Replay {
+    pub async fn start(exchanges: Vec<Exchange>) -> io::Result<Replay> {
+        let state = Arc::new(Mutex::new(ReplayState {
+            served: vec![false; exchanges.len()],
+            exchanges,
+        }));
+        let handler_state = Arc::clone(&state);
+        let server = Server::start(move |req| {
+            let res = replay(&handler_state, req);
+            async move { res }
+        })?;
+        Ok(Replay { server, state })
+    }
+
+    pub async fn load(path: impl AsRef<Path>) -> io::Result<Replay> {
+        Replay::start(load(path)?).await
+    }
+
+    pub fn url(&self) -> &Url {
+        self.server.url()
+    }
+
+    pub fn device(&self) -> Device {
+        Device::new(self.url().clone())
+    }
+
+    pub fn remaining(&self) -> Vec<Exchange> {
+        let state = self.state.lock().unwrap();
+        state
+            .exchanges
+            .iter()
+            .zip(&state.served)
+            .filter(|(_, served)| !**served)
+            .map(|(exchange, _)| exchange.clone())
+            .collect()
+    }
+}
+
+fn request_path(req: &Request<Body>) -> String {
+    match req.uri().query() {
+        Some(query) => format!("{}?{}", req.uri().path(), query),
+        None => req.uri().path().to_string(),
+    }
+}
+
+async fn forward(
+    client: Client,
+    target: Url,
+    exchanges: Arc<Mutex<Vec<Exchange>>>,
+    req: Request<Body>,
+) -> Response<Body> {
+    match proxy(&client, &target, req).await {
+        Ok(exchange) => {
+            let res = respond(&exchange);
+            exchanges.lock().unwrap().push(exchange);
+            res
+        }
+        Err(code) => status(code),
+    }
+}
+
+pub(crate) async fn proxy(
+    client: &Client,
+    target: &Url,
+    req: Request<Body>,
+) -> std::result::Result<Exchange, StatusCode> {
+    let method = req.method().clone();
+    let path = request_path(&req);
+    let url = target
+        .join(path.trim_start_matches('/'))
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let body = body::to_bytes(req.into_body())
+        .await
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let res = client
+        .request(method.clone(), url)
+        .body(body)
+        .send()
+        .await
+        .map_err(|_| StatusCode::BAD_GATEWAY)?;
+    let code = res.status();
+    let content_type = res
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .map(String::from);
+    let body = res.text().await.map_err(|_| StatusCode::BAD_GATEWAY)?;
+    Ok(Exchange {
+        method: method.to_string(),
+        path,
+        status: code.as_u16(),
+        content_type,
+        body,
+    })
+}
+
+fn replay(state: &Mutex<ReplayState>, req: Request<Body>) -> Response<Body> {
+    let mut state = state.lock().unwrap();
+    let method = req.method().to_string();
+    let path = request_path(&req);
+    let matches: Vec<usize> = state
+        .exchanges
+        .iter()
+        .enumerate()
+        .filter(|(_, exchange)| exchange.method == method && exchange.path == path)
+        .map(|(index, _)| index)
+        .collect();
+    let index = matches
+        .iter()
+        .copied()
+        .find(|index| !state.served[*index])
+        .or_else(|| matches.last().copied());
+    match index {
+        Some(index) => {
+            state.served[index] = true;
+            respond(&state.exchanges[index])
+        }
+        None => status(StatusCode::NOT_FOUND),
+    }
+}
+
+pub(crate) fn respond(exchange: &Exchange) -> Response<Body> {
+    let mut res = Response::builder()
+        .status(StatusCode::from_u16(exchange.status).unwrap_or(StatusCode::BAD_GATEWAY));
+    if let Some(content_type) = &exchange.content_type {
+        res = res.header(CONTENT_TYPE, content_type.as_str());
+    }
+    res.body(Body::from(exchange.body.clone())).unwrap()
+}
+
+pub(crate) fn status(status: StatusCode) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .unwrap()
+}
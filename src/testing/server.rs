@@ -0,0 +1,64 @@
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Request, Response,
+};
+use std::{convert::Infallible, future::Future, io, net::TcpListener, sync::Arc};
+use tokio::{sync::oneshot, task::JoinHandle};
+use url::Url;
+
+pub(crate) struct Server {
+    url: Url,
+    shutdown: Option<oneshot::Sender<()>>,
+    task: JoinHandle<()>,
+}
+
+impl Server {
+    pub(crate) fn start<F, Fut>(handler: F) -> io::Result<Server>
+    where
+        F: Fn(Request<Body>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Response<Body>> + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        listener.set_nonblocking(true)?;
+        let url = Url::parse(&format!("http://{}/", listener.local_addr()?)).unwrap();
+        let handler = Arc::new(handler);
+        let make = make_service_fn(move |_| {
+            let handler = Arc::clone(&handler);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req| {
+                    let res = handler(req);
+                    async move { Ok::<_, Infallible>(res.await) }
+                }))
+            }
+        });
+        let server = hyper::Server::from_tcp(listener)
+            .map_err(io::Error::other)?
+            .serve(make);
+        let (shutdown, rx) = oneshot::channel::<()>();
+        let task = tokio::spawn(async move {
+            let _ = server
+                .with_graceful_shutdown(async {
+                    rx.await.ok();
+                })
+                .await;
+        });
+        Ok(Server {
+            url,
+            shutdown: Some(shutdown),
+            task,
+        })
+    }
+
+    pub(crate) fn url(&self) -> &Url {
+        &self.url
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        self.task.abort();
+    }
+}
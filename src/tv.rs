@@ -0,0 +1,273 @@
+use crate::{
+    consts, App, Device, DeviceClass, Error, Key, LaunchParams, Result, Script, Step, TvChannel,
+    TvChannelStatus, TvChannels,
+};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    iter::repeat_n,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::time::{sleep, Instant};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChannelScan {
+    script: Script,
+    interval: Duration,
+    stable_polls: u32,
+    min_duration: Duration,
+    timeout: Duration,
+}
+
+impl Default for ChannelScan {
+    fn default() -> ChannelScan {
+        ChannelScan {
+            script: setup_script(),
+            interval: Duration::from_secs(10),
+            stable_polls: 6,
+            min_duration: Duration::from_secs(60),
+            timeout: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
+impl ChannelScan {
+    pub fn new() -> ChannelScan {
+        ChannelScan::default()
+    }
+
+    pub fn script(&mut self, script: Script) -> &mut Self {
+        self.script = script;
+        self
+    }
+
+    pub fn interval(&mut self, interval: Duration) -> &mut Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn stable_polls(&mut self, stable_polls: u32) -> &mut Self {
+        self.stable_polls = stable_polls;
+        self
+    }
+
+    pub fn min_duration(&mut self, min_duration: Duration) -> &mut Self {
+        self.min_duration = min_duration;
+        self
+    }
+
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub async fn run(&self, device: &Device) -> Result<TvChannels> {
+        device
+            .require_class(DeviceClass::Tv, "a channel scan")
+            .await?;
+        let start = Instant::now();
+        self.script.run(device).await?;
+        let mut last = None;
+        let mut stable = 0;
+        loop {
+            sleep(self.interval).await;
+            let channels = device.tv_channels().await?;
+            let count = channels.channels.len();
+            if last == Some(count) {
+                stable += 1;
+            } else {
+                last = Some(count);
+                stable = 0;
+            }
+            if stable >= self.stable_polls && start.elapsed() >= self.min_duration {
+                return Ok(channels);
+            }
+            if start.elapsed() >= self.timeout {
+                return Err(Error::Response(format!(
+                    "channel scan did not settle after {}s ({} channels)",
+                    self.timeout.as_secs(),
+                    count
+                )));
+            }
+        }
+    }
+}
+
+// Home > Settings > TV inputs > Live TV > Scan again for channels > Start finding channels
+fn setup_script() -> Script {
+    let pause = Step::Sleep(Duration::from_millis(750));
+    let mut steps = vec![
+        Step::Key(Key::Home),
+        Step::Sleep(Duration::from_millis(1500)),
+    ];
+    steps.extend(repeat_n(Step::Key(Key::Down), 10));
+    steps.extend([Step::Key(Key::Select), pause.clone()]);
+    steps.extend([Step::Key(Key::Down), Step::Key(Key::Select), pause.clone()]);
+    for _ in 0..2 {
+        steps.extend([Step::Key(Key::Select), pause.clone()]);
+    }
+    steps.push(Step::Key(Key::Select));
+    Script::new(steps)
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct LineupDiff {
+    pub added: Vec<TvChannel>,
+    pub removed: Vec<TvChannel>,
+    pub changed: Vec<(TvChannel, TvChannel)>,
+}
+
+impl LineupDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl TvChannels {
+    pub fn diff(&self, later: &TvChannels) -> LineupDiff {
+        let before: BTreeMap<_, _> = self
+            .channels
+            .iter()
+            .map(|channel| (&channel.number, channel))
+            .collect();
+        let after: BTreeMap<_, _> = later
+            .channels
+            .iter()
+            .map(|channel| (&channel.number, channel))
+            .collect();
+        let mut diff = LineupDiff::default();
+        for (number, channel) in &after {
+            match before.get(number) {
+                None => diff.added.push((*channel).clone()),
+                Some(previous) if previous != channel => {
+                    diff.changed.push(((*previous).clone(), (*channel).clone()))
+                }
+                Some(_) => {}
+            }
+        }
+        for (number, channel) in &before {
+            if !after.contains_key(number) {
+                diff.removed.push((*channel).clone());
+            }
+        }
+        diff
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Program {
+    pub title: String,
+    pub description: Option<String>,
+    pub ratings: Vec<String>,
+    pub has_captions: bool,
+    pub audio_format: Option<String>,
+    pub audio_languages: Vec<String>,
+    pub start: Option<SystemTime>,
+    pub duration: Option<Duration>,
+}
+
+impl Program {
+    pub fn end(&self) -> Option<SystemTime> {
+        Some(self.start? + self.duration?)
+    }
+
+    pub fn elapsed(&self, now: SystemTime) -> Option<Duration> {
+        now.duration_since(self.start?).ok()
+    }
+
+    pub fn remaining(&self, now: SystemTime) -> Option<Duration> {
+        self.end()?.duration_since(now).ok()
+    }
+
+    pub fn is_on(&self, now: SystemTime) -> bool {
+        match (self.start, self.end()) {
+            (Some(start), Some(end)) => start <= now && now < end,
+            _ => true,
+        }
+    }
+}
+
+impl TvChannelStatus {
+    pub fn resolution(&self) -> Option<&str> {
+        self.signal_mode.as_deref()
+    }
+
+    pub fn program(&self) -> Option<Program> {
+        let title = self.program_title.as_deref()?.trim();
+        if title.is_empty() {
+            return None;
+        }
+        Some(Program {
+            title: title.to_string(),
+            description: self
+                .program_description
+                .as_deref()
+                .map(str::trim)
+                .filter(|description| !description.is_empty())
+                .map(str::to_string),
+            ratings: list(self.program_ratings.as_deref()),
+            has_captions: self.program_has_cc.unwrap_or(false),
+            audio_format: self
+                .program_audio_format
+                .as_deref()
+                .map(str::trim)
+                .filter(|format| !format.is_empty())
+                .map(str::to_string),
+            audio_languages: list(self.program_audio_languages.as_deref()),
+            start: self
+                .program_start_time
+                .map(|start| UNIX_EPOCH + Duration::from_secs(start)),
+            duration: self.program_duration.map(Duration::from_secs),
+        })
+    }
+}
+
+impl Device {
+    pub async fn pause_live_tv(&self) -> Result<()> {
+        self.set_live_tv_state("pause").await
+    }
+
+    pub async fn resume_live_tv(&self) -> Result<()> {
+        self.set_live_tv_state("play").await
+    }
+
+    pub async fn tune(&self, channel: &str) -> Result<()> {
+        self.require_class(DeviceClass::Tv, "Live TV").await?;
+        let app = App {
+            id: Some(consts::APP_LIVE_TV.to_string()),
+            name: String::new(),
+            version: None,
+        };
+        let mut params = LaunchParams::new();
+        params.param("ch".to_string(), channel.to_string());
+        self.launch_with(&app, params).await
+    }
+
+    async fn set_live_tv_state(&self, state: &str) -> Result<()> {
+        self.require_class(DeviceClass::Tv, "Live TV").await?;
+        let active = self.active_app().await?;
+        if active.app.id.as_deref() != Some(consts::APP_LIVE_TV) {
+            return Err(Error::Argument(format!(
+                "live TV is not active ({})",
+                active.app.name
+            )));
+        }
+        self.set_playback(state).await.map_err(|err| match err {
+            Error::Response(message) => Error::Response(format!(
+                "{}, is a USB drive set up for Live TV Pause?",
+                message
+            )),
+            err => err,
+        })
+    }
+}
+
+fn list(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
@@ -0,0 +1,67 @@
+use crate::{consts, App};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AppId(&'static str);
+
+impl AppId {
+    pub const fn new(id: &'static str) -> AppId {
+        AppId(id)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        self.0
+    }
+}
+
+impl fmt::Display for AppId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for AppId {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl From<AppId> for App {
+    fn from(id: AppId) -> App {
+        App {
+            id: Some(id.0.to_string()),
+            name: String::new(),
+            version: None,
+        }
+    }
+}
+
+impl PartialEq<AppId> for App {
+    fn eq(&self, id: &AppId) -> bool {
+        self.id.as_deref() == Some(id.0)
+    }
+}
+
+pub const CHANNEL_STORE: AppId = AppId(consts::APP_CHANNEL_STORE);
+pub const DEV: AppId = AppId(consts::APP_DEV);
+pub const LIVE_TV: AppId = AppId(consts::APP_LIVE_TV);
+pub const MEDIA_PLAYER: AppId = AppId(consts::APP_MEDIA_PLAYER);
+pub const PLAY_ON_ROKU: AppId = AppId(consts::APP_PLAY_ON_ROKU);
+
+pub const APPLE_TV: AppId = AppId("551012");
+pub const DISNEY_PLUS: AppId = AppId("291097");
+pub const HULU: AppId = AppId("2285");
+pub const MAX: AppId = AppId("61322");
+pub const NETFLIX: AppId = AppId(consts::APP_NETFLIX);
+pub const PANDORA: AppId = AppId("28");
+pub const PARAMOUNT_PLUS: AppId = AppId("31440");
+pub const PEACOCK: AppId = AppId("593099");
+pub const PLEX: AppId = AppId("13535");
+pub const PLUTO_TV: AppId = AppId("74519");
+pub const PRIME_VIDEO: AppId = AppId("13");
+pub const ROKU_CHANNEL: AppId = AppId("151908");
+pub const SLING: AppId = AppId("46041");
+pub const SPOTIFY: AppId = AppId("22297");
+pub const TUBI: AppId = AppId("41468");
+pub const YOUTUBE: AppId = AppId(consts::APP_YOUTUBE);
+pub const YOUTUBE_TV: AppId = AppId("195316");
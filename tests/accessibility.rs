@@ -0,0 +1,40 @@
+use roku::{testing::FakeRoku, Error, Key, Shortcut};
+use std::{sync::Arc, time::Duration};
+
+#[tokio::test]
+async fn audio_guide_verified() {
+    let fake = Arc::new(FakeRoku::start().await.unwrap());
+    let device = fake.device();
+    assert_eq!(device.audio_guide_enabled().await.unwrap(), None);
+    assert!(matches!(
+        device.set_audio_guide(true).await,
+        Err(Error::Argument(_))
+    ));
+
+    fake.set_device_info("audio-guide-enabled", "true");
+    assert!(!device.set_audio_guide(true).await.unwrap());
+
+    fake.set_device_info("audio-guide-enabled", "false");
+    fake.clear_requests();
+    let toggler = fake.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        toggler.set_device_info("audio-guide-enabled", "true");
+    });
+    assert!(device.set_audio_guide(true).await.unwrap());
+    let presses = fake
+        .requests()
+        .iter()
+        .filter(|request| *request == "POST /keypress/Info")
+        .count();
+    assert_eq!(presses, 4);
+}
+
+#[tokio::test]
+async fn custom_shortcut() {
+    let fake = FakeRoku::start().await.unwrap();
+    let mut shortcut = Shortcut::new(Key::Home, 5);
+    shortcut.interval = Duration::from_millis(5);
+    fake.device().shortcut(&shortcut).await.unwrap();
+    assert_eq!(fake.requests(), vec!["POST /keypress/Home"; 5]);
+}
@@ -0,0 +1,76 @@
+use roku::{testing::FakeRoku, CachePolicy, Endpoint, MemoryCache};
+use std::{sync::Arc, time::Duration};
+
+fn count(fake: &FakeRoku, request: &str) -> usize {
+    fake.requests()
+        .iter()
+        .filter(|sent| sent.as_str() == request)
+        .count()
+}
+
+#[tokio::test]
+async fn caches_until_invalidated() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device().with_memory_cache();
+    for _ in 0..3 {
+        device.device_info().await.unwrap();
+        device.apps().await.unwrap();
+        device.active_app().await.unwrap();
+    }
+    assert_eq!(count(&fake, "GET /query/device-info"), 1);
+    assert_eq!(count(&fake, "GET /query/apps"), 1);
+    assert_eq!(count(&fake, "GET /query/active-app"), 3);
+
+    device.invalidate(Endpoint::Apps);
+    device.apps().await.unwrap();
+    device.device_info().await.unwrap();
+    assert_eq!(count(&fake, "GET /query/apps"), 2);
+    assert_eq!(count(&fake, "GET /query/device-info"), 1);
+}
+
+#[tokio::test]
+async fn per_endpoint_ttl() {
+    let fake = FakeRoku::start().await.unwrap();
+    let mut policy = CachePolicy::new();
+    policy
+        .ttl(Endpoint::DeviceInfo, Duration::from_millis(50))
+        .ttl(Endpoint::Apps, Duration::ZERO);
+    let device = fake
+        .device()
+        .with_cache(Arc::new(MemoryCache::new()), policy);
+    device.device_info().await.unwrap();
+    device.device_info().await.unwrap();
+    device.apps().await.unwrap();
+    device.apps().await.unwrap();
+    assert_eq!(count(&fake, "GET /query/device-info"), 1);
+    assert_eq!(count(&fake, "GET /query/apps"), 2);
+
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    device.device_info().await.unwrap();
+    assert_eq!(count(&fake, "GET /query/device-info"), 2);
+}
+
+#[tokio::test]
+async fn caches_icons_by_app() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device().with_memory_cache();
+    let icon = device.icon("12").await.unwrap();
+    assert_eq!(device.icon("12").await.unwrap(), icon);
+    assert_eq!(icon.content_type.as_deref(), Some("image/png"));
+    device.icon("837").await.unwrap();
+    device.icon("837").await.unwrap();
+    assert_eq!(count(&fake, "GET /query/icon/12"), 1);
+    assert_eq!(count(&fake, "GET /query/icon/837"), 1);
+
+    device.invalidate_icon("12");
+    device.icon("12").await.unwrap();
+    device.icon("837").await.unwrap();
+    assert_eq!(count(&fake, "GET /query/icon/12"), 2);
+    assert_eq!(count(&fake, "GET /query/icon/837"), 1);
+
+    device.invalidate(Endpoint::Icons);
+    device.icon("12").await.unwrap();
+    device.icon("837").await.unwrap();
+    assert_eq!(count(&fake, "GET /query/icon/12"), 3);
+    assert_eq!(count(&fake, "GET /query/icon/837"), 2);
+}
@@ -0,0 +1,60 @@
+use roku::{
+    testing::{models, FakeRoku},
+    Capability, Error,
+};
+
+#[tokio::test]
+async fn probes_once() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+    assert!(device.capabilities().is_none());
+    let capabilities = device.capabilities_probe().await.unwrap();
+    assert!(capabilities.supports(Capability::MediaPlayer));
+    assert!(capabilities.supports(Capability::TvChannels));
+    assert!(capabilities.supports(Capability::ExitApp));
+    assert!(!capabilities.supports(Capability::FwBeacons));
+    assert_eq!(Capability::SgNodes.to_string(), "sgnodes");
+    assert_eq!(Capability::ExitApp.to_string(), "exit-app");
+
+    fake.clear_requests();
+    let clone = device.clone();
+    assert!(!clone.supports(Capability::TvActiveChannel).await.unwrap());
+    assert!(clone.supports(Capability::ActiveApp).await.unwrap());
+    assert!(fake.requests().is_empty());
+}
+
+#[tokio::test]
+async fn optional_endpoints() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+    assert!(matches!(
+        device.chanperf().await,
+        Err(Error::NotSupported(_))
+    ));
+    assert!(matches!(
+        device.sgnodes_roots().await,
+        Err(Error::NotSupported(_))
+    ));
+
+    device.capabilities_probe().await.unwrap();
+    fake.clear_requests();
+    assert!(matches!(
+        device.fwbeacons().await,
+        Err(Error::NotSupported(_))
+    ));
+    assert!(matches!(
+        device.track_fwbeacons().await,
+        Err(Error::NotSupported(_))
+    ));
+    assert!(fake.requests().is_empty());
+}
+
+#[tokio::test]
+async fn exit_app() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_active_app(Some(models::app("12", "Netflix")));
+    let device = fake.device();
+    device.exit_app("12").await.unwrap();
+    assert_eq!(fake.requests(), vec!["POST /exit-app/12"]);
+    assert!(device.active_app().await.unwrap().app.id.is_none());
+}
@@ -0,0 +1,44 @@
+use roku::{testing::FakeRoku, App, CaptionToggle};
+use std::time::Duration;
+
+fn keypresses(fake: &FakeRoku) -> Vec<String> {
+    fake.requests()
+        .into_iter()
+        .filter_map(|request| {
+            request
+                .strip_prefix("POST /keypress/")
+                .map(|key| key.to_string())
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn toggle_with_channel_offset() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_player_state("play");
+    fake.set_active_app(Some(App {
+        id: Some("12".to_string()),
+        name: "Netflix".to_string(),
+        version: None,
+    }));
+    let mut toggle = CaptionToggle::new();
+    toggle
+        .delay(Duration::ZERO)
+        .offset(1)
+        .channel_offset("12".to_string(), 3);
+    toggle.run(&fake.device()).await.unwrap();
+    assert_eq!(
+        keypresses(&fake),
+        vec!["Info", "Down", "Down", "Down", "Right", "Info"]
+    );
+}
+
+#[tokio::test]
+async fn requires_playback() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_player_state("stop");
+    let mut toggle = CaptionToggle::new();
+    toggle.delay(Duration::ZERO);
+    assert!(toggle.run(&fake.device()).await.is_err());
+    assert!(keypresses(&fake).is_empty());
+}
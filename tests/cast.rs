@@ -0,0 +1,256 @@
+use futures_util::StreamExt;
+use reqwest::{header::RANGE, StatusCode};
+use roku::{testing::FakeRoku, FileServer, Media, MediaType, Order, Slide, Slideshow};
+use std::{env, fs, time::Duration};
+use url::Url;
+
+#[tokio::test]
+async fn cast_file() {
+    let dir = env::temp_dir().join(format!("roku-cast-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("holiday.mp4");
+    fs::write(&path, b"0123456789").unwrap();
+
+    let fake = FakeRoku::start().await.unwrap();
+    let session = fake.device().cast_file(&path).await.unwrap();
+    let server = session.file_server().unwrap();
+    assert_eq!(server.url().path(), "/holiday.mp4");
+
+    let requests = fake.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("POST /input/15985?"));
+    assert!(requests[0].contains("t=v"));
+    assert!(requests[0].contains("videoName=holiday"));
+    assert!(requests[0].contains("videoFormat=mp4"));
+
+    let client = reqwest::Client::new();
+    let res = client.get(server.url().clone()).send().await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()["content-type"], "video/mp4");
+    assert_eq!(&res.bytes().await.unwrap()[..], b"0123456789");
+
+    let res = client
+        .get(server.url().clone())
+        .header(RANGE, "bytes=2-5")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(&res.bytes().await.unwrap()[..], b"2345");
+
+    let res = client
+        .get(server.url().clone())
+        .header(RANGE, "bytes=100-50")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(res.headers()["content-range"], "bytes */10");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn unsatisfiable_ranges() {
+    let dir = env::temp_dir().join(format!("roku-range-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("clip.mp4");
+    fs::write(&path, b"0123456789").unwrap();
+
+    let fake = FakeRoku::start().await.unwrap();
+    let server = FileServer::start(&path, fake.url(), 0).await.unwrap();
+    let client = reqwest::Client::new();
+    for range in &["bytes=5-2", "bytes=10-", "bytes=20-30"] {
+        let res = client
+            .get(server.url().clone())
+            .header(RANGE, *range)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::RANGE_NOT_SATISFIABLE, "{}", range);
+        assert_eq!(res.headers()["content-range"], "bytes */10");
+        assert!(res.bytes().await.unwrap().is_empty());
+    }
+    let res = client
+        .get(server.url().clone())
+        .header(RANGE, "bytes=8-100")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(res.headers()["content-range"], "bytes 8-9/10");
+    assert_eq!(&res.bytes().await.unwrap()[..], b"89");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn cast_missing_file() {
+    let fake = FakeRoku::start().await.unwrap();
+    assert!(fake.device().cast_file("missing.mp4").await.is_err());
+    assert!(fake.requests().is_empty());
+}
+
+#[tokio::test]
+async fn cast_media_player() {
+    let fake = FakeRoku::start().await.unwrap();
+    let mut media =
+        Media::from_url(Url::parse("http://nas.local/music/song.mp3").unwrap()).unwrap();
+    media
+        .title("Song".to_string())
+        .artwork(Url::parse("http://nas.local/music/cover.jpg").unwrap());
+    fake.device().cast_media_player(&media).await.unwrap();
+    let requests = fake.requests();
+    assert_eq!(requests.len(), 1);
+    assert!(requests[0].starts_with("POST /launch/2213?"));
+    assert!(requests[0].contains("t=a"));
+    assert!(requests[0].contains("songname=Song"));
+    assert!(requests[0].contains("albumarturl=http%3A%2F%2Fnas.local%2Fmusic%2Fcover.jpg"));
+}
+
+#[tokio::test]
+async fn cast_by_mime() {
+    assert_eq!(
+        MediaType::from_mime("Video/MP4; codecs=avc1"),
+        Some(MediaType::Video)
+    );
+    assert_eq!(
+        MediaType::from_mime("application/vnd.apple.mpegurl"),
+        Some(MediaType::Video)
+    );
+    assert_eq!(MediaType::from_mime("audio/flac"), Some(MediaType::Audio));
+    assert_eq!(MediaType::from_mime("image/webp"), Some(MediaType::Photo));
+    assert_eq!(MediaType::from_mime("text/html"), None);
+
+    let fake = FakeRoku::start().await.unwrap();
+    let url = Url::parse("http://nas.local/stream?id=7").unwrap();
+    assert!(Media::from_url(url.clone()).is_err());
+    let media = Media::from_mime(url, "application/x-mpegURL").unwrap();
+    assert_eq!(media.media_type(), MediaType::Video);
+    fake.device().cast(&media).await.unwrap();
+    let requests = fake.requests();
+    assert!(requests[0].starts_with("POST /input/15985?"));
+    assert!(requests[0].contains("t=v"));
+    assert!(requests[0].contains("videoFormat=hls"));
+
+    let song = Media::from_mime(Url::parse("http://nas.local/a").unwrap(), "audio/mpeg").unwrap();
+    fake.clear_requests();
+    fake.device().cast(&song).await.unwrap();
+    assert!(fake.requests()[0].contains("songformat=mp3"));
+}
+
+#[tokio::test]
+async fn cast_session_controls() {
+    let fake = FakeRoku::start().await.unwrap();
+    let media = Media::from_url(Url::parse("http://nas.local/movie.mp4").unwrap()).unwrap();
+    let session = fake.device().cast(&media).await.unwrap();
+    assert_eq!(session.status().await.unwrap().state, "play");
+
+    session.pause().await.unwrap();
+    assert_eq!(session.status().await.unwrap().state, "pause");
+    session.resume().await.unwrap();
+    session.resume().await.unwrap();
+    assert_eq!(session.status().await.unwrap().state, "play");
+
+    fake.set_player_position(Some(Duration::from_secs(30)));
+    assert_eq!(
+        session.position().await.unwrap(),
+        Some(Duration::from_secs(30))
+    );
+    fake.clear_requests();
+    session.seek(Duration::from_secs(31)).await.unwrap();
+    assert!(fake
+        .requests()
+        .iter()
+        .all(|request| request.starts_with("GET ")));
+
+    let mut status = session.watch(Duration::from_millis(10));
+    assert_eq!(status.next().await.unwrap().unwrap().state, "play");
+    session.stop().await.unwrap();
+    assert_eq!(status.next().await.unwrap().unwrap().state, "close");
+    assert!(status.next().await.is_none());
+}
+
+fn slides() -> Vec<Url> {
+    ["a.jpg", "b.png", "c"]
+        .iter()
+        .map(|name| {
+            Url::parse("http://photos.example/")
+                .unwrap()
+                .join(name)
+                .unwrap()
+        })
+        .collect()
+}
+
+fn casts(fake: &FakeRoku) -> Vec<String> {
+    fake.requests()
+        .into_iter()
+        .filter(|request| request.starts_with("POST /input/15985?"))
+        .collect()
+}
+
+#[tokio::test]
+async fn slideshow_sequential() {
+    let fake = FakeRoku::start().await.unwrap();
+    let handle = Slideshow::new(slides())
+        .duration(Duration::from_millis(50))
+        .start(&fake.device())
+        .unwrap();
+    handle.finished().await.unwrap();
+
+    let casts = casts(&fake);
+    assert_eq!(casts.len(), 3);
+    assert!(casts.iter().all(|cast| cast.contains("t=p")));
+    assert!(casts[0].contains("a.jpg"));
+    assert!(casts[1].contains("b.png"));
+    assert!(casts[2].contains("photos.example%2Fc"));
+}
+
+#[test]
+fn slideshow_order() {
+    let mut slideshow = Slideshow::new(slides());
+    slideshow.order(Order::Reverse);
+    let names: Vec<_> = slideshow
+        .slides()
+        .into_iter()
+        .map(|slide| match slide {
+            Slide::Url(url) => url.path().to_string(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(names, vec!["/c", "/b.png", "/a.jpg"]);
+
+    slideshow.order(Order::Shuffle(7));
+    let shuffled = slideshow.slides();
+    assert_eq!(shuffled, slideshow.slides());
+    assert!(slides()
+        .into_iter()
+        .all(|url| shuffled.contains(&&Slide::Url(url))));
+}
+
+#[tokio::test]
+async fn slideshow_controls() {
+    let fake = FakeRoku::start().await.unwrap();
+    let handle = Slideshow::new(slides())
+        .duration(Duration::from_secs(60))
+        .start(&fake.device())
+        .unwrap();
+    while handle.current().is_none() {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    handle.pause().await;
+    handle.skip().await;
+    while handle.current() != Some(1) {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    handle.previous().await;
+    while casts(&fake).len() < 3 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(handle.current(), Some(0));
+    handle.stop().await.unwrap();
+
+    assert_eq!(casts(&fake).len(), 3);
+    assert_eq!(fake.requests().last().unwrap(), "POST /keypress/Back");
+}
@@ -0,0 +1,38 @@
+#![allow(dead_code)]
+
+use serde::de::DeserializeOwned;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub fn fixture_dir(kind: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(kind)
+}
+
+pub fn fixture_paths(kind: &str) -> Vec<PathBuf> {
+    let dir = fixture_dir(kind);
+    let mut paths: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension() == Some("xml".as_ref()))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "no fixtures in {}", dir.display());
+    paths
+}
+
+pub fn parse<T: DeserializeOwned>(kind: &str, path: &Path) -> T {
+    let text = fs::read_to_string(path).unwrap();
+    match serde_xml_rs::from_str(&text) {
+        Ok(value) => value,
+        Err(err) => panic!(
+            "{}/{}: {}",
+            kind,
+            path.file_name().unwrap().to_string_lossy(),
+            err
+        ),
+    }
+}
@@ -0,0 +1,77 @@
+use roku::{
+    testing::{FakeRoku, Fault, FaultProxy},
+    Device, Error, Key, RetryPolicy, RokuConfig,
+};
+use std::time::{Duration, Instant};
+use url::Url;
+
+#[tokio::test]
+async fn retries_unavailable() {
+    let fake = FakeRoku::start().await.unwrap();
+    let proxy = FaultProxy::start(fake.url().clone()).await.unwrap();
+    proxy.inject(Fault::Status(503));
+    assert!(proxy.device().apps().await.is_err());
+
+    proxy.inject(Fault::Status(503)).inject(Fault::Status(503));
+    let device = RokuConfig::new()
+        .retry(RetryPolicy::new(3, Duration::from_millis(5)))
+        .device(proxy.url().clone())
+        .unwrap();
+    assert!(device.apps().await.is_ok());
+    assert_eq!(proxy.injected(), 3);
+
+    proxy.inject(Fault::Status(404));
+    assert!(device.apps().await.is_err());
+    assert_eq!(proxy.injected(), 4);
+}
+
+#[tokio::test]
+async fn paces_keys() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = RokuConfig::new()
+        .pacing(Duration::from_millis(50))
+        .device(fake.url().clone())
+        .unwrap();
+    let started = Instant::now();
+    for _ in 0..3 {
+        device.keypress(&Key::Down).await.unwrap();
+    }
+    assert!(started.elapsed() >= Duration::from_millis(100));
+    assert_eq!(fake.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn inherits_global() {
+    let fake = FakeRoku::start().await.unwrap();
+    let proxy = FaultProxy::start(fake.url().clone()).await.unwrap();
+    RokuConfig::set_global(
+        RokuConfig::new()
+            .retry(RetryPolicy::new(2, Duration::ZERO))
+            .clone(),
+    )
+    .unwrap();
+    let device = Device::new(proxy.url().clone());
+    RokuConfig::set_global(RokuConfig::new()).unwrap();
+    proxy.inject(Fault::Status(503));
+    assert!(device.device_info().await.is_ok());
+    assert_eq!(RokuConfig::global().retry_policy(), RetryPolicy::none());
+}
+
+#[test]
+fn invalid_client() {
+    let url = Url::parse("http://192.168.1.20:8060/").unwrap();
+    let mut config = RokuConfig::new();
+    assert!(config.device(url.clone()).is_ok());
+    config.user_agent("roku\nrs");
+    assert!(matches!(config.device(url), Err(Error::Request(_))));
+}
+
+#[test]
+fn invalid_global() {
+    let mut config = RokuConfig::new();
+    config.user_agent("roku\nrs");
+    assert!(matches!(
+        RokuConfig::set_global(config),
+        Err(Error::Request(_))
+    ));
+}
@@ -0,0 +1,57 @@
+use roku::{
+    content::{ContentSearch, Provider},
+    testing::{models, Exchange, FakeRoku, Replay},
+};
+
+const RESPONSE: &str = r#"{"view":[{"content":{"title":"Stranger Things","type":"series","meta":{"id":"a1b2"},"releaseYear":2016,"viewOptions":[{"channelId":"12","providerName":"Netflix","license":"Subscription","media":{"contentId":"80057281","mediaType":"series"}},{"channelId":"2285","providerName":"Hulu","license":"Subscription"}]}}]}"#;
+
+async fn search() -> (Replay, ContentSearch) {
+    let replay = Replay::start(vec![Exchange {
+        method: "GET".to_string(),
+        path: "/search?query=stranger+things".to_string(),
+        status: 200,
+        content_type: Some("application/json".to_string()),
+        body: RESPONSE.to_string(),
+    }])
+    .await
+    .unwrap();
+    let search = ContentSearch::with_base_url(replay.url().clone());
+    (replay, search)
+}
+
+#[tokio::test]
+async fn typed_results() {
+    let (_replay, search) = search().await;
+    let results = search.search("stranger things").await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].title, "Stranger Things");
+    assert_eq!(results[0].content_type, "series");
+    assert_eq!(results[0].release_year, Some(2016));
+    assert_eq!(
+        results[0].providers[0],
+        Provider {
+            app_id: "12".to_string(),
+            name: Some("Netflix".to_string()),
+            license: Some("Subscription".to_string()),
+            content_id: Some("80057281".to_string()),
+            media_type: Some("series".to_string()),
+        }
+    );
+    assert_eq!(results[0].providers[1].content_id, None);
+}
+
+#[tokio::test]
+async fn search_and_play() {
+    let (_replay, search) = search().await;
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_apps(vec![models::app("12", "Netflix")]);
+    let result = search
+        .search_and_play(&fake.device(), "stranger things")
+        .await
+        .unwrap();
+    assert_eq!(result.title, "Stranger Things");
+    assert_eq!(
+        fake.requests().last().unwrap(),
+        "POST /launch/12?contentId=80057281&mediaType=series"
+    );
+}
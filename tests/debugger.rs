@@ -0,0 +1,155 @@
+use roku::debugger::{Breakpoint, Debugger, StepType, StopReason, Update, Value, Variable};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+const MAGIC: u64 = 0x0067_7562_6564_7362;
+
+fn string(data: &mut Vec<u8>, value: &str) {
+    data.extend(value.as_bytes());
+    data.push(0);
+}
+
+fn packet(id: u32, payload: &[u8]) -> Vec<u8> {
+    let mut packet = ((payload.len() + 12) as u32).to_le_bytes().to_vec();
+    packet.extend(id.to_le_bytes());
+    packet.extend(0u32.to_le_bytes());
+    packet.extend(payload);
+    packet
+}
+
+async fn handshake(stream: &mut TcpStream) {
+    assert_eq!(stream.read_u64_le().await.unwrap(), MAGIC);
+    let mut response = MAGIC.to_le_bytes().to_vec();
+    for part in [3u32, 1, 0, 12] {
+        response.extend(part.to_le_bytes());
+    }
+    response.extend(0i64.to_le_bytes());
+    stream.write_all(&response).await.unwrap();
+}
+
+async fn request(stream: &mut TcpStream) -> (u32, u32, Vec<u8>) {
+    let length = stream.read_u32_le().await.unwrap();
+    let id = stream.read_u32_le().await.unwrap();
+    let command = stream.read_u32_le().await.unwrap();
+    let mut data = vec![0; length as usize - 12];
+    stream.read_exact(&mut data).await.unwrap();
+    (id, command, data)
+}
+
+async fn serve(listener: TcpListener) {
+    let (mut stream, _) = listener.accept().await.unwrap();
+    handshake(&mut stream).await;
+
+    let mut update = 2u32.to_le_bytes().to_vec();
+    update.extend(0i32.to_le_bytes());
+    update.push(4);
+    string(&mut update, "");
+    stream.write_all(&packet(0, &update)).await.unwrap();
+
+    let (id, command, _) = request(&mut stream).await;
+    assert_eq!(command, 3);
+    let mut threads = 1u32.to_le_bytes().to_vec();
+    threads.push(1);
+    threads.push(4);
+    string(&mut threads, "");
+    threads.extend(12u32.to_le_bytes());
+    string(&mut threads, "main");
+    string(&mut threads, "pkg:/source/main.brs");
+    string(&mut threads, "stop");
+    stream.write_all(&packet(id, &threads)).await.unwrap();
+
+    let (id, command, data) = request(&mut stream).await;
+    assert_eq!(command, 7);
+    let mut expected = 1u32.to_le_bytes().to_vec();
+    string(&mut expected, "pkg:/source/main.brs");
+    expected.extend(20u32.to_le_bytes());
+    expected.extend(0u32.to_le_bytes());
+    assert_eq!(data, expected);
+    let mut breakpoints = 1u32.to_le_bytes().to_vec();
+    breakpoints.extend(7u32.to_le_bytes());
+    breakpoints.extend(0u32.to_le_bytes());
+    breakpoints.extend(0u32.to_le_bytes());
+    stream.write_all(&packet(id, &breakpoints)).await.unwrap();
+
+    let (id, command, _) = request(&mut stream).await;
+    assert_eq!(command, 5);
+    let mut variables = 2u32.to_le_bytes().to_vec();
+    variables.push(0x08 | 0x20);
+    variables.push(7);
+    string(&mut variables, "count");
+    variables.extend(42i32.to_le_bytes());
+    variables.push(0x08 | 0x10 | 0x04);
+    variables.push(1);
+    string(&mut variables, "m");
+    variables.extend(1u32.to_le_bytes());
+    variables.push(13);
+    variables.extend(3u32.to_le_bytes());
+    stream.write_all(&packet(id, &variables)).await.unwrap();
+
+    let (id, command, _) = request(&mut stream).await;
+    assert_eq!(command, 6);
+    let mut error = 16u32.to_le_bytes().to_vec();
+    error.extend(id.to_le_bytes());
+    error.extend(4u32.to_le_bytes());
+    error.extend(0u32.to_le_bytes());
+    stream.write_all(&error).await.unwrap();
+}
+
+#[tokio::test]
+async fn session() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = tokio::spawn(serve(listener));
+
+    let debugger = Debugger::attach_to("127.0.0.1", port).await.unwrap();
+    assert_eq!(debugger.version().major, 3);
+    assert_eq!(
+        debugger.next_update().await,
+        Some(Update::AllThreadsStopped {
+            primary_thread: 0,
+            stop_reason: StopReason::Break,
+            detail: String::new(),
+        })
+    );
+
+    let threads = debugger.threads().await.unwrap();
+    assert_eq!(threads.len(), 1);
+    assert!(threads[0].primary);
+    assert_eq!(threads[0].line_number, 12);
+    assert_eq!(threads[0].file_name, "pkg:/source/main.brs");
+
+    let added = debugger
+        .add_breakpoints(&[Breakpoint::new("pkg:/source/main.brs".to_string(), 20)])
+        .await
+        .unwrap();
+    assert_eq!(added[0].id, 7);
+
+    let variables = debugger.variables(0, 0, &[], false).await.unwrap();
+    assert_eq!(
+        variables,
+        vec![
+            Variable {
+                name: Some("count".to_string()),
+                child_key: false,
+                constant: false,
+                ref_count: None,
+                value: Value::Integer(42),
+            },
+            Variable {
+                name: Some("m".to_string()),
+                child_key: false,
+                constant: false,
+                ref_count: Some(1),
+                value: Value::Container {
+                    container_type: "roAssociativeArray".to_string(),
+                    element_count: 3,
+                },
+            },
+        ]
+    );
+
+    assert!(debugger.step(0, StepType::Over).await.is_err());
+    server.await.unwrap();
+}
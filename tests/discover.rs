@@ -0,0 +1,47 @@
+use roku::{DeviceIdentity, Discoverer, SsdpDiscoverer};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+#[tokio::test]
+async fn unicast_search() {
+    let responder = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let destination = responder.local_addr().unwrap();
+    let task = tokio::spawn(async move {
+        let mut buf = vec![0; 2048];
+        let (len, from) = responder.recv_from(&mut buf).await.unwrap();
+        let request = String::from_utf8_lossy(&buf[..len]).into_owned();
+        let response = "HTTP/1.1 200 OK\r\n\
+            Cache-Control: max-age=3600\r\n\
+            ST: roku:ecp\r\n\
+            Location: http://192.168.1.134:8060/\r\n\
+            USN: uuid:roku:ecp:X004000AAAAA\r\n\r\n";
+        let broken = "HTTP/1.1 200 OK\r\n\
+            ST: roku:ecp\r\n\
+            Location: 192.168.1.135\r\n\r\n";
+        let other = "HTTP/1.1 200 OK\r\n\
+            ST: upnp:rootdevice\r\n\
+            Location: http://192.168.1.1:5000/\r\n\r\n";
+        for message in &[broken, response, other, response] {
+            responder.send_to(message.as_bytes(), from).await.unwrap();
+        }
+        request
+    });
+
+    let devices = SsdpDiscoverer::new()
+        .destination(destination)
+        .timeout(Duration::from_millis(500))
+        .discover()
+        .await
+        .unwrap();
+    let request = task.await.unwrap();
+    assert!(request.starts_with("M-SEARCH * HTTP/1.1\r\n"));
+    assert!(request.contains("ST: roku:ecp\r\n"));
+    assert!(request.contains("MAN: \"ssdp:discover\"\r\n"));
+
+    assert_eq!(devices.len(), 1);
+    assert_eq!(devices[0].url().as_str(), "http://192.168.1.134:8060/");
+    assert_eq!(
+        devices[0].identity().await.unwrap(),
+        DeviceIdentity::new("X004000AAAAA")
+    );
+}
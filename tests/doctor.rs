@@ -0,0 +1,66 @@
+use roku::{
+    doctor, doctor_with,
+    testing::{FakeRoku, MockDiscoverer},
+    CheckStatus, Device, Error, Report, RokuConfig,
+};
+use std::sync::Arc;
+
+fn status(report: &Report, name: &str) -> CheckStatus {
+    report
+        .checks
+        .iter()
+        .find(|check| check.name == name)
+        .unwrap_or_else(|| panic!("missing {} check", name))
+        .status
+}
+
+#[tokio::test]
+async fn healthy_device() {
+    let fake = FakeRoku::start().await.unwrap();
+    let report = doctor(Some(&fake.device())).await;
+    assert_eq!(status(&report, "port"), CheckStatus::Pass);
+    assert_eq!(status(&report, "ecp"), CheckStatus::Pass);
+    assert_eq!(status(&report, "firmware"), CheckStatus::Pass);
+}
+
+#[tokio::test]
+async fn limited_mode_and_old_firmware() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_device_info("ecp-setting-mode", "limited");
+    fake.set_device_info("software-version", "8.2.0");
+    let report = doctor(Some(&fake.device())).await;
+    assert!(!report.is_ok());
+    assert_eq!(status(&report, "ecp"), CheckStatus::Fail);
+    assert_eq!(status(&report, "firmware"), CheckStatus::Warn);
+}
+
+#[tokio::test]
+async fn injected_discoverer() {
+    let fake = FakeRoku::start().await.unwrap();
+    let discoverer = MockDiscoverer::new();
+    discoverer.push(vec![fake.device()]);
+    let report = doctor_with(&discoverer, None).await;
+    assert!(report.is_ok());
+    assert_eq!(status(&report, "multicast"), CheckStatus::Pass);
+    assert_eq!(status(&report, "ecp"), CheckStatus::Pass);
+
+    discoverer.fail_next(Error::Argument("no network".to_string()));
+    let report = doctor_with(&discoverer, None).await;
+    assert_eq!(status(&report, "multicast"), CheckStatus::Fail);
+    assert_eq!(discoverer.discoveries(), 2);
+}
+
+#[tokio::test]
+async fn global_discoverer() {
+    let fake = FakeRoku::start().await.unwrap();
+    let discoverer = Arc::new(MockDiscoverer::new());
+    discoverer.push(vec![fake.device()]);
+    RokuConfig::set_global(RokuConfig::new().discovery(discoverer.clone()).clone()).unwrap();
+    let devices = Device::discover().await;
+    let report = doctor(None).await;
+    RokuConfig::set_global(RokuConfig::new()).unwrap();
+
+    assert_eq!(devices.unwrap()[0].url(), fake.device().url());
+    assert_eq!(status(&report, "multicast"), CheckStatus::Pass);
+    assert_eq!(discoverer.discoveries(), 2);
+}
@@ -0,0 +1,92 @@
+use futures_util::StreamExt;
+use roku::{
+    testing::{models, FakeRoku},
+    Event, EventOptions, Overflow,
+};
+use std::time::Duration;
+use tokio::time::sleep;
+
+const INTERVAL: Duration = Duration::from_millis(10);
+
+async fn switch_apps(fake: &FakeRoku) {
+    for (id, name) in [("12", "Netflix"), ("13", "Prime Video"), ("2285", "Hulu")] {
+        fake.set_active_app(Some(models::app(id, name)));
+        sleep(INTERVAL * 5).await;
+    }
+}
+
+#[tokio::test]
+async fn drop_oldest() {
+    let fake = FakeRoku::start().await.unwrap();
+    let mut events = EventOptions::new()
+        .interval(INTERVAL)
+        .capacity(2)
+        .overflow(Overflow::DropOldest)
+        .watch(&fake.device());
+    switch_apps(&fake).await;
+    assert_eq!(events.pending(), 2);
+    assert!(events.dropped() >= 3);
+    match events.next().await.unwrap().unwrap() {
+        Event::AppChanged { current, .. } => assert_eq!(current.name, "Prime Video"),
+        event => panic!("unexpected {:?}", event),
+    }
+}
+
+#[tokio::test]
+async fn coalesce() {
+    let fake = FakeRoku::start().await.unwrap();
+    let mut events = EventOptions::new()
+        .interval(INTERVAL)
+        .capacity(3)
+        .overflow(Overflow::Coalesce)
+        .watch(&fake.device());
+    switch_apps(&fake).await;
+    assert_eq!(events.pending(), 3);
+    assert_eq!(events.dropped(), 0);
+    let mut apps = vec![];
+    for _ in 0..3 {
+        if let Event::AppChanged { previous, current } = events.next().await.unwrap().unwrap() {
+            apps.push((previous, current.name));
+        }
+    }
+    assert_eq!(apps, vec![(None, "Hulu".to_string())]);
+}
+
+#[tokio::test]
+async fn coalesce_below_capacity() {
+    let fake = FakeRoku::start().await.unwrap();
+    let mut events = EventOptions::new()
+        .interval(INTERVAL)
+        .overflow(Overflow::Coalesce)
+        .watch(&fake.device());
+    switch_apps(&fake).await;
+    let pending = events.pending();
+    let mut apps = vec![];
+    for _ in 0..pending {
+        if let Event::AppChanged { current, .. } = events.next().await.unwrap().unwrap() {
+            apps.push(current.name);
+        }
+    }
+    assert_eq!(events.dropped(), 0);
+    assert_eq!(apps, vec!["Netflix", "Prime Video", "Hulu"]);
+}
+
+#[tokio::test]
+async fn block() {
+    let fake = FakeRoku::start().await.unwrap();
+    let mut events = EventOptions::new()
+        .interval(INTERVAL)
+        .capacity(1)
+        .watch(&fake.device());
+    switch_apps(&fake).await;
+    assert_eq!(events.pending(), 1);
+    assert_eq!(events.dropped(), 0);
+    assert!(matches!(
+        events.next().await.unwrap().unwrap(),
+        Event::PowerChanged { .. }
+    ));
+    assert!(matches!(
+        events.next().await.unwrap().unwrap(),
+        Event::AppChanged { .. }
+    ));
+}
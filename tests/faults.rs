@@ -0,0 +1,72 @@
+use roku::{
+    testing::{FakeRoku, Fault, FaultProxy},
+    App, EcpError, Error,
+};
+use std::time::Duration;
+use tokio::time::{timeout, Instant};
+
+async fn proxy() -> (FakeRoku, FaultProxy) {
+    let fake = FakeRoku::start().await.unwrap();
+    let proxy = FaultProxy::start(fake.url().clone()).await.unwrap();
+    (fake, proxy)
+}
+
+#[tokio::test]
+async fn server_error() {
+    let (_fake, proxy) = proxy().await;
+    proxy.inject(Fault::Status(503));
+    let err = proxy.device().apps().await.unwrap_err();
+    assert!(
+        matches!(err, Error::Ecp(err) if err.code == 503 && err.message == "Service Unavailable")
+    );
+    assert!(proxy.device().apps().await.is_ok());
+    assert_eq!(proxy.injected(), 1);
+}
+
+#[tokio::test]
+async fn malformed_xml() {
+    let (_fake, proxy) = proxy().await;
+    proxy.inject(Fault::MalformedXml);
+    let err = proxy.device().device_info().await.unwrap_err();
+    assert!(matches!(err, Error::XMLParse(_)));
+}
+
+#[tokio::test]
+async fn hang() {
+    let (_fake, proxy) = proxy().await;
+    proxy.set_fault(Some(Fault::Hang));
+    let res = timeout(Duration::from_millis(200), proxy.device().active_app()).await;
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn latency() {
+    let (fake, proxy) = proxy().await;
+    proxy.set_latency(Duration::from_millis(100));
+    proxy.inject(Fault::Latency(Duration::from_millis(100)));
+    let start = Instant::now();
+    proxy.device().active_app().await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(200));
+    assert_eq!(fake.requests(), vec!["GET /query/active-app"]);
+}
+
+#[tokio::test]
+async fn ecp_error_body() {
+    let fake = FakeRoku::start().await.unwrap();
+    let app = App {
+        id: Some("999".to_string()),
+        name: "Missing".to_string(),
+        version: None,
+    };
+    let err = fake.device().launch(&app).await.unwrap_err();
+    assert!(matches!(err, Error::Ecp(err) if err.code == 404 && err.message == "Not Found"));
+
+    let err = EcpError::parse(
+        400,
+        "<response><status>FAILED</status><error>Invalid key</error></response>",
+    );
+    assert_eq!(err.message, "Invalid key");
+    let err = EcpError::parse(403, "ECP command not allowed\n");
+    assert_eq!(err.message, "ECP command not allowed");
+    assert_eq!(err.to_string(), "403 ECP command not allowed");
+}
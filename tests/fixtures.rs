@@ -0,0 +1,263 @@
+use roku::{
+    ActiveApp, Apps, BeaconEvent, ChanPerf, DeviceClass, DeviceInfo, FwBeacons, MediaPlayer,
+    PluginRegistry, R2d2Bitmaps, SgRendezvous, TvActiveChannel, TvChannels,
+};
+use serde::de::DeserializeOwned;
+use std::time::{Duration, UNIX_EPOCH};
+
+mod common;
+
+fn fixtures<T: DeserializeOwned>(kind: &str) -> Vec<(String, T)> {
+    common::fixture_paths(kind)
+        .into_iter()
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            (name, common::parse(kind, &path))
+        })
+        .collect()
+}
+
+fn fixture<T: DeserializeOwned>(kind: &str, name: &str) -> T {
+    fixtures(kind)
+        .into_iter()
+        .find(|(fixture, _)| fixture == name)
+        .unwrap_or_else(|| panic!("missing fixture {}/{}", kind, name))
+        .1
+}
+
+#[test]
+fn device_info_fixtures_parse() {
+    for (name, info) in fixtures::<DeviceInfo>("device-info") {
+        assert!(!info.serial_number.is_empty(), "{}", name);
+        assert!(!info.software_version.is_empty(), "{}", name);
+    }
+}
+
+#[test]
+fn device_info_roku_tv() {
+    let info: DeviceInfo = fixture("device-info", "tcl-roku-tv-55r617-9.2.0.xml");
+    assert!(info.is_tv);
+    assert_eq!(info.vendor_name, "TCL");
+    assert_eq!(info.friendly_model_name, "TCL•Roku TV");
+    assert_eq!(info.power_mode, "DisplayOff");
+    assert_eq!(info.time_zone_offset, -240);
+}
+
+#[test]
+fn device_info_ethernet() {
+    let info: DeviceInfo = fixture("device-info", "roku-ultra-4800x-10.0.0.xml");
+    assert_eq!(info.network_type, "ethernet");
+    assert_eq!(info.ethernet_mac.as_deref(), Some("b8:a1:75:c3:f2:1f"));
+    assert!(info.developer_enabled);
+}
+
+#[test]
+fn device_info_newer_os_fields() {
+    let info: DeviceInfo = fixture("device-info", "roku-ultra-4850x-11.5.0.xml");
+    assert_eq!(info.supports_airplay, Some(true));
+    assert_eq!(info.mobile_has_live_tv, Some(true));
+    assert_eq!(info.ui_resolution.as_deref(), Some("1080p"));
+    assert_eq!(info.av_sync_calibration_enabled.as_deref(), Some("3.0"));
+    assert_eq!(info.trc_channel_version.as_deref(), Some("7.2.31"));
+
+    let info: DeviceInfo = fixture("device-info", "roku-express-3900x-9.4.0.xml");
+    assert_eq!(info.trc_version.as_deref(), Some("3.0"));
+    assert_eq!(info.supports_airplay, None);
+}
+
+#[test]
+fn device_info_soundbar() {
+    let info: DeviceInfo = fixture("device-info", "roku-streambar-9102x-11.0.0.xml");
+    assert_eq!(DeviceClass::from_info(&info), DeviceClass::Audio);
+    assert!(!info.headphones_connected);
+    assert!(!info.supports_private_listening);
+    assert!(info.davinci_version.is_empty());
+}
+
+#[test]
+fn apps_fixtures_parse() {
+    for (name, apps) in fixtures::<Apps>("apps") {
+        for app in &apps.apps {
+            assert!(app.id.is_some(), "{}", name);
+        }
+    }
+}
+
+#[test]
+fn apps_entities() {
+    let apps: Apps = fixture("apps", "roku-express-3900x-9.4.0.xml");
+    assert_eq!(apps.apps.len(), 7);
+    assert_eq!(apps.apps[0].name, "FandangoNOW Movies & TV");
+}
+
+#[test]
+fn active_app_fixtures_parse() {
+    fixtures::<ActiveApp>("active-app");
+}
+
+#[test]
+fn active_app_home() {
+    let active: ActiveApp = fixture("active-app", "home.xml");
+    assert_eq!(active.app.id, None);
+    assert_eq!(active.app.name, "Roku");
+    assert_eq!(active.screensaver, None);
+}
+
+#[test]
+fn active_app_screensaver() {
+    let active: ActiveApp = fixture("active-app", "screensaver.xml");
+    let screensaver = active.screensaver.unwrap();
+    assert_eq!(screensaver.id, "5533");
+    assert_eq!(screensaver.name, "Roku Digital Clock");
+}
+
+#[test]
+fn media_player_fixtures_parse() {
+    for (name, player) in fixtures::<MediaPlayer>("media-player") {
+        assert!(!player.error, "{}", name);
+    }
+}
+
+#[test]
+fn media_player_playing() {
+    let player: MediaPlayer = fixture("media-player", "play-netflix.xml");
+    assert_eq!(player.state, "play");
+    assert_eq!(player.plugin.unwrap().id, "12");
+    assert_eq!(player.format.unwrap().video_res, "3840x2160");
+    assert_eq!(player.position.as_deref(), Some("1234567 ms"));
+    assert_eq!(player.is_live, Some(false));
+}
+
+#[test]
+fn chanperf_running() {
+    let perf: ChanPerf = fixture("chanperf", "dev.xml");
+    assert_eq!(perf.status, "OK");
+    let plugin = perf.plugin.unwrap();
+    assert_eq!(plugin.id.as_deref(), Some("dev"));
+    assert_eq!(plugin.cpu_percent.user, 12.7);
+    assert_eq!(plugin.memory.used, 34758656);
+}
+
+#[test]
+fn chanperf_not_running() {
+    let perf: ChanPerf = fixture("chanperf", "not-running.xml");
+    assert_eq!(perf.plugin, None);
+    assert_eq!(perf.error.as_deref(), Some("Channel not running"));
+}
+
+#[test]
+fn fwbeacons() {
+    let beacons: FwBeacons = fixture("fwbeacons", "launch.xml");
+    assert!(beacons.tracking_enabled);
+    assert_eq!(beacons.iter().count(), 7);
+    assert_eq!(
+        beacons.data.beacons[1].event,
+        BeaconEvent::AppCompileComplete
+    );
+    assert_eq!(
+        beacons.data.beacons[6].event,
+        BeaconEvent::Other("VODStartInitiate".to_string())
+    );
+    assert_eq!(beacons.launch_time(), Some(Duration::from_millis(3335)));
+    assert_eq!(beacons.dialog_time(), Some(Duration::from_millis(620)));
+    assert_eq!(
+        beacons.data.beacons[0].time(),
+        Some(UNIX_EPOCH + Duration::from_millis(1697371200120))
+    );
+    let untracked: FwBeacons = fixture("fwbeacons", "untracked.xml");
+    assert!(!untracked.tracking_enabled);
+    assert_eq!(untracked.launch_time(), None);
+}
+
+#[test]
+fn r2d2_bitmaps() {
+    let report: R2d2Bitmaps = fixture("r2d2-bitmaps", "dev.xml");
+    assert_eq!(report.status, "OK");
+    assert_eq!(report.sizes.as_ref().unwrap().used, Some(9175040));
+    assert_eq!(report.iter().count(), 4);
+    assert_eq!(report.total_size(), 9175040);
+    assert_eq!(report.largest(1)[0].name, "pkg:/images/background.jpg");
+    let missing: R2d2Bitmaps = fixture("r2d2-bitmaps", "not-running.xml");
+    assert_eq!(missing.iter().count(), 0);
+    assert_eq!(missing.error.as_deref(), Some("Channel not running"));
+}
+
+#[test]
+fn r2d2_bitmaps_diff() {
+    let before: R2d2Bitmaps = fixture("r2d2-bitmaps", "dev.xml");
+    let mut after = before.clone();
+    after.bitmaps.bitmaps.remove(0);
+    after.bitmaps.bitmaps.push(after.bitmaps.bitmaps[0].clone());
+    let diff = before.diff_sizes(&after);
+    assert_eq!(diff.len(), 2);
+    assert_eq!(diff["pkg:/images/background.jpg"], -8294400);
+    assert_eq!(diff["https://img.example.com/poster/1234.jpg"], 425600);
+}
+
+#[test]
+fn registry() {
+    let registry: PluginRegistry = fixture("registry", "dev.xml");
+    assert_eq!(registry.get("auth", "user"), Some("guest"));
+    assert_eq!(registry.get("auth", "missing"), None);
+    let map = registry.to_map();
+    assert_eq!(map.len(), 2);
+    assert_eq!(
+        map["settings"]["captions"],
+        r#"{"enabled":true,"language":"en"}"#
+    );
+    assert_eq!(registry.registry.unwrap().space_available, Some(15872));
+    let empty: PluginRegistry = fixture("registry", "empty.xml");
+    assert!(empty.to_map().is_empty());
+}
+
+#[test]
+fn sgrendezvous() {
+    let tracking: SgRendezvous = fixture("sgrendezvous", "tracking.xml");
+    assert!(tracking.tracking_enabled);
+    assert_eq!(tracking.data.items.len(), 3);
+    assert_eq!(tracking.data.items[0].node.as_deref(), Some("rows"));
+    assert_eq!(tracking.data.items[0].ms, Some(14));
+    let untracked: SgRendezvous = fixture("sgrendezvous", "untracked.xml");
+    assert!(!untracked.tracking_enabled);
+    assert!(untracked.data.items.is_empty());
+}
+
+#[test]
+fn tv_channels() {
+    let lineup: TvChannels = fixture("tv-channels", "antenna.xml");
+    assert_eq!(lineup.channels.len(), 3);
+    assert_eq!(lineup.channels[0].number, "2.1");
+    assert_eq!(lineup.channels[0].channel_type, "air-digital");
+    assert_eq!(lineup.channels[0].physical_channel, Some(31));
+    assert_eq!(lineup.channels[0].physical_frequency, Some(575000));
+    assert_eq!(lineup.channels[2].physical_channel, None);
+    assert!(lineup.channels[1].user_favorite);
+    assert!(lineup.channels[2].user_hidden);
+    let empty: TvChannels = fixture("tv-channels", "empty.xml");
+    assert!(empty.channels.is_empty());
+}
+
+#[test]
+fn tv_active_channel() {
+    let active: TvActiveChannel = fixture("tv-active-channel", "antenna.xml");
+    let channel = active.channel.unwrap();
+    assert_eq!(channel.number, "2.1");
+    assert_eq!(channel.signal_strength, Some(-54));
+    assert_eq!(channel.physical_channel, Some(31));
+    let program = channel.program().unwrap();
+    assert_eq!(program.title, "The Evening News");
+    assert_eq!(program.ratings, vec!["TV-G"]);
+    assert!(program.has_captions);
+    assert_eq!(program.audio_format.as_deref(), Some("AC3"));
+    assert_eq!(program.audio_languages, vec!["eng", "spa"]);
+    assert_eq!(channel.resolution(), Some("1080i"));
+    let start = UNIX_EPOCH + Duration::from_secs(1728604800);
+    assert_eq!(program.start, Some(start));
+    assert_eq!(program.end(), Some(start + Duration::from_secs(1800)));
+    let now = start + Duration::from_secs(600);
+    assert!(program.is_on(now));
+    assert_eq!(program.elapsed(now), Some(Duration::from_secs(600)));
+    assert_eq!(program.remaining(now), Some(Duration::from_secs(1200)));
+    let idle: TvActiveChannel = fixture("tv-active-channel", "idle.xml");
+    assert!(idle.channel.is_none());
+}
@@ -0,0 +1,89 @@
+use futures_util::StreamExt;
+use roku::{
+    fan_out,
+    testing::{FakeRoku, Fault, FaultProxy},
+    Device, FanOut, FieldChange, FleetMonitor,
+};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn separates_failures() {
+    let first = FakeRoku::start().await.unwrap();
+    let second = FakeRoku::start().await.unwrap();
+    second.set_device_info("serial-number", "YH00AB123456");
+    let proxy = FaultProxy::start(first.url().clone()).await.unwrap();
+    proxy.set_fault(Some(Fault::Status(500)));
+    let devices = vec![first.device(), proxy.device(), second.device()];
+
+    let results = fan_out(&devices, |device: Device| async move {
+        Ok(device.device_info().await?.serial_number)
+    })
+    .await;
+    assert_eq!(results.len(), 3);
+    assert!(!results.is_ok());
+    let serials: Vec<_> = results
+        .successes
+        .iter()
+        .map(|(_, serial)| serial.as_str())
+        .collect();
+    assert_eq!(serials, vec!["X004000AAAAA", "YH00AB123456"]);
+    assert_eq!(results.failures.len(), 1);
+    assert_eq!(results.failures[0].0.url(), proxy.device().url());
+}
+
+#[tokio::test]
+async fn per_device_timeout() {
+    let fake = FakeRoku::start().await.unwrap();
+    let proxy = FaultProxy::start(fake.url().clone()).await.unwrap();
+    proxy.set_fault(Some(Fault::Hang));
+    let devices = vec![fake.device(), proxy.device()];
+
+    let results = FanOut::new()
+        .concurrency(1)
+        .timeout(Duration::from_millis(200))
+        .run(&devices, |device| async move { device.apps().await })
+        .await;
+    assert_eq!(results.successes.len(), 1);
+    assert_eq!(results.failures.len(), 1);
+    assert!(results.failures[0].1.to_string().contains("timed out"));
+}
+
+#[tokio::test]
+async fn monitor_rate_limits() {
+    let first = FakeRoku::start().await.unwrap();
+    let second = FakeRoku::start().await.unwrap();
+    let devices = vec![first.device(), second.device()];
+
+    let started = Instant::now();
+    let mut updates = FleetMonitor::new()
+        .interval(Duration::from_millis(50))
+        .max_per_second(20)
+        .watch(&devices);
+    for _ in 0..4 {
+        assert!(updates.next().await.unwrap().snapshot.is_ok());
+    }
+    assert!(started.elapsed() >= Duration::from_millis(150));
+
+    second.set_player_state("play");
+    loop {
+        let update = updates.next().await.unwrap();
+        if update.device.url() == second.url() && !update.changes.is_empty() {
+            assert!(matches!(
+                &update.changes[0],
+                FieldChange::PlaybackState { new, .. } if new == "play"
+            ));
+            break;
+        }
+    }
+}
+
+#[tokio::test]
+async fn monitor_zero_interval() {
+    let fake = FakeRoku::start().await.unwrap();
+    let mut updates = FleetMonitor::new()
+        .interval(Duration::ZERO)
+        .watch(&[fake.device()]);
+    for _ in 0..2 {
+        assert!(updates.next().await.unwrap().snapshot.is_ok());
+    }
+}
@@ -0,0 +1,79 @@
+use roku::{
+    ActiveApp, Apps, ChanPerf, DeviceInfo, FwBeacons, MediaPlayer, PluginRegistry, R2d2Bitmaps,
+    SgRendezvous, TvActiveChannel, TvChannels,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{env, fs};
+
+mod common;
+
+fn golden<T: DeserializeOwned + Serialize>(kind: &str) {
+    let update = env::var_os("UPDATE_GOLDEN").is_some();
+    for path in common::fixture_paths(kind) {
+        let value: T = common::parse(kind, &path);
+        let json = serde_json::to_string_pretty(&value).unwrap() + "\n";
+        let golden = path.with_extension("json");
+        if update {
+            fs::write(&golden, &json).unwrap();
+            continue;
+        }
+        let expected = fs::read_to_string(&golden)
+            .unwrap_or_else(|_| panic!("missing {}, run with UPDATE_GOLDEN=1", golden.display()));
+        assert_eq!(json, expected, "{}", golden.display());
+    }
+}
+
+#[test]
+fn device_info_golden() {
+    golden::<DeviceInfo>("device-info");
+}
+
+#[test]
+fn apps_golden() {
+    golden::<Apps>("apps");
+}
+
+#[test]
+fn active_app_golden() {
+    golden::<ActiveApp>("active-app");
+}
+
+#[test]
+fn media_player_golden() {
+    golden::<MediaPlayer>("media-player");
+}
+
+#[test]
+fn chanperf_golden() {
+    golden::<ChanPerf>("chanperf");
+}
+
+#[test]
+fn fwbeacons_golden() {
+    golden::<FwBeacons>("fwbeacons");
+}
+
+#[test]
+fn r2d2_bitmaps_golden() {
+    golden::<R2d2Bitmaps>("r2d2-bitmaps");
+}
+
+#[test]
+fn registry_golden() {
+    golden::<PluginRegistry>("registry");
+}
+
+#[test]
+fn sgrendezvous_golden() {
+    golden::<SgRendezvous>("sgrendezvous");
+}
+
+#[test]
+fn tv_channels_golden() {
+    golden::<TvChannels>("tv-channels");
+}
+
+#[test]
+fn tv_active_channel_golden() {
+    golden::<TvActiveChannel>("tv-active-channel");
+}
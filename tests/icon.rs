@@ -0,0 +1,24 @@
+use roku::{testing::FakeRoku, App, Error};
+
+#[tokio::test]
+async fn fetch_icon() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+    let apps = device.apps().await.unwrap();
+    let icon = device.app_icon(&apps.apps[0]).await.unwrap();
+    assert_eq!(icon.content_type.as_deref(), Some("image/png"));
+    assert_eq!(icon.extension(), Some("png"));
+    assert!(icon.data.starts_with(b"\x89PNG"));
+    assert_eq!(fake.requests().last().unwrap(), "GET /query/icon/12");
+
+    assert!(matches!(device.icon("404").await, Err(Error::Ecp(_))));
+    let app = App {
+        id: None,
+        name: "Roku".to_string(),
+        version: None,
+    };
+    assert!(matches!(
+        device.app_icon(&app).await,
+        Err(Error::Argument(_))
+    ));
+}
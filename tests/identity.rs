@@ -0,0 +1,28 @@
+use roku::{testing::FakeRoku, DeviceIdentity};
+use std::collections::HashSet;
+
+#[test]
+fn usn_identity() {
+    let identity = DeviceIdentity::from_usn("uuid:roku:ecp:x004000aaaaa").unwrap();
+    assert_eq!(identity.serial_number(), "X004000AAAAA");
+    assert_eq!(identity.udn(), None);
+    assert_eq!(DeviceIdentity::from_usn("uuid:29380000-0800"), None);
+    assert_eq!(DeviceIdentity::from_usn("uuid:roku:ecp:"), None);
+}
+
+#[tokio::test]
+async fn identity_survives_rediscovery() {
+    let fake = FakeRoku::start().await.unwrap();
+    let identity = fake.device().identity().await.unwrap();
+    assert_eq!(identity.serial_number(), "X004000AAAAA");
+    assert_eq!(identity.udn(), Some("29380000-0800-1025-80a4-d83134a7e5b6"));
+
+    let discovered = DeviceIdentity::from_usn("uuid:roku:ecp:X004000AAAAA").unwrap();
+    let rediscovered = fake.device().with_identity(discovered.clone());
+    assert_eq!(rediscovered.identity().await.unwrap(), identity);
+
+    let seen: HashSet<_> = vec![identity, discovered, DeviceIdentity::new("YH00AB123456")]
+        .into_iter()
+        .collect();
+    assert_eq!(seen.len(), 2);
+}
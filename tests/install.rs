@@ -0,0 +1,86 @@
+use roku::{
+    testing::{models, FakeRoku},
+    InstallQueue, InstallStatus,
+};
+use std::time::Duration;
+
+#[tokio::test]
+async fn installs_missing_channels() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_store_apps(vec![
+        models::app("13", "Prime Video"),
+        models::app("291097", "Disney+"),
+    ]);
+    let device = fake.device();
+
+    let mut progress = vec![];
+    let report = InstallQueue::new()
+        .interval(Duration::from_millis(10))
+        .timeout(Duration::from_millis(500))
+        .run(&device, &["12", "13", "404", "291097", "13"], |event| {
+            progress.push((event.app_id.clone(), event.status.clone()))
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(report.installed, vec!["13", "291097"]);
+    assert_eq!(report.skipped, vec!["12", "13"]);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].app_id, "404");
+    assert!(!report.is_ok());
+
+    assert_eq!(progress[0], ("12".to_string(), InstallStatus::Skipped));
+    assert_eq!(
+        &progress[1..4],
+        &[
+            ("13".to_string(), InstallStatus::Installing),
+            ("13".to_string(), InstallStatus::Confirming),
+            ("13".to_string(), InstallStatus::Installed),
+        ]
+    );
+    assert!(matches!(progress[5].1, InstallStatus::Failed { .. }));
+
+    let apps = device.apps().await.unwrap().apps;
+    assert_eq!(apps.len(), 5);
+    assert_eq!(fake.active_app(), None);
+}
+
+#[tokio::test]
+async fn unconfirmed_install_times_out() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_store_apps(vec![models::app("13", "Prime Video")]);
+
+    let report = InstallQueue::new()
+        .confirm(false)
+        .interval(Duration::from_millis(10))
+        .timeout(Duration::from_millis(100))
+        .run(&fake.device(), &["13"], |_| {})
+        .await
+        .unwrap();
+    assert!(report.installed.is_empty());
+    assert!(report.failed[0].error.contains("not installed"));
+}
+
+#[tokio::test]
+async fn install_one_returns_app() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_store_apps(vec![models::app("13", "Prime Video")]);
+    let device = fake.device();
+
+    let app = InstallQueue::new()
+        .interval(Duration::from_millis(10))
+        .timeout(Duration::from_millis(500))
+        .install_one(&device, "13")
+        .await
+        .unwrap();
+    assert_eq!(app.name, "Prime Video");
+    assert_eq!(fake.active_app(), None);
+
+    fake.clear_requests();
+    let app = device
+        .install_and_wait(&models::app("13", ""), true)
+        .await
+        .unwrap();
+    assert_eq!(app.name, "Prime Video");
+    assert_eq!(fake.requests(), vec!["GET /query/apps"]);
+}
@@ -0,0 +1,33 @@
+use roku::{GridKeyboard, Key};
+
+fn keyboard() -> GridKeyboard {
+    GridKeyboard::new(&["abcdefg", "hijklmn", "opqrstu", "vwxyz⇧ "])
+}
+
+#[test]
+fn spells_with_arrows() {
+    let keys = keyboard().keys("hi").unwrap();
+    assert_eq!(keys, vec![Key::Down, Key::Select, Key::Right, Key::Select]);
+}
+
+#[test]
+fn toggles_case() {
+    use Key::*;
+    let keys = keyboard().keys("Ab").unwrap();
+    assert_eq!(
+        keys,
+        vec![
+            Down, Down, Down, Right, Right, Right, Right, Right, Select, Up, Up, Up, Left, Left,
+            Left, Left, Left, Select, Down, Down, Down, Right, Right, Right, Right, Right, Select,
+            Up, Up, Up, Left, Left, Left, Left, Select,
+        ]
+    );
+}
+
+#[test]
+fn wraps_and_rejects() {
+    let mut keyboard = keyboard();
+    keyboard.wrap(true);
+    assert_eq!(keyboard.keys("g").unwrap(), vec![Key::Left, Key::Select]);
+    assert!(keyboard.keys("!").is_err());
+}
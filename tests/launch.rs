@@ -0,0 +1,102 @@
+use roku::{
+    testing::{models, FakeRoku},
+    well_known, App, DeepLink, DeepLinkType, Error, LaunchParams,
+};
+
+#[tokio::test]
+async fn launch_params() {
+    let fake = FakeRoku::start().await.unwrap();
+    let netflix = models::app("12", "Netflix");
+    fake.set_apps(vec![netflix.clone()]);
+    let device = fake.device();
+
+    let mut params = LaunchParams::new();
+    params
+        .param("mediaType".to_string(), "series".to_string())
+        .content_id("80057281".to_string())
+        .param("profile".to_string(), "kids & family".to_string());
+    device.launch_with(&netflix, params).await.unwrap();
+    assert_eq!(
+        fake.requests(),
+        vec!["POST /launch/12?contentId=80057281&mediaType=series&profile=kids+%26+family"]
+    );
+    assert_eq!(fake.active_app().unwrap().id.as_deref(), Some("12"));
+
+    fake.clear_requests();
+    let mut link = DeepLink::new("81".to_string());
+    link.launch("12".to_string());
+    device.deep_link(&link).await.unwrap();
+    device
+        .launch_with(&netflix, LaunchParams::new())
+        .await
+        .unwrap();
+    assert_eq!(
+        fake.requests(),
+        vec!["POST /launch/12?contentId=81", "POST /launch/12"]
+    );
+}
+
+#[tokio::test]
+async fn deep_link() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+
+    let mut link = DeepLink::new("s1e4".to_string());
+    link.media_type("episode".parse().unwrap())
+        .param("resume".to_string(), "true".to_string());
+    device.deep_link(&link).await.unwrap();
+    link.launch("12".to_string());
+    device.deep_link(&link).await.unwrap();
+    assert_eq!(
+        fake.requests(),
+        vec![
+            "POST /input?contentId=s1e4&mediaType=episode&resume=true",
+            "POST /launch/12?contentId=s1e4&mediaType=episode&resume=true"
+        ]
+    );
+    assert_eq!(DeepLinkType::ShortFormVideo.to_string(), "shortFormVideo");
+    assert!("trailer".parse::<DeepLinkType>().is_err());
+}
+
+#[tokio::test]
+async fn launch_dev() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_apps(vec![models::app("dev", "My Channel")]);
+    let device = fake.device();
+
+    device.launch_dev(LaunchParams::new()).await.unwrap();
+    let mut params = LaunchParams::new();
+    params.content_id("42".to_string());
+    device.launch_dev(params).await.unwrap();
+    assert_eq!(
+        fake.requests(),
+        vec!["POST /launch/dev", "POST /launch/dev?contentId=42"]
+    );
+    assert_eq!(fake.active_app().unwrap().name, "My Channel");
+}
+
+#[tokio::test]
+async fn launch_by_name() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+
+    let app = device.launch_by_name(" youtube ").await.unwrap();
+    assert_eq!(app.id.as_deref(), Some("837"));
+    assert_eq!(fake.active_app(), Some(app));
+    assert!(matches!(
+        device.launch_by_name("Hulu").await,
+        Err(Error::NotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn well_known_ids() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+
+    device.launch(&well_known::NETFLIX.into()).await.unwrap();
+    assert_eq!(fake.active_app().unwrap(), well_known::NETFLIX);
+    let hulu: App = well_known::HULU.into();
+    assert_eq!(hulu.id.as_deref(), Some("2285"));
+    assert_eq!(well_known::YOUTUBE.to_string(), "837");
+}
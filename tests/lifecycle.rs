@@ -0,0 +1,34 @@
+use futures_util::StreamExt;
+use roku::{
+    testing::{models, FakeRoku},
+    AppState,
+};
+use std::time::Duration;
+
+#[tokio::test]
+async fn crash_to_home() {
+    let fake = FakeRoku::start().await.unwrap();
+    let netflix = models::app("12", "Netflix");
+    let hulu = models::app("2285", "Hulu");
+    fake.set_apps(vec![netflix.clone(), hulu.clone()]);
+    let device = fake.device();
+
+    let mut session = device.track_app("12", Duration::from_millis(10));
+    assert_eq!(session.state(), AppState::Launching);
+    device.launch(&netflix).await.unwrap();
+    let transition = session.next().await.unwrap().unwrap();
+    assert_eq!(transition.previous, AppState::Launching);
+    assert_eq!(transition.current, AppState::Active);
+    assert_eq!(session.state(), AppState::Active);
+
+    device.launch(&hulu).await.unwrap();
+    let transition = session.next().await.unwrap().unwrap();
+    assert_eq!(transition.current, AppState::Backgrounded);
+    assert_eq!(transition.foreground, hulu);
+
+    fake.set_active_app(None);
+    let transition = session.next().await.unwrap().unwrap();
+    assert_eq!(transition.previous, AppState::Backgrounded);
+    assert_eq!(transition.current, AppState::Exited);
+    assert!(!session.state().is_running());
+}
@@ -0,0 +1,129 @@
+use roku::{
+    testing::{models, Call, MockDevice},
+    Acceleration, CaptionToggle, Direction, Error, GridKeyboard, InstallQueue, InstallStatus, Key,
+    Script,
+};
+use std::time::Duration;
+
+fn bare(id: &str) -> roku::App {
+    roku::App {
+        id: Some(id.to_string()),
+        name: String::new(),
+        version: None,
+    }
+}
+
+#[tokio::test]
+async fn script_on_mock() {
+    let mock = MockDevice::new();
+    let script = Script::parse("key home down\nlaunch 12\ntype ab").unwrap();
+    script.run(&mock).await.unwrap();
+    assert_eq!(
+        mock.calls(),
+        vec![
+            Call::KeyPress(Key::Home),
+            Call::KeyPress(Key::Down),
+            Call::Launch(bare("12")),
+            Call::KeyPress(Key::Lit('a')),
+            Call::KeyPress(Key::Lit('b')),
+        ]
+    );
+
+    mock.clear_calls();
+    mock.fail_next(Error::Response("offline".to_string()));
+    assert!(matches!(script.run(&mock).await, Err(Error::Response(_))));
+    assert_eq!(mock.calls(), vec![Call::KeyPress(Key::Home)]);
+}
+
+#[tokio::test]
+async fn captions_on_mock() {
+    let mock = MockDevice::new();
+    mock.set_media_player(models::media_player("play"))
+        .set_active_app(models::active_app(Some(models::app("12", "Netflix"))));
+    let mut toggle = CaptionToggle::new();
+    toggle
+        .delay(Duration::ZERO)
+        .channel_offset("12".to_string(), 1);
+    assert_eq!(toggle.run(&mock).await.unwrap(), None);
+    assert_eq!(
+        mock.calls(),
+        vec![
+            Call::MediaPlayer,
+            Call::ActiveApp,
+            Call::KeyPress(Key::Info),
+            Call::KeyPress(Key::Down),
+            Call::KeyPress(Key::Right),
+            Call::KeyPress(Key::Info),
+            Call::MediaPlayer,
+        ]
+    );
+
+    mock.set_media_player(models::media_player("close"));
+    assert!(matches!(toggle.run(&mock).await, Err(Error::Argument(_))));
+}
+
+#[tokio::test]
+async fn scroll_and_keyboard_on_mock() {
+    let mock = MockDevice::new();
+    let mut acceleration = Acceleration::new();
+    acceleration.initial(Duration::ZERO).hold(Duration::ZERO);
+    acceleration
+        .scroll(&mock, Direction::Right, 2)
+        .await
+        .unwrap();
+    assert_eq!(
+        mock.calls(),
+        vec![
+            Call::KeyDown(Key::Right),
+            Call::KeyUp(Key::Right),
+            Call::KeyDown(Key::Right),
+            Call::KeyUp(Key::Right),
+        ]
+    );
+
+    mock.clear_calls();
+    let mut keyboard = GridKeyboard::new(&["ab", "cd"]);
+    keyboard.interval(Duration::ZERO);
+    keyboard.type_text(&mock, "d").await.unwrap();
+    assert_eq!(
+        mock.calls(),
+        vec![
+            Call::KeyPress(Key::Down),
+            Call::KeyPress(Key::Right),
+            Call::KeyPress(Key::Select),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn install_queue_on_mock() {
+    let mock = MockDevice::new();
+    mock.set_apps(models::apps(&[("12", "Netflix"), ("13", "Prime Video")]));
+    let mut progress = vec![];
+    let report = InstallQueue::new()
+        .run(&mock, &["12", "13"], |event| {
+            progress.push(event.status.clone())
+        })
+        .await
+        .unwrap();
+    assert_eq!(report.skipped, vec!["12", "13"]);
+    assert_eq!(
+        progress,
+        vec![InstallStatus::Skipped, InstallStatus::Skipped]
+    );
+    assert_eq!(mock.calls(), vec![Call::Apps]);
+
+    let app = InstallQueue::new().install_one(&mock, "13").await.unwrap();
+    assert_eq!(app.name, "Prime Video");
+
+    mock.clear_calls();
+    let err = InstallQueue::new()
+        .confirm(false)
+        .interval(Duration::from_millis(5))
+        .timeout(Duration::from_millis(20))
+        .install_one(&mock, "404")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("was not installed"));
+    assert_eq!(mock.calls()[1], Call::Install(bare("404")));
+}
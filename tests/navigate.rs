@@ -0,0 +1,206 @@
+use roku::{
+    parse_duration,
+    testing::{FakeRoku, Fault, FaultProxy},
+    Acceleration, CancellationToken, Direction, Error, GameInput, Key, SecretScreen, TouchOp,
+};
+use std::time::Duration;
+
+#[test]
+fn acceleration_intervals() {
+    let mut acceleration = Acceleration::new();
+    acceleration
+        .initial(Duration::from_millis(200))
+        .minimum(Duration::from_millis(50))
+        .factor(0.5);
+    assert_eq!(acceleration.interval(0), Duration::from_millis(200));
+    assert_eq!(acceleration.interval(1), Duration::from_millis(100));
+    assert_eq!(acceleration.interval(3), Duration::from_millis(50));
+    assert_eq!(acceleration.interval(1000), Duration::from_millis(50));
+}
+
+#[tokio::test]
+async fn scroll_and_hold() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+    let mut acceleration = Acceleration::new();
+    acceleration
+        .initial(Duration::from_millis(5))
+        .minimum(Duration::from_millis(1))
+        .hold(Duration::ZERO);
+    device
+        .scroll_with(Direction::Down, 3, &acceleration)
+        .await
+        .unwrap();
+    let hold = device.hold_direction(Direction::Right).await.unwrap();
+    hold.release().await.unwrap();
+    assert_eq!(
+        fake.requests(),
+        vec![
+            "POST /keydown/Down",
+            "POST /keyup/Down",
+            "POST /keydown/Down",
+            "POST /keyup/Down",
+            "POST /keydown/Down",
+            "POST /keyup/Down",
+            "POST /keydown/Right",
+            "POST /keyup/Right",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn keypress_repeat_cancels() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+    let cancel = CancellationToken::new();
+    let sent = device
+        .keypress_repeat(&Key::Right, 3, Duration::from_millis(5), &cancel)
+        .await
+        .unwrap();
+    assert_eq!(sent, 3);
+    assert_eq!(fake.requests(), vec!["POST /keypress/Right"; 3]);
+
+    let canceller = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        canceller.cancel();
+    });
+    let sent = device
+        .keypress_repeat(&Key::Left, 100, Duration::from_millis(50), &cancel)
+        .await
+        .unwrap();
+    assert!(sent > 0 && sent < 100);
+}
+
+#[test]
+fn repeat_intervals() {
+    assert_eq!(parse_duration("0.2"), Ok(Duration::from_millis(200)));
+    assert_eq!(parse_duration("150ms"), Ok(Duration::from_millis(150)));
+    for interval in &["-1", "NaN", "inf", "1e300", "soon"] {
+        assert!(parse_duration(interval).is_err(), "{}", interval);
+    }
+}
+
+#[tokio::test]
+async fn keypress_repeat_reports_progress() {
+    let fake = FakeRoku::start().await.unwrap();
+    let proxy = FaultProxy::start(fake.url().clone()).await.unwrap();
+    proxy
+        .inject(Fault::Latency(Duration::ZERO))
+        .inject(Fault::Latency(Duration::ZERO))
+        .inject(Fault::Status(500));
+    let err = proxy
+        .device()
+        .keypress_repeat(&Key::Down, 5, Duration::ZERO, &CancellationToken::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        &err,
+        Error::Incomplete { completed: 2, source } if matches!(**source, Error::Ecp(_))
+    ));
+    assert_eq!(err.to_string(), "failed after 2 keypresses");
+    assert_eq!(fake.requests(), vec!["POST /keypress/Down"; 2]);
+}
+
+#[tokio::test]
+async fn release_all_held() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+    device.keydown(&Key::Up).await.unwrap();
+    device.clone().keydown(&Key::Right).await.unwrap();
+    device.keydown(&Key::Up).await.unwrap();
+    assert_eq!(device.held_keys(), vec![Key::Up, Key::Right]);
+
+    fake.clear_requests();
+    device.release_all().await.unwrap();
+    assert_eq!(fake.requests(), vec!["POST /keyup/Up", "POST /keyup/Right"]);
+    assert!(device.held_keys().is_empty());
+}
+
+#[tokio::test]
+async fn secret_screen() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+    device
+        .open_secret_screen("wireless".parse().unwrap())
+        .await
+        .unwrap();
+    let presses: Vec<_> = fake
+        .requests()
+        .into_iter()
+        .map(|request| request.trim_start_matches("POST /keypress/").to_string())
+        .collect();
+    assert_eq!(
+        presses,
+        vec!["Home", "Home", "Home", "Home", "Home", "Up", "Down", "Up", "Down", "Up"]
+    );
+
+    fake.clear_requests();
+    assert!(matches!(
+        device.open_secret_screen(SecretScreen::Antenna).await,
+        Err(Error::NotSupported(_))
+    ));
+    assert_eq!(fake.requests(), vec!["GET /query/device-info"]);
+}
+
+#[tokio::test]
+async fn game_input() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+    let mut input = GameInput::new();
+    input
+        .acceleration(0.0, 0.5, 9.81)
+        .touch(200.0, 135.5, TouchOp::Down)
+        .touch(640.0, 360.0, "move".parse().unwrap())
+        .acceleration(0.0, 0.0, 9.81);
+    device.game_input(input).await.unwrap();
+    assert_eq!(
+        fake.requests(),
+        vec![
+            "POST /input?acceleration.x=0.0&acceleration.y=0.0&acceleration.z=9.81\
+             &touch.0.x=200.0&touch.0.y=135.5&touch.0.op=down\
+             &touch.1.x=640.0&touch.1.y=360.0&touch.1.op=move"
+        ]
+    );
+    assert!(matches!(
+        device.game_input(GameInput::new()).await,
+        Err(Error::Argument(_))
+    ));
+}
+
+#[tokio::test]
+async fn power_and_game_keys() {
+    assert_eq!("poweron".parse::<Key>().unwrap(), Key::PowerOn);
+    assert_eq!("ButtonA".parse::<Key>().unwrap(), Key::ButtonA);
+    assert_eq!("A".parse::<Key>().unwrap(), Key::Lit('A'));
+    assert_eq!(Key::ButtonB.to_string(), "ButtonB");
+    for key in &[
+        Key::ButtonA,
+        Key::ButtonB,
+        Key::Pause,
+        Key::Power,
+        Key::PowerOn,
+        Key::Stop,
+        Key::Lit('A'),
+    ] {
+        assert_eq!(key.to_string().parse::<Key>().unwrap(), *key);
+    }
+
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+    for key in &[Key::Power, Key::ButtonA, Key::ButtonB, Key::Stop] {
+        device.keypress(key).await.unwrap();
+    }
+    assert_eq!(
+        fake.requests(),
+        vec![
+            "POST /keypress/Power",
+            "POST /keypress/A",
+            "POST /keypress/B",
+            "POST /keypress/Stop"
+        ]
+    );
+    assert_eq!(device.device_info().await.unwrap().power_mode, "DisplayOff");
+    device.keypress(&Key::Power).await.unwrap();
+    assert_eq!(device.device_info().await.unwrap().power_mode, "PowerOn");
+}
@@ -0,0 +1,35 @@
+use roku::{
+    consts,
+    testing::{models, FakeRoku},
+    App,
+};
+use std::time::Duration;
+
+#[tokio::test]
+async fn aggregates_app_and_player() {
+    let fake = FakeRoku::start().await.unwrap();
+    let netflix = models::app("12", "Netflix");
+    fake.set_active_app(Some(netflix.clone()));
+    fake.set_player_state("play");
+    fake.set_player_position(Some(Duration::from_millis(61_500)));
+
+    let now = fake.device().now_playing().await.unwrap();
+    assert_eq!(now.app, netflix);
+    assert!(now.is_playing() && !now.is_home());
+    assert_eq!(now.position, Some(Duration::from_millis(61_500)));
+    assert_eq!(now.channel, None);
+}
+
+#[tokio::test]
+async fn tuner_without_active_channel_query() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_active_app(Some(App {
+        id: Some(consts::APP_LIVE_TV.to_string()),
+        name: "Antenna TV".to_string(),
+        version: None,
+    }));
+    let now = fake.device().now_playing().await.unwrap();
+    assert_eq!(now.app.name, "Antenna TV");
+    assert_eq!(now.channel, None);
+    assert_eq!(now.program(), None);
+}
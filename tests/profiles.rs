@@ -0,0 +1,86 @@
+use roku::{profiles::host_url, Profile, Profiles};
+use std::{env, fs, time::Duration};
+
+const TOML: &str = r#"
+bedroom = "192.168.1.21"
+
+[living-room]
+host = "192.168.1.20"
+mac = "d8:31:34:a7:e5:b6"
+password = "rokudev"
+timeout_ms = 2000
+key_interval_ms = 150
+
+[office]
+serial = "X004000AAAAA"
+"#;
+
+#[test]
+fn parse_toml() {
+    let path = env::temp_dir().join(format!("roku-profiles-{}.toml", std::process::id()));
+    fs::write(&path, TOML).unwrap();
+    let profiles = Profiles::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        profiles.get("bedroom"),
+        Some(&Profile::new("192.168.1.21".to_string()))
+    );
+    let living_room = profiles.get("living-room").unwrap();
+    assert_eq!(
+        living_room.url().unwrap().unwrap().as_str(),
+        "http://192.168.1.20:8060/"
+    );
+    assert_eq!(living_room.timeout(), Some(Duration::from_secs(2)));
+    assert_eq!(living_room.key_interval(), Some(Duration::from_millis(150)));
+    assert_eq!(profiles.get("office").unwrap().url().unwrap(), None);
+    assert_eq!(profiles.iter().count(), 3);
+}
+
+#[test]
+fn round_trip() {
+    let mut profiles = Profiles::new();
+    let mut office = Profile::new("roku.local:8060".to_string());
+    office.serial = Some("X004000AAAAA".to_string());
+    office.timeout_ms = Some(500);
+    profiles.insert("office".to_string(), office);
+    profiles.insert("den".to_string(), Profile::new("192.168.1.22".to_string()));
+
+    for extension in &["toml", "json"] {
+        let path = env::temp_dir().join(format!(
+            "roku-profiles-{}-round-trip.{}",
+            std::process::id(),
+            extension
+        ));
+        profiles.save(&path).unwrap();
+        assert_eq!(Profiles::load(&path).unwrap(), profiles);
+        fs::remove_file(&path).unwrap();
+    }
+
+    assert!(profiles.remove("den").is_some());
+    assert!(profiles.get("den").is_none());
+}
+
+#[tokio::test]
+async fn device() {
+    let profile = Profile::new("http://192.168.1.20:8060/".to_string());
+    let device = profile.device().await.unwrap();
+    assert_eq!(device.url().as_str(), "http://192.168.1.20:8060/");
+    assert!(Profile::default().device().await.is_err());
+}
+
+#[test]
+fn host_urls() {
+    let cases = [
+        ("192.168.1.20", "http://192.168.1.20:8060/"),
+        ("192.168.1.20:18060", "http://192.168.1.20:18060/"),
+        ("tunnel.local/roku", "http://tunnel.local:8060/roku/"),
+        (
+            "tunnel.local:9000/roku/ecp/",
+            "http://tunnel.local:9000/roku/ecp/",
+        ),
+    ];
+    for (host, url) in cases.iter() {
+        assert_eq!(host_url(host).unwrap().as_str(), *url);
+    }
+}
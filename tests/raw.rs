@@ -0,0 +1,106 @@
+use roku::{
+    testing::{Exchange, FakeRoku, Replay},
+    BeaconEvent, Error,
+};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+struct Power {
+    power_mode: String,
+    supports_suspend: bool,
+}
+
+#[tokio::test]
+async fn raw_requests() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+
+    let response = device.raw_get("/query/device-info").await.unwrap();
+    assert!(response.is_success());
+    assert!(response.content_type.unwrap().starts_with("text/xml"));
+    assert!(response.body.contains("<serial-number>"));
+
+    let response = device
+        .raw_post("keypress/Lit_a", &[("source", "test")])
+        .await
+        .unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(
+        fake.requests().last().unwrap(),
+        "POST /keypress/Lit_a?source=test"
+    );
+
+    let response = device.raw_get("query/undocumented").await.unwrap();
+    assert_eq!(response.status, 404);
+    assert!(matches!(
+        response.error_for_status(),
+        Err(Error::Ecp(err)) if err.code == 404
+    ));
+}
+
+#[tokio::test]
+async fn typed_query() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_device_info("power-mode", "DisplayOff");
+    let power: Power = fake.device().query_as("query/device-info").await.unwrap();
+    assert_eq!(power.power_mode, "DisplayOff");
+
+    let missing = fake.device().query_as::<Power>("query/undocumented").await;
+    assert!(matches!(missing, Err(Error::Ecp(err)) if err.code == 404));
+}
+
+#[tokio::test]
+async fn app_chanperf() {
+    let body = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/chanperf/dev.xml"
+    ))
+    .unwrap();
+    let replay = Replay::start(vec![Exchange {
+        method: "GET".to_string(),
+        path: "/query/chanperf/dev".to_string(),
+        status: 200,
+        content_type: Some("text/xml; charset=\"utf-8\"".to_string()),
+        body,
+    }])
+    .await
+    .unwrap();
+    let perf = replay.device().app_chanperf("dev").await.unwrap();
+    assert_eq!(perf.plugin.unwrap().id.as_deref(), Some("dev"));
+    assert!(replay.remaining().is_empty());
+}
+
+#[tokio::test]
+async fn fwbeacons_tracking() {
+    let exchange = |method: &str, path: &str, body: &str| Exchange {
+        method: method.to_string(),
+        path: path.to_string(),
+        status: 200,
+        content_type: Some("text/xml; charset=\"utf-8\"".to_string()),
+        body: body.to_string(),
+    };
+    let replay = Replay::start(vec![
+        exchange("POST", "/fwbeacons/track", ""),
+        exchange(
+            "GET",
+            "/query/fwbeacons",
+            "<fwbeacons><tracking-enabled>true</tracking-enabled><data>\
+             <beacon><event>AppLaunchComplete</event><channel-id>dev</channel-id>\
+             <timestamp>1697371203455</timestamp></beacon></data></fwbeacons>",
+        ),
+        exchange("POST", "/fwbeacons/untrack", ""),
+    ])
+    .await
+    .unwrap();
+    let device = replay.device();
+    device.track_fwbeacons().await.unwrap();
+    let beacons = device.fwbeacons().await.unwrap();
+    assert!(beacons.tracking_enabled);
+    assert_eq!(
+        beacons.data.beacons[0].event,
+        BeaconEvent::AppLaunchComplete
+    );
+    device.untrack_fwbeacons().await.unwrap();
+    assert!(replay.remaining().is_empty());
+}
@@ -0,0 +1,33 @@
+use roku::{testing::Replay, App, Key};
+use std::path::Path;
+
+async fn replay(name: &str) -> Replay {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/sessions")
+        .join(name);
+    Replay::load(path).await.unwrap()
+}
+
+#[tokio::test]
+async fn launch_and_return_home() {
+    let replay = replay("launch-home.json").await;
+    let device = replay.device();
+    let apps = device.apps().await.unwrap();
+    let youtube = apps.apps.iter().find(|app| app.name == "YouTube").unwrap();
+    device.launch(youtube).await.unwrap();
+    assert_eq!(device.active_app().await.unwrap().app, *youtube);
+    device.keypress(&Key::Home).await.unwrap();
+    assert_eq!(device.active_app().await.unwrap().app.name, "Roku");
+    assert!(replay.remaining().is_empty());
+}
+
+#[tokio::test]
+async fn unrecorded_request_fails() {
+    let replay = replay("launch-home.json").await;
+    let app = App {
+        id: Some("12".to_string()),
+        name: "Netflix".to_string(),
+        version: None,
+    };
+    assert!(replay.device().launch(&app).await.is_err());
+}
@@ -0,0 +1,142 @@
+use chrono::{Duration as ChronoDuration, Local, NaiveDateTime};
+use roku::{
+    schedule::{Job, Scheduler, When},
+    testing::FakeRoku,
+    CancellationToken, Device, Script,
+};
+use std::{env, fs, time::Duration};
+use tokio::time::sleep;
+use url::Url;
+
+fn time(text: &str) -> NaiveDateTime {
+    NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M").unwrap()
+}
+
+fn next(when: &str, now: &str) -> Option<NaiveDateTime> {
+    When::parse(when).unwrap().next_after(time(now))
+}
+
+#[test]
+fn once() {
+    assert_eq!(
+        next("2026-01-02 03:04", "2026-01-01 00:00"),
+        Some(time("2026-01-02 03:04"))
+    );
+    assert_eq!(next("2026-01-02 03:04", "2026-01-02 03:04"), None);
+}
+
+#[test]
+fn daily() {
+    assert_eq!(
+        next("00:00", "2026-03-10 23:59"),
+        Some(time("2026-03-11 00:00"))
+    );
+    assert_eq!(
+        next("18:30", "2026-03-10 12:00"),
+        Some(time("2026-03-10 18:30"))
+    );
+}
+
+#[test]
+fn cron() {
+    assert_eq!(
+        next("*/15 * * * *", "2026-03-10 12:01"),
+        Some(time("2026-03-10 12:15"))
+    );
+    assert_eq!(
+        next("0 0 * * 1-5", "2026-03-13 12:00"),
+        Some(time("2026-03-16 00:00"))
+    );
+    assert_eq!(
+        next("30 6 1 * *", "2026-03-10 12:00"),
+        Some(time("2026-04-01 06:30"))
+    );
+    assert_eq!(
+        next("0 12 * * 7", "2026-03-10 12:00"),
+        Some(time("2026-03-15 12:00"))
+    );
+}
+
+#[test]
+fn invalid() {
+    assert!(When::parse("tomorrow").is_err());
+    assert!(When::parse("60 * * * *").is_err());
+    assert!(When::parse("* * * *").is_err());
+}
+
+#[test]
+fn persisted_jobs() {
+    let path = env::temp_dir().join(format!("roku-jobs-{}.json", std::process::id()));
+    let devices = vec![Device::new(
+        Url::parse("http://192.168.1.20:8060/").unwrap(),
+    )];
+    let script = Script::parse("key poweroff\ntype \"hello world\"").unwrap();
+
+    let mut scheduler = Scheduler::load(&path).unwrap();
+    let midnight = scheduler.at("00:00", &devices, &script).unwrap();
+    let once = scheduler.at("2026-01-01 09:00", &devices, &script).unwrap();
+    assert!(scheduler.at("someday", &[], &script).is_err());
+
+    let mut loaded = Scheduler::load(&path).unwrap();
+    assert_eq!(loaded.jobs(), scheduler.jobs());
+    assert_eq!(Script::parse(&loaded.jobs()[0].script).unwrap(), script);
+    assert_eq!(
+        loaded.next_run(time("2025-12-31 23:00")),
+        Some((time("2026-01-01 00:00"), midnight))
+    );
+    assert_eq!(
+        loaded.next_run(time("2026-01-01 08:00")),
+        Some((time("2026-01-01 09:00"), once))
+    );
+
+    assert!(loaded.cancel(midnight).unwrap());
+    assert!(!loaded.cancel(midnight).unwrap());
+    assert_eq!(Scheduler::load(&path).unwrap().jobs().len(), 1);
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn malformed_jobs() {
+    let fake = FakeRoku::start().await.unwrap();
+    let path = env::temp_dir().join(format!("roku-bad-jobs-{}.json", std::process::id()));
+    let now = Local::now().naive_local();
+    let job = |id, devices: &[&str], script: &str| Job {
+        id,
+        when: (now + ChronoDuration::seconds(id as i64 + 1))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string(),
+        devices: devices.iter().map(|device| device.to_string()).collect(),
+        script: script.to_string(),
+    };
+    let jobs = vec![
+        job(1, &[fake.url().as_str()], "dance"),
+        job(2, &["not a url", fake.url().as_str()], "key home"),
+    ];
+    fs::write(&path, serde_json::to_string(&jobs).unwrap()).unwrap();
+    let mut scheduler = Scheduler::load(&path).unwrap();
+    fs::write(&path, "{").unwrap();
+
+    let cancel = CancellationToken::new();
+    let canceller = cancel.clone();
+    let mut results = vec![];
+    let (ran, _) = tokio::join!(
+        scheduler.run(&cancel, |job, device, result| {
+            results.push((job.id, device.to_string(), result.is_ok()))
+        }),
+        async move {
+            sleep(Duration::from_secs(5)).await;
+            canceller.cancel();
+        }
+    );
+    ran.unwrap();
+    assert_eq!(
+        results,
+        vec![
+            (1, fake.url().to_string(), false),
+            (2, "not a url".to_string(), false),
+            (2, fake.url().to_string(), true),
+        ]
+    );
+    assert_eq!(fake.requests(), vec!["POST /keypress/Home"]);
+    fs::remove_file(&path).unwrap();
+}
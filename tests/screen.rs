@@ -0,0 +1,70 @@
+use image::{ImageFormat, Rgba, RgbaImage};
+use roku::{
+    screen::{Comparison, Region},
+    Image,
+};
+use std::io::Cursor;
+
+fn png(width: u32, height: u32, paint: impl Fn(u32, u32) -> [u8; 4]) -> Image {
+    let image = RgbaImage::from_fn(width, height, |x, y| Rgba(paint(x, y)));
+    let mut data = Cursor::new(vec![]);
+    image.write_to(&mut data, ImageFormat::Png).unwrap();
+    Image {
+        content_type: Some("image/png".to_string()),
+        data: data.into_inner(),
+    }
+}
+
+#[test]
+fn identical() {
+    let golden = png(4, 4, |_, _| [10, 20, 30, 255]);
+    let diff = Comparison::new().diff(&golden, &golden).unwrap();
+    assert_eq!(diff.compared, 16);
+    assert_eq!(diff.differing, 0);
+}
+
+#[test]
+fn tolerance() {
+    let golden = png(4, 4, |_, _| [100, 100, 100, 255]);
+    let actual = png(4, 4, |_, _| [104, 100, 100, 255]);
+    assert!(Comparison::new()
+        .tolerance(4)
+        .matches(&actual, &golden)
+        .unwrap());
+    assert!(!Comparison::new()
+        .tolerance(3)
+        .matches(&actual, &golden)
+        .unwrap());
+}
+
+#[test]
+fn masks() {
+    let golden = png(4, 4, |_, _| [0, 0, 0, 255]);
+    let actual = png(4, 4, |x, y| {
+        if x < 2 && y < 2 {
+            [255, 255, 255, 255]
+        } else {
+            [0, 0, 0, 255]
+        }
+    });
+    let diff = Comparison::new().diff(&actual, &golden).unwrap();
+    assert_eq!(diff.differing, 4);
+    assert_eq!(diff.ratio(), 0.25);
+    assert!(Comparison::new()
+        .max_ratio(0.25)
+        .matches(&actual, &golden)
+        .unwrap());
+    let masked = Comparison::new()
+        .mask(Region::new(0, 0, 2, 2))
+        .diff(&actual, &golden)
+        .unwrap();
+    assert_eq!(masked.compared, 12);
+    assert_eq!(masked.differing, 0);
+}
+
+#[test]
+fn size_mismatch() {
+    let golden = png(4, 4, |_, _| [0, 0, 0, 255]);
+    let actual = png(2, 4, |_, _| [0, 0, 0, 255]);
+    assert!(Comparison::new().diff(&actual, &golden).is_err());
+}
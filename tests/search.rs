@@ -0,0 +1,217 @@
+use proptest::{option, prelude::*};
+use roku::{App, Search, SearchType};
+use url::{form_urlencoded, Url};
+
+#[derive(Clone, Debug)]
+struct Params {
+    keyword: String,
+    launch: Option<bool>,
+    match_any: Option<bool>,
+    providers: Vec<String>,
+    provider_ids: Vec<String>,
+    search_type: Option<SearchType>,
+    season: Option<u32>,
+    show_unavailable: Option<bool>,
+    title: Option<String>,
+    tmsid: Option<String>,
+}
+
+impl Params {
+    fn search(&self) -> Search {
+        let mut search = Search::new(self.keyword.clone());
+        if let Some(launch) = self.launch {
+            search.launch(launch);
+        }
+        if let Some(match_any) = self.match_any {
+            search.match_any(match_any);
+        }
+        for provider in &self.providers {
+            search.provider(provider.clone());
+        }
+        for provider_id in &self.provider_ids {
+            search.provider_id(provider_id.clone());
+        }
+        if let Some(search_type) = self.search_type {
+            search.search_type(search_type);
+        }
+        if let Some(season) = self.season {
+            search.season(season);
+        }
+        if let Some(show_unavailable) = self.show_unavailable {
+            search.show_unavailable(show_unavailable);
+        }
+        if let Some(title) = &self.title {
+            search.title(title.clone());
+        }
+        if let Some(tmsid) = &self.tmsid {
+            search.tmsid(tmsid.clone());
+        }
+        search
+    }
+
+    fn expected(&self) -> Vec<(String, String)> {
+        let mut ret = vec![("keyword", self.keyword.clone())];
+        if let Some(launch) = self.launch {
+            ret.push(("launch", launch.to_string()));
+        }
+        if let Some(match_any) = self.match_any {
+            ret.push(("match-any", match_any.to_string()));
+        }
+        if !self.provider_ids.is_empty() {
+            ret.push(("provider-id", self.provider_ids.join(",")));
+        }
+        if !self.providers.is_empty() {
+            ret.push(("provider", self.providers.join(",")));
+        }
+        if let Some(search_type) = self.search_type {
+            let search_type = match search_type {
+                SearchType::Movie => "movie",
+                SearchType::TvShow => "tv-show",
+                SearchType::Person => "person",
+                SearchType::Channel => "channel",
+                SearchType::Game => "game",
+            };
+            ret.push(("type", search_type.to_string()));
+        }
+        if let Some(season) = self.season {
+            ret.push(("season", season.to_string()));
+        }
+        if let Some(show_unavailable) = self.show_unavailable {
+            ret.push(("show-unavailable", show_unavailable.to_string()));
+        }
+        if let Some(title) = &self.title {
+            ret.push(("title", title.clone()));
+        }
+        if let Some(tmsid) = &self.tmsid {
+            ret.push(("tmsid", tmsid.clone()));
+        }
+        ret.into_iter().map(|(k, v)| (k.to_string(), v)).collect()
+    }
+}
+
+fn search_type() -> impl Strategy<Value = SearchType> {
+    prop_oneof![
+        Just(SearchType::Movie),
+        Just(SearchType::TvShow),
+        Just(SearchType::Person),
+        Just(SearchType::Channel),
+        Just(SearchType::Game),
+    ]
+}
+
+fn text() -> impl Strategy<Value = String> {
+    any::<String>()
+}
+
+fn provider() -> impl Strategy<Value = String> {
+    "[^,]+"
+}
+
+prop_compose! {
+    fn params()(
+        keyword in text(),
+        launch in option::of(any::<bool>()),
+        match_any in option::of(any::<bool>()),
+        providers in prop::collection::vec(provider(), 0..4),
+        provider_ids in prop::collection::vec(provider(), 0..4),
+        search_type in option::of(search_type()),
+        season in option::of(any::<u32>()),
+        show_unavailable in option::of(any::<bool>()),
+        title in option::of(text()),
+        tmsid in option::of(text()),
+    ) -> Params {
+        Params {
+            keyword,
+            launch,
+            match_any,
+            providers,
+            provider_ids,
+            search_type,
+            season,
+            show_unavailable,
+            title,
+            tmsid,
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn query_pairs(params in params()) {
+        prop_assert_eq!(params.search().into_query_pairs(), params.expected());
+    }
+
+    #[test]
+    fn query_pairs_survive_encoding(params in params()) {
+        let pairs = params.search().into_query_pairs();
+        let mut url = Url::parse("http://127.0.0.1:8060/search").unwrap();
+        url.query_pairs_mut().extend_pairs(&pairs);
+        let query = url.query().unwrap_or_default();
+        let decoded: Vec<(String, String)> = form_urlencoded::parse(query.as_bytes())
+            .into_owned()
+            .collect();
+        prop_assert_eq!(decoded, pairs);
+    }
+
+    #[test]
+    fn providers_are_joined(providers in prop::collection::vec(provider(), 1..6)) {
+        let mut search = Search::new("keyword".to_string());
+        for provider in &providers {
+            search.provider(provider.clone());
+        }
+        let pairs = search.into_query_pairs();
+        let joined = pairs
+            .iter()
+            .find(|(key, _)| key == "provider")
+            .map(|(_, value)| value.split(',').map(String::from).collect::<Vec<_>>());
+        prop_assert_eq!(joined, Some(providers));
+    }
+}
+
+fn installed() -> Vec<App> {
+    [
+        ("12", "Netflix"),
+        ("2285", "Hulu"),
+        ("tvinput.dtv", "Antenna TV"),
+    ]
+    .iter()
+    .map(|(id, name)| App {
+        id: Some(id.to_string()),
+        name: name.to_string(),
+        version: None,
+    })
+    .collect()
+}
+
+fn value(pairs: &[(String, String)], key: &str) -> Option<String> {
+    pairs
+        .iter()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.clone())
+}
+
+#[test]
+fn resolves_provider_names() {
+    let mut search = Search::new("stranger things".to_string());
+    search
+        .provider("netflix".to_string())
+        .provider("Peacock".to_string())
+        .resolve_providers(true);
+    search.resolve(&installed()).unwrap();
+    let pairs = search.into_query_pairs();
+    assert_eq!(value(&pairs, "provider-id").as_deref(), Some("12"));
+    assert_eq!(value(&pairs, "provider").as_deref(), Some("Peacock"));
+}
+
+#[test]
+fn restricts_to_installed() {
+    let mut search = Search::new("the bear".to_string());
+    search.installed_only(true);
+    search.resolve(&installed()).unwrap();
+    let pairs = search.into_query_pairs();
+    assert_eq!(value(&pairs, "provider-id").as_deref(), Some("12,2285"));
+
+    let mut search = Search::new("the bear".to_string());
+    search.provider_id("61322".to_string()).installed_only(true);
+    assert!(search.resolve(&installed()).is_err());
+}
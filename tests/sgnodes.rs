@@ -0,0 +1,83 @@
+use roku::{
+    testing::{Exchange, FakeRoku, Replay},
+    NodeTree,
+};
+use std::fs;
+
+mod common;
+
+fn tree(name: &str) -> NodeTree {
+    let path = common::fixture_dir("sgnodes").join(name);
+    NodeTree::parse(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn parse_tree() {
+    let tree = tree("all.xml");
+    assert_eq!(tree.roots.len(), 3);
+    assert_eq!(tree.count(), 7);
+    let scene = &tree.roots[0];
+    assert_eq!(scene.node_type, "HomeScene");
+    assert_eq!(scene.id.as_deref(), Some("HomeScene"));
+    assert_eq!(scene.children.len(), 2);
+    assert_eq!(scene.fields["extends"], "Scene");
+}
+
+#[test]
+fn find_by_id() {
+    let tree = tree("all.xml");
+    let rows = tree.find_by_id("rows").unwrap();
+    assert_eq!(rows.node_type, "RowList");
+    assert_eq!(rows.children.len(), 2);
+    assert_eq!(rows.field_count(), 6);
+    assert!(tree.find_by_id("missing").is_none());
+}
+
+#[test]
+fn count_by_type() {
+    let counts = tree("all.xml").count_by_type();
+    assert_eq!(counts["ContentNode"], 3);
+    assert_eq!(counts["Timer"], 1);
+}
+
+#[test]
+fn diff_counts() {
+    let diff = tree("all.xml").diff_counts(&tree("all-later.xml"));
+    assert_eq!(diff.len(), 2);
+    assert_eq!(diff["ContentNode"], 2);
+    assert_eq!(diff["Timer"], -1);
+}
+
+#[tokio::test]
+async fn device_queries() {
+    let xml = |name: &str| fs::read_to_string(common::fixture_dir("sgnodes").join(name)).unwrap();
+    let exchange = |path: &str, body: String| Exchange {
+        method: "GET".to_string(),
+        path: path.to_string(),
+        status: 200,
+        content_type: Some("text/xml; charset=\"utf-8\"".to_string()),
+        body,
+    };
+    let replay = Replay::start(vec![
+        exchange("/query/sgnodes/all", xml("all.xml")),
+        exchange("/query/sgnodes/nodes?node-id=rows", xml("all.xml")),
+    ])
+    .await
+    .unwrap();
+    let device = replay.device();
+    assert_eq!(device.sgnodes_all().await.unwrap(), tree("all.xml"));
+    assert_eq!(device.sgnodes_node("rows").await.unwrap().count(), 7);
+    assert!(device.sgnodes_roots().await.is_err());
+}
+
+#[tokio::test]
+async fn rendezvous_tracking() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+    assert!(!device.sgrendezvous().await.unwrap().tracking_enabled);
+    device.track_sgrendezvous().await.unwrap();
+    assert!(fake.rendezvous_tracking());
+    assert!(device.sgrendezvous().await.unwrap().tracking_enabled);
+    device.untrack_sgrendezvous().await.unwrap();
+    assert!(!device.sgrendezvous().await.unwrap().tracking_enabled);
+}
@@ -0,0 +1,45 @@
+use roku::SideloadResult;
+use std::fs;
+
+mod common;
+
+fn parse(name: &str) -> SideloadResult {
+    let path = common::fixture_dir("plugin-install").join(name);
+    SideloadResult::parse(&fs::read_to_string(path).unwrap())
+}
+
+#[test]
+fn success() {
+    assert_eq!(parse("success.html"), SideloadResult::Installed);
+    assert_eq!(parse("legacy-success.html"), SideloadResult::Installed);
+}
+
+#[test]
+fn identical() {
+    let result = parse("identical.html");
+    assert_eq!(result, SideloadResult::Identical);
+    assert!(result.is_success());
+}
+
+#[test]
+fn compilation_failed() {
+    let result = parse("compilation-failed.html");
+    assert!(!result.is_success());
+    assert_eq!(
+        result,
+        SideloadResult::CompilationFailed(vec![
+            "Install Failure: Compilation Failed.".to_string(),
+            "Syntax Error. (compile error &h02) in pkg:/source/main.brs(12)".to_string(),
+            "Variable used as function name. (compile error &h2b) in pkg:/components/Home.brs(40)"
+                .to_string(),
+        ])
+    );
+}
+
+#[test]
+fn failed() {
+    assert_eq!(
+        parse("no-archive.html"),
+        SideloadResult::Failed(vec!["Failed: no archive uploaded".to_string()])
+    );
+}
@@ -0,0 +1,41 @@
+use roku::{
+    testing::{models, FakeRoku},
+    FieldChange,
+};
+
+#[tokio::test]
+async fn snapshot_diff() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+    let before = device.snapshot().await.unwrap();
+    assert!(before.diff(&before).is_empty());
+
+    let app = models::app("12", "Netflix");
+    fake.set_active_app(Some(app.clone()));
+    fake.set_player_state("play");
+    fake.set_device_info("power-mode", "DisplayOff");
+    let after = device.snapshot().await.unwrap();
+    let changes = before.diff(&after);
+    assert_eq!(changes.len(), 3);
+    assert_eq!(
+        changes[0],
+        FieldChange::PowerMode {
+            old: "PowerOn".to_string(),
+            new: "DisplayOff".to_string(),
+        }
+    );
+    assert!(matches!(&changes[1], FieldChange::ActiveApp { new, .. } if new.name == app.name));
+    assert!(matches!(&changes[2], FieldChange::PlaybackState { new, .. } if new == "play"));
+}
+
+#[test]
+fn diff_serializes_field_name() {
+    let change = FieldChange::HeadphonesConnected {
+        old: false,
+        new: true,
+    };
+    assert_eq!(
+        serde_json::to_string(&change).unwrap(),
+        r#"{"field":"headphones-connected","old":false,"new":true}"#
+    );
+}
@@ -0,0 +1,80 @@
+use roku::{
+    store::{ChannelInfo, ChannelStore},
+    testing::{Exchange, Fault, FaultProxy, Replay},
+    App, Apps, RokuConfig,
+};
+use std::time::{Duration, Instant};
+
+fn exchange(path: &str, status: u16, body: &str) -> Exchange {
+    Exchange {
+        method: "GET".to_string(),
+        path: path.to_string(),
+        status,
+        content_type: Some("application/json".to_string()),
+        body: body.to_string(),
+    }
+}
+
+async fn store() -> (Replay, ChannelStore) {
+    let replay = Replay::start(vec![
+        exchange(
+            "/channels/detailsunion/12",
+            200,
+            r#"{"details":{"id":"12","name":"Netflix","iconUrl":"https://image.roku.com/netflix.png","description":"Watch TV shows and movies.","categories":[{"name":"Movies & TV"}]}}"#,
+        ),
+        exchange("/channels/detailsunion/999", 404, ""),
+    ])
+    .await
+    .unwrap();
+    let store = ChannelStore::with_base_url(replay.url().clone());
+    (replay, store)
+}
+
+fn app(id: Option<&str>, name: &str) -> App {
+    App {
+        id: id.map(|id| id.to_string()),
+        name: name.to_string(),
+        version: None,
+    }
+}
+
+#[tokio::test]
+async fn details() {
+    let (_replay, store) = store().await;
+    assert_eq!(
+        store.details("12").await.unwrap(),
+        Some(ChannelInfo {
+            id: "12".to_string(),
+            name: "Netflix".to_string(),
+            icon_url: Some("https://image.roku.com/netflix.png".to_string()),
+            description: Some("Watch TV shows and movies.".to_string()),
+            category: Some("Movies & TV".to_string()),
+        })
+    );
+    assert_eq!(store.details("999").await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn enrich() {
+    let (_replay, store) = store().await;
+    let apps = Apps {
+        apps: vec![app(Some("12"), "Netflix"), app(None, "Input")],
+    };
+    let enriched = store.enrich(&apps).await.unwrap();
+    assert_eq!(enriched.len(), 2);
+    assert_eq!(enriched[0].1.as_ref().unwrap().name, "Netflix");
+    assert_eq!(enriched[1], (app(None, "Input"), None));
+}
+
+#[tokio::test]
+async fn inherits_global_timeout() {
+    let (replay, _store) = store().await;
+    let proxy = FaultProxy::start(replay.url().clone()).await.unwrap();
+    RokuConfig::set_global(RokuConfig::new().timeout(Duration::from_millis(50)).clone()).unwrap();
+    let store = ChannelStore::with_base_url(proxy.url().clone());
+    RokuConfig::set_global(RokuConfig::new()).unwrap();
+    proxy.inject(Fault::Hang);
+    let started = Instant::now();
+    assert!(store.details("12").await.is_err());
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
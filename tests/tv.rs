@@ -0,0 +1,227 @@
+use roku::{
+    testing::{Exchange, FakeRoku, Replay},
+    App, ChannelScan, DeviceClass, Error, Script, TvChannel, TvChannels,
+};
+use std::time::Duration;
+
+fn channel(number: &str, name: &str) -> TvChannel {
+    TvChannel {
+        number: number.to_string(),
+        name: name.to_string(),
+        channel_type: "air-digital".to_string(),
+        user_hidden: false,
+        user_favorite: false,
+        physical_channel: None,
+        physical_frequency: None,
+    }
+}
+
+fn scan(script: &str) -> ChannelScan {
+    let mut scan = ChannelScan::new();
+    scan.script(Script::parse(script).unwrap())
+        .interval(Duration::from_millis(10))
+        .stable_polls(2)
+        .min_duration(Duration::ZERO)
+        .timeout(Duration::from_secs(5));
+    scan
+}
+
+#[tokio::test]
+async fn scan_settles() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_device_info("is-tv", "true");
+    fake.set_tv_channels(vec![channel("2.1", "KTVU-HD"), channel("4.1", "KRON-HD")]);
+    let lineup = scan("key home\nkey select")
+        .run(&fake.device())
+        .await
+        .unwrap();
+    assert_eq!(lineup.channels.len(), 2);
+    let requests = fake.requests();
+    assert_eq!(requests[0], "GET /query/device-info");
+    assert_eq!(requests[1], "POST /keypress/Home");
+    assert_eq!(requests[2], "POST /keypress/Select");
+    assert_eq!(
+        requests
+            .iter()
+            .filter(|request| *request == "GET /query/tv-channels")
+            .count(),
+        3
+    );
+}
+
+#[tokio::test]
+async fn scan_default_setup() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_device_info("is-tv", "true");
+    fake.set_tv_channels(vec![channel("2.1", "KTVU-HD")]);
+    let mut scan = ChannelScan::new();
+    scan.interval(Duration::from_millis(10))
+        .stable_polls(1)
+        .min_duration(Duration::ZERO);
+    assert_eq!(scan.run(&fake.device()).await.unwrap().channels.len(), 1);
+    let keys: Vec<_> = fake
+        .requests()
+        .into_iter()
+        .filter_map(|request| request.strip_prefix("POST /keypress/").map(str::to_string))
+        .collect();
+    assert_eq!(keys[0], "Home");
+    assert_eq!(keys.iter().filter(|key| *key == "Down").count(), 11);
+    assert_eq!(keys.iter().filter(|key| *key == "Select").count(), 5);
+    assert_eq!(keys.last().unwrap(), "Select");
+}
+
+#[tokio::test]
+async fn scan_times_out() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_device_info("is-tv", "true");
+    let mut scan = scan("key select");
+    scan.min_duration(Duration::from_secs(60))
+        .timeout(Duration::from_millis(50));
+    assert!(scan.run(&fake.device()).await.is_err());
+}
+
+fn live_tv() -> App {
+    App {
+        id: Some("tvinput.dtv".to_string()),
+        name: "Live TV".to_string(),
+        version: None,
+    }
+}
+
+#[tokio::test]
+async fn pause_and_resume_live_tv() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_device_info("is-tv", "true");
+    fake.set_active_app(Some(live_tv()));
+    fake.set_player_state("play");
+    let device = fake.device();
+    device.pause_live_tv().await.unwrap();
+    device.pause_live_tv().await.unwrap();
+    device.resume_live_tv().await.unwrap();
+    let keypresses: Vec<_> = fake
+        .requests()
+        .into_iter()
+        .filter(|request| request.starts_with("POST /keypress/"))
+        .collect();
+    assert_eq!(
+        keypresses,
+        vec!["POST /keypress/Play", "POST /keypress/Play"]
+    );
+}
+
+#[tokio::test]
+async fn pause_requires_live_tv() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_device_info("is-tv", "true");
+    fake.set_player_state("play");
+    assert!(fake.device().pause_live_tv().await.is_err());
+}
+
+#[tokio::test]
+async fn lineup_diff() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_device_info("is-tv", "true");
+    let mut kron = channel("4.1", "KRON-HD");
+    fake.set_tv_channels(vec![channel("2.1", "KTVU-HD"), kron.clone()]);
+    let before = fake.device().tv_channels().await.unwrap();
+    assert!(before.diff(&before).is_empty());
+
+    kron.user_favorite = true;
+    let after = TvChannels {
+        channels: vec![kron.clone(), channel("9.1", "KQED-HD")],
+    };
+    let diff = before.diff(&after);
+    assert_eq!(diff.added, vec![channel("9.1", "KQED-HD")]);
+    assert_eq!(diff.removed, vec![channel("2.1", "KTVU-HD")]);
+    assert_eq!(diff.changed, vec![(channel("4.1", "KRON-HD"), kron)]);
+}
+
+#[test]
+fn lineup_json_round_trip() {
+    let mut kron = channel("4.1", "KRON-HD");
+    kron.user_hidden = true;
+    let lineup = TvChannels {
+        channels: vec![channel("2.1", "KTVU-HD"), kron],
+    };
+    let json = serde_json::to_string(&lineup).unwrap();
+    assert_eq!(serde_json::from_str::<TvChannels>(&json).unwrap(), lineup);
+}
+
+#[tokio::test]
+async fn tune() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_device_info("is-tv", "true");
+    fake.set_apps(vec![live_tv()]);
+    let device = fake.device();
+    device.tune("4.1").await.unwrap();
+    assert_eq!(
+        fake.requests().last().unwrap(),
+        "POST /launch/tvinput.dtv?ch=4.1"
+    );
+    assert_eq!(fake.active_app(), Some(live_tv()));
+}
+
+#[tokio::test]
+async fn tv_only_on_players() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+    assert_eq!(device.device_class().await.unwrap(), DeviceClass::Player);
+    assert!(matches!(
+        device.tv_channels().await,
+        Err(Error::NotSupported(_))
+    ));
+    assert!(matches!(
+        device.pause_live_tv().await,
+        Err(Error::NotSupported(_))
+    ));
+    assert!(matches!(
+        device.tune("2.1").await,
+        Err(Error::NotSupported(_))
+    ));
+    let queries = fake
+        .requests()
+        .iter()
+        .filter(|request| *request == "GET /query/device-info")
+        .count();
+    assert_eq!(queries, 1);
+
+    let soundbar = FakeRoku::start().await.unwrap();
+    soundbar.set_device_info("model-name", "Roku Smart Soundbar");
+    let device = soundbar.device();
+    assert_eq!(device.device_class().await.unwrap(), DeviceClass::Audio);
+    assert!(matches!(
+        device.tv_active_channel().await,
+        Err(Error::NotSupported(_))
+    ));
+}
+
+#[tokio::test]
+async fn classifies_sparse_device_info() {
+    let replay = Replay::start(vec![Exchange {
+        method: "GET".to_string(),
+        path: "/query/device-info".to_string(),
+        status: 200,
+        content_type: Some("text/xml; charset=\"utf-8\"".to_string()),
+        body: "<device-info><model-name>Roku Wireless Speakers</model-name>\
+               <is-tv>false</is-tv></device-info>"
+            .to_string(),
+    }])
+    .await
+    .unwrap();
+    let device = replay.device();
+    assert!(device.device_info().await.is_err());
+    assert_eq!(device.device_class().await.unwrap(), DeviceClass::Audio);
+}
+
+#[tokio::test]
+async fn typed_lineup() {
+    let fake = FakeRoku::start().await.unwrap();
+    fake.set_device_info("is-tv", "true");
+    let mut ktvu = channel("2.1", "KTVU-HD");
+    ktvu.physical_channel = Some(31);
+    ktvu.physical_frequency = Some(575000);
+    fake.set_tv_channels(vec![ktvu.clone(), channel("4.1", "KRON-HD")]);
+    let lineup = fake.device().tv_channels().await.unwrap();
+    assert_eq!(lineup.channels[0], ktvu);
+    assert_eq!(lineup.channels[1].physical_channel, None);
+}
@@ -0,0 +1,89 @@
+use roku::{
+    testing::{Exchange, FakeRoku, Replay},
+    Device, Error, Key,
+};
+use url::Url;
+
+fn exchange(method: &str, path: &str, body: &str) -> Exchange {
+    Exchange {
+        method: method.to_string(),
+        path: path.to_string(),
+        status: 200,
+        content_type: Some("text/xml; charset=\"utf-8\"".to_string()),
+        body: body.to_string(),
+    }
+}
+
+#[test]
+fn base_path_normalized() {
+    let device = Device::new(Url::parse("http://tunnel.local:9000/roku").unwrap());
+    assert_eq!(device.url().as_str(), "http://tunnel.local:9000/roku/");
+
+    let device = Device::new(Url::parse("http://192.168.1.20:8060/").unwrap())
+        .with_port(18060)
+        .unwrap()
+        .with_base_path("/living-room/ecp");
+    assert_eq!(
+        device.url().as_str(),
+        "http://192.168.1.20:18060/living-room/ecp/"
+    );
+    assert_eq!(
+        device.with_base_path("").url().as_str(),
+        "http://192.168.1.20:18060/"
+    );
+}
+
+#[tokio::test]
+async fn requests_keep_prefix() {
+    let replay = Replay::start(vec![
+        exchange("POST", "/roku/keypress/Home", ""),
+        exchange("POST", "/roku/keypress/Lit_%3F", ""),
+        exchange(
+            "GET",
+            "/roku/query/active-app",
+            "<active-app><app>Roku</app></active-app>",
+        ),
+    ])
+    .await
+    .unwrap();
+    let mut url = replay.url().clone();
+    url.set_path("/roku");
+    let device = Device::new(url);
+    device.keypress(&Key::Home).await.unwrap();
+    device.keypress(&Key::Lit('?')).await.unwrap();
+    assert_eq!(device.active_app().await.unwrap().app.name, "Roku");
+    assert!(replay.remaining().is_empty());
+}
+
+#[tokio::test]
+async fn custom_port() {
+    let fake = FakeRoku::start().await.unwrap();
+    let port = fake.url().port().unwrap();
+    let device = Device::new(Url::parse("http://127.0.0.1/").unwrap())
+        .with_port(port)
+        .unwrap();
+    assert_eq!(device.apps().await.unwrap().apps.len(), 3);
+
+    let device = Device::new(Url::parse("file:///tmp/roku").unwrap());
+    assert!(matches!(device.with_port(8060), Err(Error::Argument(_))));
+}
+
+#[tokio::test]
+async fn literal_keys_are_encoded() {
+    let fake = FakeRoku::start().await.unwrap();
+    let device = fake.device();
+    for c in " &#é".chars() {
+        device.keypress(&Key::Lit(c)).await.unwrap();
+    }
+    assert_eq!(
+        fake.requests(),
+        vec![
+            "POST /keypress/Lit_%20",
+            "POST /keypress/Lit_%26",
+            "POST /keypress/Lit_%23",
+            "POST /keypress/Lit_%C3%A9",
+        ]
+    );
+    assert_eq!("Lit_%C3%A9".parse::<Key>().unwrap(), Key::Lit('é'));
+    assert_eq!("Lit_%20".parse::<Key>().unwrap(), Key::Lit(' '));
+}